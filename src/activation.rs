@@ -0,0 +1,232 @@
+use std::arch::x86_64::*;
+use super::*;
+
+
+unroll_fn!(sigmoid, sigmoid_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(softplus, softplus_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(log_sigmoid, log_sigmoid_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(gelu, gelu_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(gelu_tanh, gelu_tanh_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(silu, silu_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(swish, swish_intr, [x] [beta: f64] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(elu, elu_intr, [x] [alpha: f64] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(selu, selu_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(mish, mish_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_sigmoid_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    sigmoid_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_softplus_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    softplus_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_log_sigmoid_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    log_sigmoid_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_gelu_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    gelu_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_gelu_tanh_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    gelu_tanh_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_silu_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    silu_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_swish_pd(x: __m512d, beta: f64) -> __m512d
+{
+    let mut y = D512_ZERO;
+    swish_intr(&x, beta, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_elu_pd(x: __m512d, alpha: f64) -> __m512d
+{
+    let mut y = D512_ZERO;
+    elu_intr(&x, alpha, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_selu_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    selu_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_mish_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    mish_intr(&x, &mut y);
+    y
+}
+
+
+/// AVX-512 implementation of the logistic function, 1/(1 + e^-x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn sigmoid_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = sigmoid_stable(*x);
+}
+
+/// AVX-512 implementation of softplus, ln(1 + e^x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn softplus_intr(x: &__m512d, y: &mut __m512d)
+{
+    // max(x, 0) + ln(1 + e^-|x|), which neither overflows nor loses the small e^x for very negative x
+    *y = _mm512_add_pd(_mm512_max_pd(*x, D512_ZERO), log1p_exp_neg_abs(*x));
+}
+
+/// AVX-512 implementation of ln(sigmoid(x)) = -softplus(-x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn log_sigmoid_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_sub_pd(_mm512_min_pd(*x, D512_ZERO), log1p_exp_neg_abs(*x));
+}
+
+/// AVX-512 implementation of the exact GELU, x Phi(x) with Phi the standard normal CDF.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gelu_intr(x: &__m512d, y: &mut __m512d)
+{
+    // Phi(x) = erfc(-x/sqrt(2))/2 keeps its relative precision in the left tail, where 1 + erf() would not
+    let mut c = D512_ZERO;
+    erfc_intr(&_mm512_mul_pd(*x, D512_NEG_ONE_OVER_SQRT2), &mut c);
+    *y = gated(*x, _mm512_mul_pd(c, D512_HALF));
+}
+
+/// AVX-512 implementation of the tanh approximation of GELU, x (1 + tanh(sqrt(2/pi) (x + 0.044715 x^3)))/2.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gelu_tanh_intr(x: &__m512d, y: &mut __m512d)
+{
+    // (1 + tanh(u))/2 = sigmoid(2u)
+    let x2 = _mm512_mul_pd(*x, *x);
+    let u2 = _mm512_mul_pd(_mm512_fmadd_pd(x2, D512_GELU_CUBIC, D512_ONE), _mm512_mul_pd(*x, D512_TWO_SQRT_2_OVER_PI));
+    *y = gated(*x, sigmoid_stable(u2));
+}
+
+/// AVX-512 implementation of SiLU, x sigmoid(x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn silu_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = gated(*x, sigmoid_stable(*x));
+}
+
+/// AVX-512 implementation of swish, x sigmoid(beta x). beta = 1 is SiLU.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn swish_intr(x: &__m512d, beta: f64, y: &mut __m512d)
+{
+    *y = gated(*x, sigmoid_stable(_mm512_mul_pd(*x, _mm512_set1_pd(beta))));
+}
+
+/// AVX-512 implementation of ELU, x for x > 0 and alpha (e^x - 1) otherwise.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn elu_intr(x: &__m512d, alpha: f64, y: &mut __m512d)
+{
+    *y = elu_alpha(*x, _mm512_set1_pd(alpha));
+}
+
+/// AVX-512 implementation of SELU, lambda elu(x, alpha) with the self-normalizing lambda and alpha.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn selu_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_mul_pd(elu_alpha(*x, D512_SELU_ALPHA), D512_SELU_LAMBDA);
+}
+
+/// AVX-512 implementation of mish, x tanh(softplus(x)).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn mish_intr(x: &__m512d, y: &mut __m512d)
+{
+    // with n = e^x (e^x + 2), tanh(ln(1 + e^x)) = n/(n + 2), which only needs the one exp. Past x = 20 it is 1 to
+    // double precision, and n would go on to overflow.
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_min_pd(*x, D512_MISH_MAX), &mut e);
+    let n = _mm512_mul_pd(e, _mm512_add_pd(e, D512_TWO));
+    let t = _mm512_div_pd(n, _mm512_add_pd(n, D512_TWO));
+    *y = _mm512_mask_blend_pd(_mm512_cmp_pd_mask(*x, D512_MISH_MAX, _CMP_GT_OQ), gated(*x, t), *x);
+}
+
+/// 1/(1 + e^-x), from e^-|x| so that nothing overflows.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn sigmoid_stable(x: __m512d) -> __m512d
+{
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_sub_pd(D512_ZERO, _mm512_abs_pd(x)), &mut e);
+    let s = _mm512_div_pd(D512_ONE, _mm512_add_pd(D512_ONE, e));
+    _mm512_mask_mul_pd(s, _mm512_cmplt_pd_mask(x, D512_ZERO), e, s)
+}
+
+/// ln(1 + e^-|x|)
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn log1p_exp_neg_abs(x: __m512d) -> __m512d
+{
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_sub_pd(D512_ZERO, _mm512_abs_pd(x)), &mut e);
+    log1p(e)
+}
+
+/// x g, taking an infinite x with a gate that has gone to 0 as the 0 it is the limit of rather than NaN.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn gated(x: __m512d, g: __m512d) -> __m512d
+{
+    let zero_mask = _mm512_cmpeq_pd_mask(_mm512_abs_pd(x), D512_POSITIVE_INFINITY) & _mm512_cmpeq_pd_mask(g, D512_ZERO);
+    _mm512_mask_blend_pd(zero_mask, _mm512_mul_pd(x, g), _mm512_and_pd(x, D512_NEGATIVE_ZERO))
+}
+
+/// x for x > 0 and alpha (e^x - 1) otherwise.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn elu_alpha(x: __m512d, alpha: __m512d) -> __m512d
+{
+    let neg = _mm512_mul_pd(alpha, expm1(_mm512_min_pd(D512_ZERO, x)));
+    _mm512_mask_blend_pd(_mm512_cmp_pd_mask(x, D512_ZERO, _CMP_GT_OQ), neg, x)
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_NEGATIVE_ZERO: __m512d = m64x8_constant!(-0.0);
+const D512_HALF: __m512d = m64x8_constant!(0.5);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_TWO: __m512d = m64x8_constant!(2.0);
+const D512_NEG_ONE_OVER_SQRT2: __m512d = m64x8_constant!(-std::f64::consts::FRAC_1_SQRT_2);
+const D512_GELU_CUBIC: __m512d = m64x8_constant!(0.044715);
+const D512_TWO_SQRT_2_OVER_PI: __m512d = m64x8_constant!(1.5957691216057308);
+const D512_SELU_ALPHA: __m512d = m64x8_constant!(1.6732632423543772);
+const D512_SELU_LAMBDA: __m512d = m64x8_constant!(1.0507009873554805);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_MISH_MAX: __m512d = m64x8_constant!(20.0);
@@ -0,0 +1,169 @@
+use std::arch::x86_64::*;
+use super::*;
+
+
+unroll_fn_n!(add, add_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(sub, sub_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(mul, mul_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(div, div_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(sqr, sqr_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(abs, abs_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(fmax, fmax_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(fmin, fmin_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(copysign, copysign_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(nextafter, nextafter_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(linear_frac, linear_frac_intr, [x0, x1] [scale0: f64, shift0: f64, scale1: f64, shift1: f64] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+
+
+// add, sub, mul, div, abs, max and min are already intrinsics, so only the rest get an _mm512_*_pd form here.
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_sqr_pd(x: __m512d) -> __m512d
+{
+    _mm512_mul_pd(x, x)
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_fmax_pd(x0: __m512d, x1: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    fmax_intr(&x0, &x1, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_fmin_pd(x0: __m512d, x1: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    fmin_intr(&x0, &x1, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_copysign_pd(x0: __m512d, x1: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    copysign_intr(&x0, &x1, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_nextafter_pd(x0: __m512d, x1: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    nextafter_intr(&x0, &x1, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_linear_frac_pd(x0: __m512d, x1: __m512d, scale0: f64, shift0: f64, scale1: f64, shift1: f64) -> __m512d
+{
+    let mut y = D512_ZERO;
+    linear_frac_intr(&x0, &x1, scale0, shift0, scale1, shift1, &mut y);
+    y
+}
+
+
+/// x0 + x1
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn add_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_add_pd(*x0, *x1);
+}
+
+/// x0 - x1
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn sub_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_sub_pd(*x0, *x1);
+}
+
+/// x0 * x1
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn mul_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_mul_pd(*x0, *x1);
+}
+
+/// x0 / x1
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn div_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_div_pd(*x0, *x1);
+}
+
+/// x * x
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn sqr_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_mul_pd(*x, *x);
+}
+
+/// |x|
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn abs_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_abs_pd(*x);
+}
+
+/// The larger of x0 and x1, or the other one if either is NaN, like C's fmax.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn fmax_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    // _mm512_max_pd gives its second operand when either is NaN
+    let m = _mm512_max_pd(*x0, *x1);
+    *y = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x1, *x1), m, *x0);
+}
+
+/// The smaller of x0 and x1, or the other one if either is NaN, like C's fmin.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn fmin_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    let m = _mm512_min_pd(*x0, *x1);
+    *y = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x1, *x1), m, *x0);
+}
+
+/// |x0| with the sign of x1.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn copysign_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_or_pd(_mm512_abs_pd(*x0), _mm512_and_pd(*x1, D512_NEGATIVE_ZERO));
+}
+
+/// The next double after x0 in the direction of x1, or x1 if they are equal, like C's nextafter.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn nextafter_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    // stepping the bits of a nonzero double by one moves its magnitude by one ulp, infinity and subnormals included
+    let lt_mask = _mm512_cmp_pd_mask(*x0, *x1, _CMP_LT_OQ);
+    let gt_mask = _mm512_cmp_pd_mask(*x0, *x1, _CMP_GT_OQ);
+    let pos_mask = _mm512_cmp_pd_mask(*x0, D512_ZERO, _CMP_GT_OQ);
+    let neg_mask = _mm512_cmp_pd_mask(*x0, D512_ZERO, _CMP_LT_OQ);
+    let up_mask = (lt_mask & pos_mask) | (gt_mask & neg_mask);
+    let down_mask = (gt_mask & pos_mask) | (lt_mask & neg_mask);
+
+    let bits = _mm512_castpd_si512(*x0);
+    let mut yb = _mm512_mask_add_epi64(bits, up_mask, bits, _mm512_set1_epi64(1));
+    yb = _mm512_mask_sub_epi64(yb, down_mask, yb, _mm512_set1_epi64(1));
+    let mut yy = _mm512_castsi512_pd(yb);
+
+    // from zero, the smallest subnormal toward x1; where x0 == x1, x1 itself; NaN if either is NaN
+    let tiny = _mm512_or_pd(D512_MIN_SUBNORMAL, _mm512_and_pd(*x1, D512_NEGATIVE_ZERO));
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*x0, D512_ZERO) & (lt_mask | gt_mask), yy, tiny);
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*x0, *x1), yy, *x1);
+    *y = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x0, *x1), yy, _mm512_add_pd(*x0, *x1));
+}
+
+/// (scale0 x0 + shift0)/(scale1 x1 + shift1), MKL's LinearFrac.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn linear_frac_intr(x0: &__m512d, x1: &__m512d, scale0: f64, shift0: f64, scale1: f64, shift1: f64, y: &mut __m512d)
+{
+    let num = _mm512_fmadd_pd(_mm512_set1_pd(scale0), *x0, _mm512_set1_pd(shift0));
+    let den = _mm512_fmadd_pd(_mm512_set1_pd(scale1), *x1, _mm512_set1_pd(shift1));
+    *y = _mm512_div_pd(num, den);
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_NEGATIVE_ZERO: __m512d = m64x8_constant!(-0.0);
+const D512_MIN_SUBNORMAL: __m512d = m64x8_constant!(5e-324);
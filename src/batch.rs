@@ -0,0 +1,342 @@
+use std::arch::x86_64::*;
+use super::*;
+
+// Batched operations on many independent small n x n matrices (n = 2, 3 or 4), one matrix per AVX-512 lane.
+//
+// The matrices are stored structure-of-arrays, field by field: with `count` matrices, entry (i, j) of matrix
+// b is a[(i * n + j) * count + b]. Vectors are stored the same way, entry i of vector b at x[i * count + b].
+// So the n * n slices a[f * count..(f + 1) * count] are exactly the per-field slices the rest of the crate
+// works on, and eight consecutive matrices load as one register per field. A final partial block of fewer
+// than eight matrices is handled with masked loads and stores.
+
+/// The determinants of a batch of n x n matrices, one per matrix.
+pub fn batch_det(a: &[f64], n: usize, det: &mut [f64])
+{
+    let count = det.len();
+    check_batch(a.len(), n * n, count);
+    unsafe{
+        match n
+        {
+            2 => batch_detu::<2, 4>(a, count, det),
+            3 => batch_detu::<3, 9>(a, count, det),
+            4 => batch_detu::<4, 16>(a, count, det),
+            _ => panic!("batch_det supports 2x2, 3x3 and 4x4 matrices, not {}x{}", n, n),
+        }
+    }
+}
+
+/// The inverses of a batch of n x n matrices, from the adjugate. Singular matrices give infinities and NaNs
+/// in their own lane and leave the rest alone.
+pub fn batch_inverse(a: &[f64], n: usize, inv: &mut [f64])
+{
+    assert_eq!(a.len(), inv.len());
+    let count = a.len() / (n * n).max(1);
+    check_batch(a.len(), n * n, count);
+    unsafe{
+        match n
+        {
+            2 => batch_inverseu::<2, 4>(a, count, inv),
+            3 => batch_inverseu::<3, 9>(a, count, inv),
+            4 => batch_inverseu::<4, 16>(a, count, inv),
+            _ => panic!("batch_inverse supports 2x2, 3x3 and 4x4 matrices, not {}x{}", n, n),
+        }
+    }
+}
+
+/// y = A x for a batch of n x n matrices and n-vectors.
+pub fn batch_matvec(a: &[f64], n: usize, x: &[f64], y: &mut [f64])
+{
+    assert_eq!(x.len(), y.len());
+    let count = x.len() / n.max(1);
+    check_batch(x.len(), n, count);
+    check_batch(a.len(), n * n, count);
+    unsafe{
+        match n
+        {
+            2 => batch_matvecu::<2, 4>(a, count, x, y),
+            3 => batch_matvecu::<3, 9>(a, count, x, y),
+            4 => batch_matvecu::<4, 16>(a, count, x, y),
+            _ => panic!("batch_matvec supports 2x2, 3x3 and 4x4 matrices, not {}x{}", n, n),
+        }
+    }
+}
+
+/// The Cholesky factors L, with A = L L^T, of a batch of symmetric positive definite n x n matrices. Only
+/// the lower triangle of each matrix is read, and the strict upper triangle of each L is zero. A matrix
+/// that isn't positive definite gets NaNs from the first failing column on.
+pub fn batch_cholesky(a: &[f64], n: usize, l: &mut [f64])
+{
+    assert_eq!(a.len(), l.len());
+    let count = a.len() / (n * n).max(1);
+    check_batch(a.len(), n * n, count);
+    unsafe{
+        match n
+        {
+            2 => batch_choleskyu::<2, 4>(a, count, l),
+            3 => batch_choleskyu::<3, 9>(a, count, l),
+            4 => batch_choleskyu::<4, 16>(a, count, l),
+            _ => panic!("batch_cholesky supports 2x2, 3x3 and 4x4 matrices, not {}x{}", n, n),
+        }
+    }
+}
+
+fn check_batch(len: usize, fields: usize, count: usize)
+{
+    assert!(fields > 0);
+    assert_eq!(len, fields * count);
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn batch_detu<const N: usize, const F: usize>(a: &[f64], count: usize, det: &mut [f64])
+{
+    let mut i = 0;
+
+    while i < count
+    {
+        let k = tail_mask(count - i);
+        let m = load_fields::<F>(a, count, i, k);
+        let d = match N { 2 => det2(&m), 3 => det3(&m), _ => det4(&m) };
+        _mm512_mask_storeu_pd(det.as_mut_ptr().add(i), k, d);
+        i += 8;
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn batch_inverseu<const N: usize, const F: usize>(a: &[f64], count: usize, inv: &mut [f64])
+{
+    let mut i = 0;
+    let mut b = [D512_ZERO; F];
+
+    while i < count
+    {
+        let k = tail_mask(count - i);
+        let m = load_fields::<F>(a, count, i, k);
+        match N { 2 => inverse2(&m, &mut b), 3 => inverse3(&m, &mut b), _ => inverse4(&m, &mut b) };
+        store_fields::<F>(inv, count, i, k, &b);
+        i += 8;
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn batch_matvecu<const N: usize, const F: usize>(a: &[f64], count: usize, x: &[f64], y: &mut [f64])
+{
+    let mut i = 0;
+    let mut yy = [D512_ZERO; N];
+
+    while i < count
+    {
+        let k = tail_mask(count - i);
+        let m = load_fields::<F>(a, count, i, k);
+        let xx = load_fields::<N>(x, count, i, k);
+        for r in 0..N {
+            yy[r] = _mm512_mul_pd(m[r * N], xx[0]);
+            for c in 1..N {
+                yy[r] = _mm512_fmadd_pd(m[r * N + c], xx[c], yy[r]);
+            }
+        }
+        store_fields::<N>(y, count, i, k, &yy);
+        i += 8;
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn batch_choleskyu<const N: usize, const F: usize>(a: &[f64], count: usize, l: &mut [f64])
+{
+    let mut i = 0;
+
+    while i < count
+    {
+        let k = tail_mask(count - i);
+        let m = load_fields::<F>(a, count, i, k);
+        let mut ll = [D512_ZERO; F];
+
+        for c in 0..N {
+            let mut d = m[c * N + c];
+            for p in 0..c {
+                d = _mm512_fnmadd_pd(ll[c * N + p], ll[c * N + p], d);
+            }
+            // sqrt of a negative or NaN pivot is NaN, which then spreads down the rest of the column.
+            ll[c * N + c] = _mm512_sqrt_pd(d);
+            let r = _mm512_div_pd(D512_ONE, ll[c * N + c]);
+
+            for row in c + 1..N {
+                let mut s = m[row * N + c];
+                for p in 0..c {
+                    s = _mm512_fnmadd_pd(ll[row * N + p], ll[c * N + p], s);
+                }
+                ll[row * N + c] = _mm512_mul_pd(s, r);
+            }
+        }
+
+        store_fields::<F>(l, count, i, k, &ll);
+        i += 8;
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn load_fields<const F: usize>(a: &[f64], count: usize, i: usize, k: __mmask8) -> [__m512d; F]
+{
+    let mut m = [D512_ZERO; F];
+    for (f, mf) in m.iter_mut().enumerate() {
+        *mf = _mm512_maskz_loadu_pd(k, a.as_ptr().add(f * count + i));
+    }
+    m
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn store_fields<const F: usize>(y: &mut [f64], count: usize, i: usize, k: __mmask8, v: &[__m512d; F])
+{
+    for (f, vf) in v.iter().enumerate() {
+        _mm512_mask_storeu_pd(y.as_mut_ptr().add(f * count + i), k, *vf);
+    }
+}
+
+/// a b - c d, with the first product kept exact by the FMA.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn diff_of_products(a: __m512d, b: __m512d, c: __m512d, d: __m512d) -> __m512d
+{
+    _mm512_fmsub_pd(a, b, _mm512_mul_pd(c, d))
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn det2(m: &[__m512d]) -> __m512d
+{
+    diff_of_products(m[0], m[3], m[1], m[2])
+}
+
+// Cofactors along the first row of a 3x3 matrix.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn cofactors3(m: &[__m512d]) -> [__m512d; 3]
+{
+    [
+        diff_of_products(m[4], m[8], m[5], m[7]),
+        diff_of_products(m[5], m[6], m[3], m[8]),
+        diff_of_products(m[3], m[7], m[4], m[6]),
+    ]
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn det3(m: &[__m512d]) -> __m512d
+{
+    let c = cofactors3(m);
+    _mm512_fmadd_pd(m[0], c[0], _mm512_fmadd_pd(m[1], c[1], _mm512_mul_pd(m[2], c[2])))
+}
+
+// The 2x2 minors of the top two rows (s) and the bottom two rows (c) of a 4x4 matrix, from which both its
+// determinant and its adjugate follow.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn minors4(m: &[__m512d]) -> ([__m512d; 6], [__m512d; 6])
+{
+    let s = [
+        diff_of_products(m[0], m[5], m[4], m[1]),
+        diff_of_products(m[0], m[6], m[4], m[2]),
+        diff_of_products(m[0], m[7], m[4], m[3]),
+        diff_of_products(m[1], m[6], m[5], m[2]),
+        diff_of_products(m[1], m[7], m[5], m[3]),
+        diff_of_products(m[2], m[7], m[6], m[3]),
+    ];
+    let c = [
+        diff_of_products(m[8], m[13], m[12], m[9]),
+        diff_of_products(m[8], m[14], m[12], m[10]),
+        diff_of_products(m[8], m[15], m[12], m[11]),
+        diff_of_products(m[9], m[14], m[13], m[10]),
+        diff_of_products(m[9], m[15], m[13], m[11]),
+        diff_of_products(m[10], m[15], m[14], m[11]),
+    ];
+    (s, c)
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn det4_from_minors(s: &[__m512d; 6], c: &[__m512d; 6]) -> __m512d
+{
+    let p = _mm512_add_pd(diff_of_products(s[0], c[5], s[1], c[4]), diff_of_products(s[2], c[3], s[4], c[1]));
+    _mm512_add_pd(p, _mm512_fmadd_pd(s[3], c[2], _mm512_mul_pd(s[5], c[0])))
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn det4(m: &[__m512d]) -> __m512d
+{
+    let (s, c) = minors4(m);
+    det4_from_minors(&s, &c)
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn inverse2(m: &[__m512d], b: &mut [__m512d])
+{
+    let r = _mm512_div_pd(D512_ONE, det2(m));
+    b[0] = _mm512_mul_pd(m[3], r);
+    b[1] = _mm512_mul_pd(_mm512_sub_pd(D512_ZERO, m[1]), r);
+    b[2] = _mm512_mul_pd(_mm512_sub_pd(D512_ZERO, m[2]), r);
+    b[3] = _mm512_mul_pd(m[0], r);
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn inverse3(m: &[__m512d], b: &mut [__m512d])
+{
+    let c = cofactors3(m);
+    let r = _mm512_div_pd(D512_ONE, _mm512_fmadd_pd(m[0], c[0], _mm512_fmadd_pd(m[1], c[1], _mm512_mul_pd(m[2], c[2]))));
+    b[0] = _mm512_mul_pd(c[0], r);
+    b[1] = _mm512_mul_pd(diff_of_products(m[2], m[7], m[1], m[8]), r);
+    b[2] = _mm512_mul_pd(diff_of_products(m[1], m[5], m[2], m[4]), r);
+    b[3] = _mm512_mul_pd(c[1], r);
+    b[4] = _mm512_mul_pd(diff_of_products(m[0], m[8], m[2], m[6]), r);
+    b[5] = _mm512_mul_pd(diff_of_products(m[2], m[3], m[0], m[5]), r);
+    b[6] = _mm512_mul_pd(c[2], r);
+    b[7] = _mm512_mul_pd(diff_of_products(m[1], m[6], m[0], m[7]), r);
+    b[8] = _mm512_mul_pd(diff_of_products(m[0], m[4], m[1], m[3]), r);
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn inverse4(m: &[__m512d], b: &mut [__m512d])
+{
+    let (s, c) = minors4(m);
+    let r = _mm512_div_pd(D512_ONE, det4_from_minors(&s, &c));
+
+    let nr = _mm512_sub_pd(D512_ZERO, r);
+
+    b[0] = _mm512_mul_pd(adjugate_entry(m[5], c[5], m[6], c[4], m[7], c[3]), r);
+    b[1] = _mm512_mul_pd(adjugate_entry(m[1], c[5], m[2], c[4], m[3], c[3]), nr);
+    b[2] = _mm512_mul_pd(adjugate_entry(m[13], s[5], m[14], s[4], m[15], s[3]), r);
+    b[3] = _mm512_mul_pd(adjugate_entry(m[9], s[5], m[10], s[4], m[11], s[3]), nr);
+    b[4] = _mm512_mul_pd(adjugate_entry(m[4], c[5], m[6], c[2], m[7], c[1]), nr);
+    b[5] = _mm512_mul_pd(adjugate_entry(m[0], c[5], m[2], c[2], m[3], c[1]), r);
+    b[6] = _mm512_mul_pd(adjugate_entry(m[12], s[5], m[14], s[2], m[15], s[1]), nr);
+    b[7] = _mm512_mul_pd(adjugate_entry(m[8], s[5], m[10], s[2], m[11], s[1]), r);
+    b[8] = _mm512_mul_pd(adjugate_entry(m[4], c[4], m[5], c[2], m[7], c[0]), r);
+    b[9] = _mm512_mul_pd(adjugate_entry(m[0], c[4], m[1], c[2], m[3], c[0]), nr);
+    b[10] = _mm512_mul_pd(adjugate_entry(m[12], s[4], m[13], s[2], m[15], s[0]), r);
+    b[11] = _mm512_mul_pd(adjugate_entry(m[8], s[4], m[9], s[2], m[11], s[0]), nr);
+    b[12] = _mm512_mul_pd(adjugate_entry(m[4], c[3], m[5], c[1], m[6], c[0]), nr);
+    b[13] = _mm512_mul_pd(adjugate_entry(m[0], c[3], m[1], c[1], m[2], c[0]), r);
+    b[14] = _mm512_mul_pd(adjugate_entry(m[12], s[3], m[13], s[1], m[14], s[0]), nr);
+    b[15] = _mm512_mul_pd(adjugate_entry(m[8], s[3], m[9], s[1], m[10], s[0]), r);
+}
+
+// x p - y q + z t: every entry of a 4x4 adjugate is, up to sign, one of these over an entry of the matrix
+// and two of its 2x2 minors.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn adjugate_entry(x: __m512d, p: __m512d, y: __m512d, q: __m512d, z: __m512d, t: __m512d) -> __m512d
+{
+    _mm512_fmadd_pd(z, t, diff_of_products(x, p, y, q))
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
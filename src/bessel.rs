@@ -0,0 +1,681 @@
+use std::arch::x86_64::*;
+use super::*;
+
+
+unroll_fn!(j0, j0_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(j1, j1_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(y0, y0_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(y1, y1_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(i0, i0_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(i1, i1_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(i0e, i0e_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(i1e, i1e_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(k0, k0_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(k1, k1_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(k0e, k0e_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(k1e, k1e_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_j0_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    j0_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_j1_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    j1_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_y0_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    y0_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_y1_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    y1_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_i0_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    i0_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_i1_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    i1_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_i0e_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    i0e_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_i1e_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    i1e_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_k0_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    k0_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_k1_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    k1_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_k0e_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    k0e_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_k1e_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    k1e_intr(&x, &mut y);
+    y
+}
+
+/// AVX-512 implementation of the Bessel function of the first kind of order 0, J0(x). Near its zeros the error is
+/// ~1e-16 absolute rather than relative.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn j0_intr(x: &__m512d, y: &mut __m512d)
+{
+    let ax = _mm512_abs_pd(*x);
+    let small_mask = _mm512_cmple_pd_mask(ax, D512_EIGHT);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, small_mask, clenshaw(small_t(ax), &D512_J0S));
+    }
+    if !small_mask != 0
+    {
+        let (j, _) = hankel(ax, &D512_P0, &D512_Q0, false);
+        yy = _mm512_mask_mov_pd(yy, !small_mask, j);
+    }
+
+    *y = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(ax, D512_POSITIVE_INFINITY), yy, D512_ZERO);
+}
+
+/// AVX-512 implementation of the Bessel function of the first kind of order 1, J1(x). Near its zeros the error is
+/// ~1e-16 absolute rather than relative.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn j1_intr(x: &__m512d, y: &mut __m512d)
+{
+    let ax = _mm512_abs_pd(*x);
+    let small_mask = _mm512_cmple_pd_mask(ax, D512_EIGHT);
+
+    // J1 is odd, and J1(x)/x is smooth in x^2
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        yy = _mm512_mask_mul_pd(yy, small_mask, *x, clenshaw(small_t(ax), &D512_J1S));
+    }
+    if !small_mask != 0
+    {
+        let (j, _) = hankel(ax, &D512_P1, &D512_Q1, true);
+        yy = _mm512_mask_mov_pd(yy, !small_mask, _mm512_xor_pd(j, _mm512_and_pd(*x, D512_NEGATIVE_ZERO)));
+    }
+
+    *y = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(ax, D512_POSITIVE_INFINITY), yy, D512_ZERO);
+}
+
+/// AVX-512 implementation of the Bessel function of the second kind of order 0, Y0(x), for x >= 0. Near its zeros
+/// the error is ~1e-16 absolute rather than relative.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn y0_intr(x: &__m512d, y: &mut __m512d)
+{
+    let small_mask = _mm512_cmple_pd_mask(*x, D512_EIGHT);
+
+    // Y0(x) - 2/pi ln(x) J0(x) is smooth in x^2
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        let t = small_t(*x);
+        let mut lx = D512_ZERO;
+        ln_intr(x, &mut lx);
+        let ys = _mm512_fmadd_pd(_mm512_mul_pd(lx, D512_TWO_OVER_PI), clenshaw(t, &D512_J0S), clenshaw(t, &D512_Y0S));
+        yy = _mm512_mask_mov_pd(yy, small_mask, ys);
+    }
+    if !small_mask != 0
+    {
+        let (_, yl) = hankel(*x, &D512_P0, &D512_Q0, false);
+        yy = _mm512_mask_mov_pd(yy, !small_mask, yl);
+    }
+
+    *y = second_kind_specials(*x, yy, D512_NEGATIVE_INFINITY, D512_ZERO);
+}
+
+/// AVX-512 implementation of the Bessel function of the second kind of order 1, Y1(x), for x >= 0. Near its zeros
+/// the error is ~1e-16 absolute rather than relative.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn y1_intr(x: &__m512d, y: &mut __m512d)
+{
+    let small_mask = _mm512_cmple_pd_mask(*x, D512_EIGHT);
+
+    // (Y1(x) - 2/pi (ln(x) J1(x) - 1/x))/x is smooth in x^2
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        let t = small_t(*x);
+        let mut lx = D512_ZERO;
+        ln_intr(x, &mut lx);
+        let j = _mm512_mul_pd(*x, clenshaw(t, &D512_J1S));
+        let mut ys = _mm512_fmsub_pd(lx, j, _mm512_div_pd(D512_ONE, *x));
+        ys = _mm512_fmadd_pd(ys, D512_TWO_OVER_PI, _mm512_mul_pd(*x, clenshaw(t, &D512_Y1S)));
+        yy = _mm512_mask_mov_pd(yy, small_mask, ys);
+    }
+    if !small_mask != 0
+    {
+        let (_, yl) = hankel(*x, &D512_P1, &D512_Q1, true);
+        yy = _mm512_mask_mov_pd(yy, !small_mask, yl);
+    }
+
+    *y = second_kind_specials(*x, yy, D512_NEGATIVE_INFINITY, D512_ZERO);
+}
+
+/// AVX-512 implementation of the modified Bessel function of the first kind of order 0, I0(x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn i0_intr(x: &__m512d, y: &mut __m512d)
+{
+    let ax = _mm512_abs_pd(*x);
+    let small_mask = _mm512_cmple_pd_mask(ax, D512_EIGHT);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, small_mask, horner(_mm512_mul_pd(ax, ax), &D512_I0T));
+    }
+    if !small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, !small_mask, unscale_i(ax, modified_large(ax, &D512_I0L, D512_SIXTEEN)));
+    }
+
+    *y = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(ax, D512_POSITIVE_INFINITY), yy, ax);
+}
+
+/// AVX-512 implementation of the modified Bessel function of the first kind of order 1, I1(x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn i1_intr(x: &__m512d, y: &mut __m512d)
+{
+    let ax = _mm512_abs_pd(*x);
+    let small_mask = _mm512_cmple_pd_mask(ax, D512_EIGHT);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, small_mask, _mm512_mul_pd(ax, horner(_mm512_mul_pd(ax, ax), &D512_I1T)));
+    }
+    if !small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, !small_mask, unscale_i(ax, modified_large(ax, &D512_I1L, D512_SIXTEEN)));
+    }
+
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(ax, D512_POSITIVE_INFINITY), yy, ax);
+    *y = _mm512_xor_pd(yy, _mm512_and_pd(*x, D512_NEGATIVE_ZERO));
+}
+
+/// AVX-512 implementation of the exponentially scaled modified Bessel function of the first kind of order 0,
+/// e^-|x| I0(x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn i0e_intr(x: &__m512d, y: &mut __m512d)
+{
+    let ax = _mm512_abs_pd(*x);
+    let small_mask = _mm512_cmple_pd_mask(ax, D512_EIGHT);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        let mut e = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(D512_ZERO, ax), &mut e);
+        yy = _mm512_mask_mul_pd(yy, small_mask, e, horner(_mm512_mul_pd(ax, ax), &D512_I0T));
+    }
+    if !small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, !small_mask, modified_large(ax, &D512_I0L, D512_SIXTEEN));
+    }
+
+    *y = yy;
+}
+
+/// AVX-512 implementation of the exponentially scaled modified Bessel function of the first kind of order 1,
+/// e^-|x| I1(x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn i1e_intr(x: &__m512d, y: &mut __m512d)
+{
+    let ax = _mm512_abs_pd(*x);
+    let small_mask = _mm512_cmple_pd_mask(ax, D512_EIGHT);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        let mut e = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(D512_ZERO, ax), &mut e);
+        let i = _mm512_mul_pd(ax, horner(_mm512_mul_pd(ax, ax), &D512_I1T));
+        yy = _mm512_mask_mul_pd(yy, small_mask, e, i);
+    }
+    if !small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, !small_mask, modified_large(ax, &D512_I1L, D512_SIXTEEN));
+    }
+
+    *y = _mm512_xor_pd(yy, _mm512_and_pd(*x, D512_NEGATIVE_ZERO));
+}
+
+/// AVX-512 implementation of the modified Bessel function of the second kind of order 0, K0(x), for x >= 0.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn k0_intr(x: &__m512d, y: &mut __m512d)
+{
+    let small_mask = _mm512_cmple_pd_mask(*x, D512_TWO);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, small_mask, k0_small(*x));
+    }
+    if !small_mask != 0
+    {
+        let mut e = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(D512_ZERO, *x), &mut e);
+        yy = _mm512_mask_mul_pd(yy, !small_mask, e, modified_large(*x, &D512_K0L, D512_FOUR));
+    }
+
+    *y = second_kind_specials(*x, yy, D512_POSITIVE_INFINITY, D512_ZERO);
+}
+
+/// AVX-512 implementation of the modified Bessel function of the second kind of order 1, K1(x), for x >= 0.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn k1_intr(x: &__m512d, y: &mut __m512d)
+{
+    let small_mask = _mm512_cmple_pd_mask(*x, D512_TWO);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, small_mask, k1_small(*x));
+    }
+    if !small_mask != 0
+    {
+        let mut e = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(D512_ZERO, *x), &mut e);
+        yy = _mm512_mask_mul_pd(yy, !small_mask, e, modified_large(*x, &D512_K1L, D512_FOUR));
+    }
+
+    *y = second_kind_specials(*x, yy, D512_POSITIVE_INFINITY, D512_ZERO);
+}
+
+/// AVX-512 implementation of the exponentially scaled modified Bessel function of the second kind of order 0,
+/// e^x K0(x), for x >= 0.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn k0e_intr(x: &__m512d, y: &mut __m512d)
+{
+    let small_mask = _mm512_cmple_pd_mask(*x, D512_TWO);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        let mut e = D512_ZERO;
+        exp_intr(x, &mut e);
+        yy = _mm512_mask_mul_pd(yy, small_mask, e, k0_small(*x));
+    }
+    if !small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, !small_mask, modified_large(*x, &D512_K0L, D512_FOUR));
+    }
+
+    *y = second_kind_specials(*x, yy, D512_POSITIVE_INFINITY, D512_ZERO);
+}
+
+/// AVX-512 implementation of the exponentially scaled modified Bessel function of the second kind of order 1,
+/// e^x K1(x), for x >= 0.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn k1e_intr(x: &__m512d, y: &mut __m512d)
+{
+    let small_mask = _mm512_cmple_pd_mask(*x, D512_TWO);
+
+    let mut yy = D512_ZERO;
+    if small_mask != 0
+    {
+        let mut e = D512_ZERO;
+        exp_intr(x, &mut e);
+        yy = _mm512_mask_mul_pd(yy, small_mask, e, k1_small(*x));
+    }
+    if !small_mask != 0
+    {
+        yy = _mm512_mask_mov_pd(yy, !small_mask, modified_large(*x, &D512_K1L, D512_FOUR));
+    }
+
+    *y = second_kind_specials(*x, yy, D512_POSITIVE_INFINITY, D512_ZERO);
+}
+
+/// K0(x) = R(x^2) - ln(x/2) I0(x) for 0 < x <= 2
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn k0_small(x: __m512d) -> __m512d
+{
+    let z = _mm512_mul_pd(x, x);
+    // ln_intr loses its relative accuracy near 1, which is where this crosses zero
+    let (lx, _) = ln_dd(_mm512_mul_pd(x, D512_HALF));
+    let r = clenshaw(_mm512_fmsub_pd(z, D512_HALF, D512_ONE), &D512_K0S);
+    _mm512_fnmadd_pd(lx, horner(z, &D512_I0T), r)
+}
+
+/// K1(x) = 1/x + ln(x/2) I1(x) + x R(x^2) for 0 < x <= 2
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn k1_small(x: __m512d) -> __m512d
+{
+    let z = _mm512_mul_pd(x, x);
+    // ln_intr loses its relative accuracy near 1, which is where this crosses zero
+    let (lx, _) = ln_dd(_mm512_mul_pd(x, D512_HALF));
+    let r = clenshaw(_mm512_fmsub_pd(z, D512_HALF, D512_ONE), &D512_K1S);
+    let i = _mm512_mul_pd(x, horner(z, &D512_I1T));
+    _mm512_add_pd(_mm512_div_pd(D512_ONE, x), _mm512_fmadd_pd(lx, i, _mm512_mul_pd(x, r)))
+}
+
+/// The x^2/32 - 1 that maps [0, 8] to the [-1, 1] of the small argument Chebyshev series.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn small_t(x: __m512d) -> __m512d
+{
+    _mm512_fmsub_pd(_mm512_mul_pd(x, x), D512_ONE_THIRTYSECOND, D512_ONE)
+}
+
+/// (J, Y) for x > 8 from Hankel's asymptotic form, sqrt(2/(pi x)) (P cos(theta) - Q sin(theta)) and
+/// sqrt(2/(pi x)) (P sin(theta) + Q cos(theta)), where P and Q/x are series in 64/x^2 and theta = x - pi/4 for
+/// order 0 and x - 3pi/4 for order 1. x - pi/4 would round away the digits that sin needs once x is large, so
+/// sin(x) and cos(x) are taken as they are and turned into sin(theta) and cos(theta) with the exact identities
+/// sin(x - pi/4) = (sin(x) - cos(x))/sqrt(2), cos(x - pi/4) = (cos(x) + sin(x))/sqrt(2), and a further quarter
+/// turn for order 1.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn hankel(x: __m512d, p: &[__m512d], q: &[__m512d], first_order: bool) -> (__m512d, __m512d)
+{
+    let r = _mm512_div_pd(D512_EIGHT, x);
+    let t = _mm512_fmsub_pd(_mm512_mul_pd(r, r), D512_TWO, D512_ONE);
+    let pp = clenshaw(t, p);
+    let qq = _mm512_div_pd(clenshaw(t, q), x);
+
+    let (sx, cx) = sincos(x);
+    let mut s = _mm512_mul_pd(_mm512_sub_pd(sx, cx), D512_FRAC_1_SQRT_2);
+    let mut c = _mm512_mul_pd(_mm512_add_pd(cx, sx), D512_FRAC_1_SQRT_2);
+    if first_order
+    {
+        // sin(theta - pi/2) = -cos(theta) and cos(theta - pi/2) = sin(theta)
+        (s, c) = (_mm512_sub_pd(D512_ZERO, c), s);
+    }
+
+    let a = _mm512_sqrt_pd(_mm512_div_pd(D512_TWO_OVER_PI, x));
+    let j = _mm512_mul_pd(a, _mm512_fmsub_pd(pp, c, _mm512_mul_pd(qq, s)));
+    let y = _mm512_mul_pd(a, _mm512_fmadd_pd(pp, s, _mm512_mul_pd(qq, c)));
+    (j, y)
+}
+
+/// sqrt(x) e^-x I(x) or sqrt(x) e^x K(x) for x > split, as a Chebyshev series in 2 split/x - 1.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn modified_large(x: __m512d, coeffs: &[__m512d], two_split: __m512d) -> __m512d
+{
+    let t = _mm512_sub_pd(_mm512_div_pd(two_split, x), D512_ONE);
+    _mm512_div_pd(clenshaw(t, coeffs), _mm512_sqrt_pd(x))
+}
+
+/// e^x s, split in two so that I(x) doesn't overflow before it has to.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn unscale_i(x: __m512d, s: __m512d) -> __m512d
+{
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_mul_pd(x, D512_HALF), &mut e);
+    _mm512_mul_pd(_mm512_mul_pd(s, e), e)
+}
+
+/// Y and K are NaN for x < 0, at_zero at 0 and at_inf at infinity.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn second_kind_specials(x: __m512d, y: __m512d, at_zero: __m512d, at_inf: __m512d) -> __m512d
+{
+    let mut yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(x, D512_ZERO), y, at_zero);
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(x, D512_POSITIVE_INFINITY), yy, at_inf);
+    _mm512_mask_blend_pd(_mm512_cmplt_pd_mask(x, D512_ZERO), yy, D512_NAN)
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_HALF: __m512d = m64x8_constant!(0.5);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_TWO: __m512d = m64x8_constant!(2.0);
+const D512_FOUR: __m512d = m64x8_constant!(4.0);
+const D512_EIGHT: __m512d = m64x8_constant!(8.0);
+const D512_SIXTEEN: __m512d = m64x8_constant!(16.0);
+const D512_ONE_THIRTYSECOND: __m512d = m64x8_constant!(1.0 / 32.0);
+const D512_NEGATIVE_ZERO: __m512d = m64x8_constant!(-0.0);
+const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_NEGATIVE_INFINITY: __m512d = m64x8_constant!(f64::NEG_INFINITY);
+const D512_TWO_OVER_PI: __m512d = m64x8_constant!(2.0 / std::f64::consts::PI);
+const D512_FRAC_1_SQRT_2: __m512d = m64x8_constant!(std::f64::consts::FRAC_1_SQRT_2);
+
+// Chebyshev series: J0(x), J1(x)/x, Y0(x) - 2/pi ln(x) J0(x) and (Y1(x) - 2/pi (ln(x) J1(x) - 1/x))/x in x^2/32 - 1
+// for x <= 8; Hankel's P and Q x in 128/x^2 - 1 for x > 8; sqrt(x) e^-x I(x) in 16/x - 1 for x > 8;
+// K0(x) + ln(x/2) I0(x) and (K1(x) - 1/x - ln(x/2) I1(x))/x in x^2/2 - 1 for x <= 2; sqrt(x) e^x K(x) in 4/x - 1
+// for x > 2. Then the Taylor series of I0(x) and I1(x)/x in x^2.
+const D512_J0S: [__m512d; 17] = [
+    m64x8_constant!(0.15772797147489012), m64x8_constant!(-0.008723442352852221), m64x8_constant!(0.2651786132033368),
+    m64x8_constant!(-0.37009499387264977), m64x8_constant!(0.15806710233209725),
+    m64x8_constant!(-0.034893769411408884), m64x8_constant!(0.004819180069467605),
+    m64x8_constant!(-0.00046062616620627504), m64x8_constant!(3.246032882100508e-05),
+    m64x8_constant!(-1.7619469077621507e-06), m64x8_constant!(7.608163592418782e-08),
+    m64x8_constant!(-2.679253530557673e-09), m64x8_constant!(7.848696314479465e-11),
+    m64x8_constant!(-1.9438346867370164e-12), m64x8_constant!(4.125320595634374e-14),
+    m64x8_constant!(-7.588508125447546e-16), m64x8_constant!(1.2218515873961411e-17)
+];
+const D512_J1S: [__m512d; 16] = [
+    m64x8_constant!(0.08104484632565812), m64x8_constant!(-0.1489751450676521), m64x8_constant!(0.1609992623572097),
+    m64x8_constant!(-0.08268049176681791), m64x8_constant!(0.022213639654966037),
+    m64x8_constant!(-0.003646940600769276), m64x8_constant!(0.0004050337728354822),
+    m64x8_constant!(-3.255554866857259e-05), m64x8_constant!(1.9858774049915165e-06),
+    m64x8_constant!(-9.521984756750436e-08), m64x8_constant!(3.687133759097148e-09),
+    m64x8_constant!(-1.178026622695885e-10), m64x8_constant!(3.160154580348003e-12),
+    m64x8_constant!(-7.221755239651773e-14), m64x8_constant!(1.4232144003513942e-15),
+    m64x8_constant!(-2.4441972916190464e-17)
+];
+const D512_Y0S: [__m512d; 17] = [
+    m64x8_constant!(-0.03314611320328494), m64x8_constant!(-0.2744743055297453), m64x8_constant!(0.17903431407718265),
+    m64x8_constant!(0.26156734625504663), m64x8_constant!(-0.17730201278114358), m64x8_constant!(0.04719668959576339),
+    m64x8_constant!(-0.007287962479552079), m64x8_constant!(0.0007531135932577742),
+    m64x8_constant!(-5.632079141056987e-05), m64x8_constant!(3.206532537654801e-06),
+    m64x8_constant!(-1.44072332740187e-07), m64x8_constant!(5.248794787330516e-09),
+    m64x8_constant!(-1.5837552541812015e-10), m64x8_constant!(4.02633081830612e-12),
+    m64x8_constant!(-8.747341203310769e-14), m64x8_constant!(1.6434898714919468e-15),
+    m64x8_constant!(-2.6977881152566837e-17)
+];
+const D512_Y1S: [__m512d; 17] = [
+    m64x8_constant!(0.0025380132357417816), m64x8_constant!(-0.01608717304766875),
+    m64x8_constant!(-0.09591204536083074), m64x8_constant!(0.08445197259652346),
+    m64x8_constant!(-0.028328123944594365), m64x8_constant!(0.005289897544167113),
+    m64x8_constant!(-0.0006414551451326356), m64x8_constant!(5.505982873338744e-05),
+    m64x8_constant!(-3.53808001868935e-06), m64x8_constant!(1.7707804556154404e-07),
+    m64x8_constant!(-7.11055004989928e-09), m64x8_constant!(2.344337905911516e-10),
+    m64x8_constant!(-6.4651518414115946e-12), m64x8_constant!(1.51429151205002e-13),
+    m64x8_constant!(-3.0511859694473254e-15), m64x8_constant!(5.34668038572867e-17),
+    m64x8_constant!(-8.224911372021585e-19)
+];
+const D512_P0: [__m512d; 14] = [
+    m64x8_constant!(0.9994603493475187), m64x8_constant!(-0.0005365220468132117),
+    m64x8_constant!(3.0751847875194745e-06), m64x8_constant!(-5.1705945376060975e-08),
+    m64x8_constant!(1.6306464635151382e-09), m64x8_constant!(-7.86409137723707e-11),
+    m64x8_constant!(5.168262387349193e-12), m64x8_constant!(-4.3045788699253914e-13),
+    m64x8_constant!(4.3265957431549404e-14), m64x8_constant!(-5.069034095935236e-15),
+    m64x8_constant!(6.748072215733873e-16), m64x8_constant!(-1.0011513723467786e-16),
+    m64x8_constant!(1.6305919233744186e-17), m64x8_constant!(-2.880866169482871e-18)
+];
+const D512_Q0: [__m512d; 17] = [
+    m64x8_constant!(-0.12444683684269607), m64x8_constant!(0.0005470815954089319),
+    m64x8_constant!(-5.9315987288485175e-06), m64x8_constant!(1.4377965798375193e-07),
+    m64x8_constant!(-5.817532749493056e-09), m64x8_constant!(3.376097523734991e-10),
+    m64x8_constant!(-2.565397936797308e-11), m64x8_constant!(2.404916100281365e-12),
+    m64x8_constant!(-2.6690625482579414e-13), m64x8_constant!(3.4041800321963686e-14),
+    m64x8_constant!(-4.87994410531204e-15), m64x8_constant!(7.729703176242605e-16),
+    m64x8_constant!(-1.3348852171502517e-16), m64x8_constant!(2.4865952389390515e-17),
+    m64x8_constant!(-4.952892629886516e-18), m64x8_constant!(1.0473158973776097e-18),
+    m64x8_constant!(-2.336930172211422e-19)
+];
+const D512_P1: [__m512d; 14] = [
+    m64x8_constant!(1.0009030408600137), m64x8_constant!(0.0008989898330859408),
+    m64x8_constant!(-3.987284300488908e-06), m64x8_constant!(6.177633960644299e-08),
+    m64x8_constant!(-1.8718907491063067e-09), m64x8_constant!(8.816898659582339e-11),
+    m64x8_constant!(-5.704863640395645e-12), m64x8_constant!(4.699195515230542e-13),
+    m64x8_constant!(-4.6842237839904895e-14), m64x8_constant!(5.452674896044717e-15),
+    m64x8_constant!(-7.221180842274018e-16), m64x8_constant!(1.0667689114335412e-16),
+    m64x8_constant!(-1.7312313216116335e-17), m64x8_constant!(3.0492991197665872e-18)
+];
+const D512_Q1: [__m512d; 16] = [
+    m64x8_constant!(0.3742222965562826), m64x8_constant!(-0.0007702178839325664),
+    m64x8_constant!(7.3108922063643636e-06), m64x8_constant!(-1.676782510726674e-07),
+    m64x8_constant!(6.583354662120443e-09), m64x8_constant!(-3.749090950541556e-10),
+    m64x8_constant!(2.8121750359748866e-11), m64x8_constant!(-2.61145253946232e-12),
+    m64x8_constant!(2.8774212663332235e-13), m64x8_constant!(-3.649001916061838e-14),
+    m64x8_constant!(5.206626366226707e-15), m64x8_constant!(-8.215318025458595e-16),
+    m64x8_constant!(1.4141084390211833e-16), m64x8_constant!(-2.626761589838529e-17),
+    m64x8_constant!(5.2192649196714085e-18), m64x8_constant!(-1.101261718787959e-18)
+];
+const D512_I0L: [__m512d; 27] = [
+    m64x8_constant!(0.4022452055070544), m64x8_constant!(0.0033691164782556943),
+    m64x8_constant!(6.889758346916825e-05), m64x8_constant!(2.8913705208347567e-06),
+    m64x8_constant!(2.0489185894690638e-07), m64x8_constant!(2.266668990498178e-08),
+    m64x8_constant!(3.3962320257083865e-09), m64x8_constant!(4.94060238822497e-10),
+    m64x8_constant!(1.1889147107846439e-11), m64x8_constant!(-3.1499165279632416e-11),
+    m64x8_constant!(-1.3215811840447713e-11), m64x8_constant!(-1.7941785315068062e-12),
+    m64x8_constant!(7.180124451383666e-13), m64x8_constant!(3.8527783827421426e-13),
+    m64x8_constant!(1.54008621752141e-14), m64x8_constant!(-4.150569347287222e-14),
+    m64x8_constant!(-9.554846698828307e-15), m64x8_constant!(3.8116806693526224e-15),
+    m64x8_constant!(1.7725601330565263e-15), m64x8_constant!(-3.425485619677219e-16),
+    m64x8_constant!(-2.8276239805165836e-16), m64x8_constant!(3.461222867697461e-17),
+    m64x8_constant!(4.46562142029676e-17), m64x8_constant!(-4.830504485944182e-18),
+    m64x8_constant!(-7.233180487874754e-18), m64x8_constant!(9.921475412173699e-19),
+    m64x8_constant!(1.193650890845982e-18)
+];
+const D512_I1L: [__m512d; 27] = [
+    m64x8_constant!(0.38928811750914005), m64x8_constant!(-0.009761097491361469),
+    m64x8_constant!(-0.00011058893876262371), m64x8_constant!(-3.882564808877691e-06),
+    m64x8_constant!(-2.512236237870209e-07), m64x8_constant!(-2.6314688468895196e-08),
+    m64x8_constant!(-3.835380385964237e-09), m64x8_constant!(-5.589743462196584e-10),
+    m64x8_constant!(-1.8974958123505413e-11), m64x8_constant!(3.2526035830154884e-11),
+    m64x8_constant!(1.4125807436613782e-11), m64x8_constant!(2.0356285441470896e-12),
+    m64x8_constant!(-7.198551776245908e-13), m64x8_constant!(-4.0835511110921974e-13),
+    m64x8_constant!(-2.1015418427726643e-14), m64x8_constant!(4.272440016711951e-14),
+    m64x8_constant!(1.0420276984128802e-14), m64x8_constant!(-3.8144030724370075e-15),
+    m64x8_constant!(-1.8803547755107825e-15), m64x8_constant!(3.3082023109209285e-16),
+    m64x8_constant!(2.96262899764595e-16), m64x8_constant!(-3.209525921993424e-17),
+    m64x8_constant!(-4.6503053684893586e-17), m64x8_constant!(4.414348323071708e-18),
+    m64x8_constant!(7.517296310842105e-18), m64x8_constant!(-9.314178867326884e-19),
+    m64x8_constant!(-1.242193275194891e-18)
+];
+const D512_K0S: [__m512d; 10] = [
+    m64x8_constant!(-0.2676636966169514), m64x8_constant!(0.3442898999246285), m64x8_constant!(0.0359799365153615),
+    m64x8_constant!(0.001264615411446926), m64x8_constant!(2.286212103119452e-05),
+    m64x8_constant!(2.5347910790261494e-07), m64x8_constant!(1.904516377220209e-09),
+    m64x8_constant!(1.0349695257633625e-11), m64x8_constant!(4.2598161427910826e-14),
+    m64x8_constant!(1.3744654358807508e-16)
+];
+const D512_K1S: [__m512d; 10] = [
+    m64x8_constant!(-0.06409034516866254), m64x8_constant!(-0.10916919599320105),
+    m64x8_constant!(-0.006636878452817714), m64x8_constant!(-0.00016822792382067343),
+    m64x8_constant!(-2.389559180803608e-06), m64x8_constant!(-2.1853569024549695e-08),
+    m64x8_constant!(-1.3982271326113327e-10), m64x8_constant!(-6.618562752976041e-13),
+    m64x8_constant!(-2.4134516242987822e-15), m64x8_constant!(-6.990873731242567e-18)
+];
+const D512_K0L: [__m512d; 26] = [
+    m64x8_constant!(1.2201515410329777), m64x8_constant!(-0.0314481013119645), m64x8_constant!(0.0015698838857300533),
+    m64x8_constant!(-0.00012849549581627802), m64x8_constant!(1.39498137188765e-05),
+    m64x8_constant!(-1.8317555227191195e-06), m64x8_constant!(2.766813639445015e-07),
+    m64x8_constant!(-4.660489897687948e-08), m64x8_constant!(8.574034017414225e-09),
+    m64x8_constant!(-1.6975345093890614e-09), m64x8_constant!(3.5773972814003283e-10),
+    m64x8_constant!(-7.957489244477396e-11), m64x8_constant!(1.8559491149549264e-11),
+    m64x8_constant!(-4.514597883374519e-12), m64x8_constant!(1.1403405882073441e-12),
+    m64x8_constant!(-2.9800969231481784e-13), m64x8_constant!(8.032890775068375e-14),
+    m64x8_constant!(-2.2275133267462965e-14), m64x8_constant!(6.340076476276646e-15),
+    m64x8_constant!(-1.848593377920907e-15), m64x8_constant!(5.5120559994043335e-16),
+    m64x8_constant!(-1.6782311257549006e-16), m64x8_constant!(5.2103917776435543e-17),
+    m64x8_constant!(-1.6475805939842632e-17), m64x8_constant!(5.3004337711773354e-18),
+    m64x8_constant!(-1.7331712005821001e-18)
+];
+const D512_K1L: [__m512d; 26] = [
+    m64x8_constant!(1.3603130952422213), m64x8_constant!(0.10392373657681724), m64x8_constant!(-0.002857816859622779),
+    m64x8_constant!(0.00019521551847135162), m64x8_constant!(-1.936197974166083e-05),
+    m64x8_constant!(2.406484947837217e-06), m64x8_constant!(-3.5019606030878126e-07),
+    m64x8_constant!(5.7410841254500495e-08), m64x8_constant!(-1.0345762465678097e-08),
+    m64x8_constant!(2.0150497551970347e-09), m64x8_constant!(-4.1903547593419254e-10),
+    m64x8_constant!(9.218315187605315e-11), m64x8_constant!(-2.129967838427791e-11),
+    m64x8_constant!(5.139639673482343e-12), m64x8_constant!(-1.2891739609498229e-12),
+    m64x8_constant!(3.348419666052243e-13), m64x8_constant!(-8.976705182010146e-14),
+    m64x8_constant!(2.4771544242195988e-14), m64x8_constant!(-7.0198370892147685e-15),
+    m64x8_constant!(2.038703166239861e-15), m64x8_constant!(-6.057047270643018e-16),
+    m64x8_constant!(1.8380935752430455e-16), m64x8_constant!(-5.689462849193648e-17),
+    m64x8_constant!(1.7940510478863572e-17), m64x8_constant!(-5.7567444820733025e-18),
+    m64x8_constant!(1.8778651901623268e-18)
+];
+const D512_I0T: [__m512d; 24] = [
+    m64x8_constant!(1.0), m64x8_constant!(0.25), m64x8_constant!(0.015625), m64x8_constant!(0.00043402777777777775),
+    m64x8_constant!(6.781684027777777e-06), m64x8_constant!(6.781684027777778e-08),
+    m64x8_constant!(4.709502797067901e-10), m64x8_constant!(2.4028075495244395e-12),
+    m64x8_constant!(9.385966990329842e-15), m64x8_constant!(2.896903392077112e-17),
+    m64x8_constant!(7.242258480192779e-20), m64x8_constant!(1.4963343967340453e-22),
+    m64x8_constant!(2.5978027721077174e-25), m64x8_constant!(3.842903509035085e-28),
+    m64x8_constant!(4.9016626390753635e-31), m64x8_constant!(5.4462918211948485e-34),
+    m64x8_constant!(5.318644356635594e-37), m64x8_constant!(4.60090342269515e-40),
+    m64x8_constant!(3.5500798014623073e-43), m64x8_constant!(2.458504017633177e-46),
+    m64x8_constant!(1.5365650110207356e-49), m64x8_constant!(8.71068600351891e-53),
+    m64x8_constant!(4.499321282809354e-56), m64x8_constant!(2.1263333094562166e-59)
+];
+const D512_I1T: [__m512d; 24] = [
+    m64x8_constant!(0.5), m64x8_constant!(0.0625), m64x8_constant!(0.0026041666666666665),
+    m64x8_constant!(5.425347222222222e-05), m64x8_constant!(6.781684027777778e-07),
+    m64x8_constant!(5.651403356481481e-09), m64x8_constant!(3.363930569334215e-11),
+    m64x8_constant!(1.5017547184527747e-13), m64x8_constant!(5.214426105738801e-16),
+    m64x8_constant!(1.4484516960385557e-18), m64x8_constant!(3.2919356728148996e-21),
+    m64x8_constant!(6.234726653058522e-24), m64x8_constant!(9.991549123491221e-27),
+    m64x8_constant!(1.3724655389411017e-29), m64x8_constant!(1.6338875463584545e-32),
+    m64x8_constant!(1.7019661941233902e-35), m64x8_constant!(1.564307163716351e-38),
+    m64x8_constant!(1.2780287285264307e-41), m64x8_constant!(9.342315267006072e-45),
+    m64x8_constant!(6.146260044082942e-48), m64x8_constant!(3.658488121477942e-51),
+    m64x8_constant!(1.9797013644361157e-54), m64x8_constant!(9.781133223498595e-58),
+    m64x8_constant!(4.4298610613671176e-61)
+];
@@ -0,0 +1,818 @@
+use std::arch::x86_64::*;
+use super::*;
+
+
+// Every distribution function f(x, params.., y) also comes as fx(x, params.., y) with scalar parameters, in the way
+// pow and powx do, plus an _mm512_f_pd(x, params..) wrapper.
+macro_rules! dist_fn {
+    ($name:ident, $fun:ident, [$($p:ident),+]) => {
+        paste::paste! {
+            unroll_fn_n!($name, $fun, [x, $($p),+] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+            unroll_fn_n!([<$name x>], [<$name x_intr>], [x] [$($p: f64),+] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+
+            #[inline]
+            #[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+            pub unsafe fn [<$name x_intr>](x: &__m512d, $($p: f64,)+ y: &mut __m512d)
+            {
+                $fun(x, $(&_mm512_set1_pd($p),)+ y);
+            }
+
+            #[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+            pub unsafe fn [<_mm512_ $name _pd>](x: __m512d, $($p: __m512d),+) -> __m512d
+            {
+                let mut y = D512_ZERO;
+                $fun(&x, $(&$p,)+ &mut y);
+                y
+            }
+        }
+    };
+}
+
+dist_fn!(normal_pdf, normal_pdf_intr, [mu, sigma]);
+dist_fn!(normal_logpdf, normal_logpdf_intr, [mu, sigma]);
+dist_fn!(normal_cdf, normal_cdf_intr, [mu, sigma]);
+dist_fn!(normal_quantile, normal_quantile_intr, [mu, sigma]);
+dist_fn!(lognormal_pdf, lognormal_pdf_intr, [mu, sigma]);
+dist_fn!(lognormal_logpdf, lognormal_logpdf_intr, [mu, sigma]);
+dist_fn!(lognormal_cdf, lognormal_cdf_intr, [mu, sigma]);
+dist_fn!(lognormal_quantile, lognormal_quantile_intr, [mu, sigma]);
+dist_fn!(exponential_pdf, exponential_pdf_intr, [lambda]);
+dist_fn!(exponential_logpdf, exponential_logpdf_intr, [lambda]);
+dist_fn!(exponential_cdf, exponential_cdf_intr, [lambda]);
+dist_fn!(exponential_quantile, exponential_quantile_intr, [lambda]);
+dist_fn!(student_t_pdf, student_t_pdf_intr, [nu]);
+dist_fn!(student_t_logpdf, student_t_logpdf_intr, [nu]);
+dist_fn!(student_t_cdf, student_t_cdf_intr, [nu]);
+dist_fn!(student_t_quantile, student_t_quantile_intr, [nu]);
+dist_fn!(chi_squared_pdf, chi_squared_pdf_intr, [k]);
+dist_fn!(chi_squared_logpdf, chi_squared_logpdf_intr, [k]);
+dist_fn!(chi_squared_cdf, chi_squared_cdf_intr, [k]);
+dist_fn!(chi_squared_quantile, chi_squared_quantile_intr, [k]);
+dist_fn!(gamma_pdf, gamma_pdf_intr, [shape, scale]);
+dist_fn!(gamma_logpdf, gamma_logpdf_intr, [shape, scale]);
+dist_fn!(gamma_cdf, gamma_cdf_intr, [shape, scale]);
+dist_fn!(gamma_quantile, gamma_quantile_intr, [shape, scale]);
+dist_fn!(beta_pdf, beta_pdf_intr, [a, b]);
+dist_fn!(beta_logpdf, beta_logpdf_intr, [a, b]);
+dist_fn!(beta_cdf, beta_cdf_intr, [a, b]);
+dist_fn!(beta_quantile, beta_quantile_intr, [a, b]);
+dist_fn!(poisson_pmf, poisson_pmf_intr, [lambda]);
+dist_fn!(poisson_logpmf, poisson_logpmf_intr, [lambda]);
+dist_fn!(poisson_cdf, poisson_cdf_intr, [lambda]);
+dist_fn!(poisson_quantile, poisson_quantile_intr, [lambda]);
+dist_fn!(binomial_pmf, binomial_pmf_intr, [n, p]);
+dist_fn!(binomial_logpmf, binomial_logpmf_intr, [n, p]);
+dist_fn!(binomial_cdf, binomial_cdf_intr, [n, p]);
+dist_fn!(binomial_quantile, binomial_quantile_intr, [n, p]);
+
+
+/// Density of the normal distribution with mean mu and standard deviation sigma.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn normal_pdf_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let z = _mm512_div_pd(_mm512_sub_pd(*x, *mu), *sigma);
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_mul_pd(_mm512_mul_pd(z, z), D512_NEGHALF), &mut e);
+    let yy = _mm512_div_pd(_mm512_mul_pd(e, D512_ONE_OVER_SQRT_2PI), *sigma);
+    *y = _mm512_mask_blend_pd(positive(*sigma), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn normal_logpdf_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let z = _mm512_div_pd(_mm512_sub_pd(*x, *mu), *sigma);
+    let mut ls = D512_ZERO;
+    ln_intr(sigma, &mut ls);
+    let yy = _mm512_fmadd_pd(_mm512_mul_pd(z, z), D512_NEGHALF, _mm512_sub_pd(D512_NEG_LN_SQRT_2PI, ls));
+    *y = _mm512_mask_blend_pd(positive(*sigma), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn normal_cdf_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let z = _mm512_div_pd(_mm512_sub_pd(*x, *mu), *sigma);
+    *y = _mm512_mask_blend_pd(positive(*sigma), D512_NAN, _mm512_std_norm_cdf_pd(z));
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn normal_quantile_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let yy = _mm512_fmadd_pd(*sigma, _mm512_std_norm_inv_cdf_pd(*x), *mu);
+    *y = _mm512_mask_blend_pd(positive(*sigma), D512_NAN, yy);
+}
+
+/// Density of the log-normal distribution, where ln(x) has mean mu and standard deviation sigma.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn lognormal_pdf_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let mut lx = D512_ZERO;
+    ln_intr(x, &mut lx);
+    let mut yy = D512_ZERO;
+    normal_pdf_intr(&lx, mu, sigma, &mut yy);
+    yy = outside(_mm512_cmple_pd_mask(*x, D512_ZERO), _mm512_div_pd(yy, *x), D512_ZERO);
+    *y = _mm512_mask_blend_pd(positive(*sigma), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn lognormal_logpdf_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let mut lx = D512_ZERO;
+    ln_intr(x, &mut lx);
+    let mut yy = D512_ZERO;
+    normal_logpdf_intr(&lx, mu, sigma, &mut yy);
+    yy = outside(_mm512_cmple_pd_mask(*x, D512_ZERO), _mm512_sub_pd(yy, lx), D512_NEGATIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(positive(*sigma), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn lognormal_cdf_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let mut lx = D512_ZERO;
+    ln_intr(x, &mut lx);
+    let mut yy = D512_ZERO;
+    normal_cdf_intr(&lx, mu, sigma, &mut yy);
+    yy = outside(_mm512_cmple_pd_mask(*x, D512_ZERO), yy, D512_ZERO);
+    *y = _mm512_mask_blend_pd(positive(*sigma), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn lognormal_quantile_intr(x: &__m512d, mu: &__m512d, sigma: &__m512d, y: &mut __m512d)
+{
+    let mut yy = D512_ZERO;
+    normal_quantile_intr(x, mu, sigma, &mut yy);
+    exp_intr(&yy, y);
+}
+
+/// Density of the exponential distribution with rate lambda.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn exponential_pdf_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_mul_pd(_mm512_sub_pd(D512_ZERO, *lambda), *x), &mut e);
+    let yy = outside(_mm512_cmplt_pd_mask(*x, D512_ZERO), _mm512_mul_pd(*lambda, e), D512_ZERO);
+    *y = _mm512_mask_blend_pd(positive(*lambda), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn exponential_logpdf_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    let mut ll = D512_ZERO;
+    ln_intr(lambda, &mut ll);
+    let yy = outside(_mm512_cmplt_pd_mask(*x, D512_ZERO), _mm512_fnmadd_pd(*lambda, *x, ll), D512_NEGATIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(positive(*lambda), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn exponential_cdf_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    // 1 - e^(-lambda x), without the cancellation for small lambda x
+    let yy = _mm512_sub_pd(D512_ZERO, expm1(_mm512_mul_pd(_mm512_sub_pd(D512_ZERO, *lambda), *x)));
+    let yy = outside(_mm512_cmplt_pd_mask(*x, D512_ZERO), yy, D512_ZERO);
+    *y = _mm512_mask_blend_pd(positive(*lambda), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn exponential_quantile_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    let yy = _mm512_div_pd(log1p(_mm512_sub_pd(D512_ZERO, *x)), _mm512_sub_pd(D512_ZERO, *lambda));
+    *y = _mm512_mask_blend_pd(positive(*lambda) & probability(*x), D512_NAN, yy);
+}
+
+/// Density of Student's t distribution with nu degrees of freedom.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn student_t_pdf_intr(x: &__m512d, nu: &__m512d, y: &mut __m512d)
+{
+    let mut yy = D512_ZERO;
+    student_t_logpdf_intr(x, nu, &mut yy);
+    exp_intr(&yy, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn student_t_logpdf_intr(x: &__m512d, nu: &__m512d, y: &mut __m512d)
+{
+    // ln(gamma((nu + 1)/2)/(gamma(nu/2) sqrt(nu pi))) - (nu + 1)/2 ln(1 + x^2/nu)
+    let h = _mm512_mul_pd(*nu, D512_HALF);
+    let hp = _mm512_add_pd(h, D512_HALF);
+    let mut lg = D512_ZERO;
+    let mut lgp = D512_ZERO;
+    lgamma_intr(&h, &mut lg);
+    lgamma_intr(&hp, &mut lgp);
+    let mut lnu = D512_ZERO;
+    ln_intr(&_mm512_mul_pd(*nu, D512_PI), &mut lnu);
+    let c = _mm512_fmadd_pd(lnu, D512_NEGHALF, _mm512_sub_pd(lgp, lg));
+
+    let l = log1p(_mm512_div_pd(_mm512_mul_pd(*x, *x), *nu));
+    let yy = _mm512_fnmadd_pd(hp, l, c);
+    *y = _mm512_mask_blend_pd(positive(*nu), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn student_t_cdf_intr(x: &__m512d, nu: &__m512d, y: &mut __m512d)
+{
+    // P(|T| > |x|) = I_w(nu/2, 1/2), with w = nu/(nu + x^2) and 1 - w = x^2/(nu + x^2) both kept precise
+    let h = _mm512_mul_pd(*nu, D512_HALF);
+    let xsq = _mm512_mul_pd(*x, *x);
+    let den = _mm512_add_pd(*nu, xsq);
+    let wc = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(xsq, D512_POSITIVE_INFINITY), _mm512_div_pd(xsq, den), D512_ONE);
+    let ib = beta_inc_xc(h, D512_HALF, _mm512_div_pd(*nu, den), wc);
+
+    // ib/2 is the tail beyond |x|
+    let tail = _mm512_mul_pd(ib, D512_HALF);
+    let yy = _mm512_mask_sub_pd(tail, _mm512_cmp_pd_mask(*x, D512_ZERO, _CMP_GT_OQ), D512_ONE, tail);
+    *y = _mm512_mask_blend_pd(positive(*nu), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn student_t_quantile_intr(x: &__m512d, nu: &__m512d, y: &mut __m512d)
+{
+    // With q = P(|T| > |t|) = 2min(p, 1 - p), t^2 = nu(1 - w)/w where I_w(nu/2, 1/2) = q, or t^2 = nu v/(1 - v)
+    // where I_v(1/2, nu/2) = 1 - q. Solving for whichever of w and v = 1 - w is below 1/2 keeps it precise.
+    let h = _mm512_mul_pd(*nu, D512_HALF);
+    let mut half = D512_ZERO;
+    beta_inc_intr(&h, &D512_HALF, &D512_HALF, &mut half);
+    let one_less_q = _mm512_abs_pd(_mm512_fmsub_pd(*x, D512_TWO, D512_ONE));
+    let q = _mm512_mul_pd(_mm512_min_pd(*x, _mm512_sub_pd(D512_ONE, *x)), D512_TWO);
+    let tail_mask = _mm512_cmple_pd_mask(q, half);
+    let a = _mm512_mask_blend_pd(tail_mask, D512_HALF, h);
+    let b = _mm512_mask_blend_pd(tail_mask, h, D512_HALF);
+    let r = inv_beta_inc(a, b, _mm512_mask_blend_pd(tail_mask, one_less_q, q), _mm512_mask_blend_pd(tail_mask, q, one_less_q));
+
+    let rc = _mm512_sub_pd(D512_ONE, r);
+    let tsq = _mm512_mul_pd(*nu, _mm512_mask_blend_pd(tail_mask, _mm512_div_pd(r, rc), _mm512_div_pd(rc, r)));
+    let mut yy = _mm512_sqrt_pd(tsq);
+    yy = _mm512_mask_sub_pd(yy, _mm512_cmplt_pd_mask(*x, D512_HALF), D512_ZERO, yy);
+    *y = _mm512_mask_blend_pd(positive(*nu) & probability(*x), D512_NAN, yy);
+}
+
+/// Density of the chi-squared distribution with k degrees of freedom.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn chi_squared_pdf_intr(x: &__m512d, k: &__m512d, y: &mut __m512d)
+{
+    gamma_pdf_intr(x, &_mm512_mul_pd(*k, D512_HALF), &D512_TWO, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn chi_squared_logpdf_intr(x: &__m512d, k: &__m512d, y: &mut __m512d)
+{
+    gamma_logpdf_intr(x, &_mm512_mul_pd(*k, D512_HALF), &D512_TWO, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn chi_squared_cdf_intr(x: &__m512d, k: &__m512d, y: &mut __m512d)
+{
+    gamma_cdf_intr(x, &_mm512_mul_pd(*k, D512_HALF), &D512_TWO, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn chi_squared_quantile_intr(x: &__m512d, k: &__m512d, y: &mut __m512d)
+{
+    gamma_quantile_intr(x, &_mm512_mul_pd(*k, D512_HALF), &D512_TWO, y);
+}
+
+/// Density of the gamma distribution with the given shape and scale (not rate).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gamma_pdf_intr(x: &__m512d, shape: &__m512d, scale: &__m512d, y: &mut __m512d)
+{
+    let mut yy = D512_ZERO;
+    gamma_logpdf_intr(x, shape, scale, &mut yy);
+    exp_intr(&yy, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gamma_logpdf_intr(x: &__m512d, shape: &__m512d, scale: &__m512d, y: &mut __m512d)
+{
+    // (k - 1)ln(x/theta) - x/theta - ln(gamma(k)) - ln(theta), with (k - 1)ln(x/theta) = 0 for k = 1, even at x = 0
+    let xs = _mm512_div_pd(*x, *scale);
+    let mut lx = D512_ZERO;
+    let mut ls = D512_ZERO;
+    let mut lg = D512_ZERO;
+    ln_intr(&xs, &mut lx);
+    ln_intr(scale, &mut ls);
+    lgamma_intr(shape, &mut lg);
+    let k1 = _mm512_sub_pd(*shape, D512_ONE);
+    let mut yy = _mm512_maskz_mul_pd(_mm512_cmpneq_pd_mask(k1, D512_ZERO), k1, lx);
+    yy = _mm512_sub_pd(_mm512_sub_pd(yy, xs), _mm512_add_pd(lg, ls));
+    yy = outside(_mm512_cmplt_pd_mask(*x, D512_ZERO), yy, D512_NEGATIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(positive(*shape) & positive(*scale), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gamma_cdf_intr(x: &__m512d, shape: &__m512d, scale: &__m512d, y: &mut __m512d)
+{
+    let mut yy = D512_ZERO;
+    gamma_p_intr(shape, &_mm512_max_pd(_mm512_div_pd(*x, *scale), D512_ZERO), &mut yy);
+    yy = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x, *x), yy, *x);
+    *y = _mm512_mask_blend_pd(positive(*scale), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gamma_quantile_intr(x: &__m512d, shape: &__m512d, scale: &__m512d, y: &mut __m512d)
+{
+    let yy = _mm512_mul_pd(inv_gamma_p(*shape, *x), *scale);
+    *y = _mm512_mask_blend_pd(positive(*scale), D512_NAN, yy);
+}
+
+/// Density of the beta distribution with shape parameters a and b.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn beta_pdf_intr(x: &__m512d, a: &__m512d, b: &__m512d, y: &mut __m512d)
+{
+    let mut yy = D512_ZERO;
+    beta_logpdf_intr(x, a, b, &mut yy);
+    exp_intr(&yy, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn beta_logpdf_intr(x: &__m512d, a: &__m512d, b: &__m512d, y: &mut __m512d)
+{
+    // (a - 1)ln(x) + (b - 1)ln(1 - x) - ln(B(a, b)), where either term is 0 when its exponent is
+    let mut lx = D512_ZERO;
+    ln_intr(x, &mut lx);
+    let lxc = log1p(_mm512_sub_pd(D512_ZERO, *x));
+    let a1 = _mm512_sub_pd(*a, D512_ONE);
+    let b1 = _mm512_sub_pd(*b, D512_ONE);
+    let mut yy = _mm512_maskz_mul_pd(_mm512_cmpneq_pd_mask(a1, D512_ZERO), a1, lx);
+    yy = _mm512_add_pd(yy, _mm512_maskz_mul_pd(_mm512_cmpneq_pd_mask(b1, D512_ZERO), b1, lxc));
+    yy = _mm512_sub_pd(yy, ln_beta(*a, *b));
+
+    let outside_mask = _mm512_cmplt_pd_mask(*x, D512_ZERO) | _mm512_cmp_pd_mask(*x, D512_ONE, _CMP_GT_OQ);
+    yy = outside(outside_mask, yy, D512_NEGATIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(positive(*a) & positive(*b), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn beta_cdf_intr(x: &__m512d, a: &__m512d, b: &__m512d, y: &mut __m512d)
+{
+    beta_inc_intr(a, b, &_mm512_min_pd(_mm512_max_pd(*x, D512_ZERO), D512_ONE), y);
+    *y = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x, *x), *y, *x);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn beta_quantile_intr(x: &__m512d, a: &__m512d, b: &__m512d, y: &mut __m512d)
+{
+    *y = inv_beta_inc(*a, *b, *x, _mm512_sub_pd(D512_ONE, *x));
+}
+
+/// Probability mass of the Poisson distribution with mean lambda. Non-integer x have zero mass.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn poisson_pmf_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    let mut yy = D512_ZERO;
+    poisson_logpmf_intr(x, lambda, &mut yy);
+    exp_intr(&yy, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn poisson_logpmf_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    // k ln(lambda) - lambda - ln(k!)
+    let mut ll = D512_ZERO;
+    let mut lg = D512_ZERO;
+    ln_intr(lambda, &mut ll);
+    lgamma_intr(&_mm512_add_pd(*x, D512_ONE), &mut lg);
+    let mut yy = _mm512_maskz_mul_pd(_mm512_cmpneq_pd_mask(*x, D512_ZERO), *x, ll);
+    yy = _mm512_sub_pd(yy, _mm512_add_pd(*lambda, lg));
+
+    yy = outside(!support(*x), yy, D512_NEGATIVE_INFINITY);
+    let valid_mask = _mm512_cmp_pd_mask(*lambda, D512_ZERO, _CMP_GE_OQ) & finite(*lambda);
+    *y = _mm512_mask_blend_pd(valid_mask, D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn poisson_cdf_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    // P(X <= k) = Q(k + 1, lambda)
+    let k = _mm512_roundscale_pd(*x, _MM_FROUND_TO_NEG_INF);
+    let mut yy = D512_ZERO;
+    gamma_q_intr(&_mm512_add_pd(_mm512_max_pd(k, D512_ZERO), D512_ONE), lambda, &mut yy);
+    yy = outside(_mm512_cmplt_pd_mask(*x, D512_ZERO), yy, D512_ZERO);
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*x, D512_POSITIVE_INFINITY), yy, D512_ONE);
+    yy = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x, *x), yy, *x);
+    let valid_mask = _mm512_cmp_pd_mask(*lambda, D512_ZERO, _CMP_GE_OQ) & finite(*lambda);
+    *y = _mm512_mask_blend_pd(valid_mask, D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn poisson_quantile_intr(x: &__m512d, lambda: &__m512d, y: &mut __m512d)
+{
+    // Start from the Cornish-Fisher expansion, lambda + sqrt(lambda)z + (z^2 - 1)/6, and search from there.
+    let z = _mm512_std_norm_inv_cdf_pd(*x);
+    let mut guess = _mm512_fmadd_pd(_mm512_sqrt_pd(*lambda), z, *lambda);
+    guess = _mm512_add_pd(guess, _mm512_mul_pd(_mm512_fmsub_pd(z, z, D512_ONE), D512_ONE_SIXTH));
+
+    let yy = discrete_quantile(*x, guess, D512_POSITIVE_INFINITY, |k| {
+        let mut c = D512_ZERO;
+        poisson_cdf_intr(&k, lambda, &mut c);
+        c
+    });
+    let valid_mask = _mm512_cmp_pd_mask(*lambda, D512_ZERO, _CMP_GE_OQ) & finite(*lambda) & probability(*x);
+    *y = _mm512_mask_blend_pd(valid_mask, D512_NAN, yy);
+}
+
+/// Probability mass of the binomial distribution with n trials of success probability p. Non-integer x have zero
+/// mass.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn binomial_pmf_intr(x: &__m512d, n: &__m512d, p: &__m512d, y: &mut __m512d)
+{
+    let mut yy = D512_ZERO;
+    binomial_logpmf_intr(x, n, p, &mut yy);
+    exp_intr(&yy, y);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn binomial_logpmf_intr(x: &__m512d, n: &__m512d, p: &__m512d, y: &mut __m512d)
+{
+    // ln(n!/(k!(n - k)!)) + k ln(p) + (n - k)ln(1 - p)
+    let nk = _mm512_sub_pd(*n, *x);
+    let mut ln = D512_ZERO;
+    let mut lk = D512_ZERO;
+    let mut lnk = D512_ZERO;
+    lgamma_intr(&_mm512_add_pd(*n, D512_ONE), &mut ln);
+    lgamma_intr(&_mm512_add_pd(*x, D512_ONE), &mut lk);
+    lgamma_intr(&_mm512_add_pd(nk, D512_ONE), &mut lnk);
+    let mut lp = D512_ZERO;
+    ln_intr(p, &mut lp);
+    let lpc = log1p(_mm512_sub_pd(D512_ZERO, *p));
+
+    let mut yy = _mm512_sub_pd(ln, _mm512_add_pd(lk, lnk));
+    yy = _mm512_add_pd(yy, _mm512_maskz_mul_pd(_mm512_cmpneq_pd_mask(*x, D512_ZERO), *x, lp));
+    yy = _mm512_add_pd(yy, _mm512_maskz_mul_pd(_mm512_cmpneq_pd_mask(nk, D512_ZERO), nk, lpc));
+
+    yy = outside(!support(*x) | _mm512_cmplt_pd_mask(nk, D512_ZERO), yy, D512_NEGATIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(binomial_valid(*n, *p), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn binomial_cdf_intr(x: &__m512d, n: &__m512d, p: &__m512d, y: &mut __m512d)
+{
+    // P(X <= k) = I_(1-p)(n - k, k + 1)
+    let k = _mm512_roundscale_pd(*x, _MM_FROUND_TO_NEG_INF);
+    let mut yy = D512_ZERO;
+    beta_inc_intr(&_mm512_sub_pd(*n, k), &_mm512_add_pd(k, D512_ONE), &_mm512_sub_pd(D512_ONE, *p), &mut yy);
+    yy = outside(_mm512_cmp_pd_mask(k, *n, _CMP_GE_OQ), yy, D512_ONE);
+    yy = outside(_mm512_cmplt_pd_mask(*x, D512_ZERO), yy, D512_ZERO);
+    *y = _mm512_mask_blend_pd(binomial_valid(*n, *p), D512_NAN, yy);
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn binomial_quantile_intr(x: &__m512d, n: &__m512d, p: &__m512d, y: &mut __m512d)
+{
+    // Start from the Cornish-Fisher expansion, np + s(z + (1 - 2p)(z^2 - 1)/(6s)) with s = sqrt(np(1 - p)), and
+    // search from there.
+    let z = _mm512_std_norm_inv_cdf_pd(*x);
+    let np = _mm512_mul_pd(*n, *p);
+    let s = _mm512_sqrt_pd(_mm512_mul_pd(np, _mm512_sub_pd(D512_ONE, *p)));
+    let skew = _mm512_mul_pd(_mm512_fnmadd_pd(D512_TWO, *p, D512_ONE), D512_ONE_SIXTH);
+    let mut guess = _mm512_fmadd_pd(s, z, np);
+    guess = _mm512_fmadd_pd(skew, _mm512_fmsub_pd(z, z, D512_ONE), guess);
+
+    let yy = discrete_quantile(*x, guess, *n, |k| {
+        let mut c = D512_ZERO;
+        binomial_cdf_intr(&k, n, p, &mut c);
+        c
+    });
+    *y = _mm512_mask_blend_pd(binomial_valid(*n, *p) & probability(*x), D512_NAN, yy);
+}
+
+/// The smallest integer k in [0, max] with cdf(k) >= p. It gallops outwards from guess with doubling steps
+/// until cdf(lo) < p <= cdf(hi), taking cdf(-1) = 0 and cdf(max) = 1, and then bisects between them. Both phases
+/// at least halve or double a distance every round, so they finish in a bounded number of rounds however poor the
+/// guess is.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn discrete_quantile<F>(p: __m512d, guess: __m512d, max: __m512d, cdf: F) -> __m512d
+    where F: Fn(__m512d) -> __m512d
+{
+    let one_mask = _mm512_cmpeq_pd_mask(p, D512_ONE);
+    let mut k = _mm512_roundscale_pd(guess, _MM_FROUND_TO_NEG_INF);
+    k = _mm512_min_pd(_mm512_max_pd(k, D512_ZERO), max);
+    let mut lo = k;
+    let mut hi = k;
+
+    let below_mask = _mm512_cmplt_pd_mask(cdf(k), p);
+    let mut up = !one_mask & below_mask;
+    let mut down = !one_mask & !below_mask;
+    let mut step = D512_ONE;
+    while up | down != 0
+    {
+        let t = _mm512_mask_blend_pd(up, _mm512_max_pd(_mm512_sub_pd(hi, step), D512_NEGONE), _mm512_min_pd(_mm512_add_pd(lo, step), max));
+        let ge_mask = _mm512_cmp_pd_mask(cdf(t), p, _CMP_GE_OQ);
+
+        let up_done = up & (ge_mask | _mm512_cmpeq_pd_mask(t, max));
+        hi = _mm512_mask_mov_pd(hi, up_done, t);
+        lo = _mm512_mask_mov_pd(lo, up & !up_done, t);
+        let down_done = down & (!ge_mask | _mm512_cmpeq_pd_mask(t, D512_NEGONE));
+        lo = _mm512_mask_mov_pd(lo, down_done, t);
+        hi = _mm512_mask_mov_pd(hi, down & !down_done, t);
+
+        up &= !up_done;
+        down &= !down_done;
+        step = _mm512_add_pd(step, step);
+    }
+
+    let mut active = !one_mask & _mm512_cmp_pd_mask(_mm512_sub_pd(hi, lo), D512_ONE, _CMP_GT_OQ);
+    while active != 0
+    {
+        let mid = _mm512_roundscale_pd(_mm512_mul_pd(_mm512_add_pd(lo, hi), D512_HALF), _MM_FROUND_TO_NEG_INF);
+        active &= _mm512_cmp_pd_mask(mid, lo, _CMP_GT_OQ) & _mm512_cmplt_pd_mask(mid, hi);
+        let ge_mask = _mm512_cmp_pd_mask(cdf(mid), p, _CMP_GE_OQ);
+        hi = _mm512_mask_mov_pd(hi, active & ge_mask, mid);
+        lo = _mm512_mask_mov_pd(lo, active & !ge_mask, mid);
+        active &= _mm512_cmp_pd_mask(_mm512_sub_pd(hi, lo), D512_ONE, _CMP_GT_OQ);
+    }
+
+    _mm512_mask_blend_pd(one_mask, hi, max)
+}
+
+/// The x with P(a, x) = p, by Newton's method from the starting points in Numerical Recipes (invgammp).
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn inv_gamma_p(a: __m512d, p: __m512d) -> __m512d
+{
+    let big_mask = _mm512_cmp_pd_mask(a, D512_ONE, _CMP_GT_OQ);
+    let mut gln = D512_ZERO;
+    lgamma_intr(&a, &mut gln);
+
+    // a > 1: Wilson-Hilferty, a(1 - 1/(9a) + z/(3 sqrt(a)))^3
+    let z = _mm512_std_norm_inv_cdf_pd(p);
+    let mut w = _mm512_sub_pd(D512_ONE, _mm512_div_pd(D512_ONE_NINTH, a));
+    w = _mm512_add_pd(w, _mm512_div_pd(_mm512_mul_pd(z, D512_ONE_THIRD), _mm512_sqrt_pd(a)));
+    let x_big = _mm512_max_pd(_mm512_mul_pd(a, _mm512_mul_pd(_mm512_mul_pd(w, w), w)), D512_THOUSANDTH);
+
+    // a <= 1: (p/t)^(1/a) below t = 1 - a(0.253 + 0.12a), and 1 - ln(1 - (p - t)/(1 - t)) above it
+    let t = _mm512_fnmadd_pd(a, _mm512_fmadd_pd(a, D512_IG_T1, D512_IG_T0), D512_ONE);
+    let mut x_low = D512_ZERO;
+    pow_intr(&_mm512_div_pd(p, t), &_mm512_div_pd(D512_ONE, a), &mut x_low);
+    let mut x_high = D512_ZERO;
+    ln_intr(&_mm512_sub_pd(D512_ONE, _mm512_div_pd(_mm512_sub_pd(p, t), _mm512_sub_pd(D512_ONE, t))), &mut x_high);
+    x_high = _mm512_sub_pd(D512_ONE, x_high);
+    let x_small = _mm512_mask_blend_pd(_mm512_cmplt_pd_mask(p, t), x_high, x_low);
+
+    let mut x = _mm512_mask_blend_pd(big_mask, x_small, x_big);
+
+    // Match whichever of P and Q is the smaller, in the log of both it and x, where it is close to a power law.
+    let upper_mask = _mm512_cmp_pd_mask(p, D512_HALF, _CMP_GT_OQ);
+    let target = _mm512_mask_sub_pd(p, upper_mask, D512_ONE, p);
+    let valid_mask = positive(a) & probability(p);
+    let r_of = |x: __m512d| {
+        let (pp, qq) = gamma_pq(a, x);
+        _mm512_mask_blend_pd(upper_mask, pp, qq)
+    };
+    let mut lo = D512_LN_MIN;
+    let mut hi = D512_LN_MAX;
+    let mut active = valid_mask & _mm512_cmp_pd_mask(p, D512_ZERO, _CMP_GT_OQ) & _mm512_cmplt_pd_mask(p, D512_ONE);
+    let mut i = 0;
+    while active != 0 && i < MAX_NEWTON
+    {
+        let r = r_of(x);
+
+        // x dP/dx = x^a e^-x/gamma(a)
+        let mut lx = D512_ZERO;
+        ln_intr(&x, &mut lx);
+        (lo, hi) = narrow(active, r, target, upper_mask, lx, lo, hi);
+        let mut xdens = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(_mm512_fmsub_pd(a, lx, x), gln), &mut xdens);
+
+        let mut step = ln_newton_step(r, target, xdens);
+        step = _mm512_mask_sub_pd(step, upper_mask, D512_ZERO, step);
+        let mut e = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(D512_ZERO, step), &mut e);
+        x = _mm512_mask_mul_pd(x, active, x, e);
+
+        active &= _mm512_cmp_pd_mask(_mm512_abs_pd(step), D512_NEWTON_TOL, _CMP_GE_OQ);
+        i += 1;
+    }
+    x = bisect_ln(x, active, r_of, target, upper_mask, lo, hi);
+
+    x = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(p, D512_ZERO), x, D512_ZERO);
+    x = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(p, D512_ONE), x, D512_POSITIVE_INFINITY);
+    _mm512_mask_blend_pd(valid_mask, D512_NAN, x)
+}
+
+/// The x with I_x(a, b) = p, by Newton's method from the starting points in Numerical Recipes (invbetai). pc is 1 - p,
+/// which the caller may know more precisely.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn inv_beta_inc(a: __m512d, b: __m512d, p: __m512d, pc: __m512d) -> __m512d
+{
+    // Solve for whichever of x and 1 - x is below 1/2, with I_x(a, b) = 1 - I_(1-x)(b, a), so that it stays precise.
+    let valid_mask = positive(a) & positive(b) & probability(p);
+    let mut half = D512_ZERO;
+    beta_inc_intr(&a, &b, &D512_HALF, &mut half);
+    let flip_mask = _mm512_cmp_pd_mask(p, half, _CMP_GT_OQ);
+    let (a, b) = (_mm512_mask_blend_pd(flip_mask, a, b), _mm512_mask_blend_pd(flip_mask, b, a));
+    let (p, pc) = (_mm512_mask_blend_pd(flip_mask, p, pc), _mm512_mask_blend_pd(flip_mask, pc, p));
+    let b1 = _mm512_sub_pd(b, D512_ONE);
+    let lbeta = ln_beta(a, b);
+
+    // a, b >= 1: a/(a + b e^(2w)), from the normal approximation of Abramowitz and Stegun 26.5.22
+    let big_mask = _mm512_cmp_pd_mask(a, D512_ONE, _CMP_GE_OQ) & _mm512_cmp_pd_mask(b, D512_ONE, _CMP_GE_OQ);
+    let z = _mm512_std_norm_inv_cdf_pd(p);
+    let al = _mm512_mul_pd(_mm512_fmsub_pd(z, z, D512_THREE), D512_ONE_SIXTH);
+    let ra = _mm512_div_pd(D512_ONE, _mm512_fmsub_pd(D512_TWO, a, D512_ONE));
+    let rb = _mm512_div_pd(D512_ONE, _mm512_fmsub_pd(D512_TWO, b, D512_ONE));
+    let h = _mm512_div_pd(D512_TWO, _mm512_add_pd(ra, rb));
+    let mut w = _mm512_div_pd(_mm512_mul_pd(z, _mm512_sqrt_pd(_mm512_add_pd(al, h))), h);
+    w = _mm512_sub_pd(D512_ZERO, w);
+    let c = _mm512_sub_pd(_mm512_add_pd(al, D512_FIVE_SIXTHS), _mm512_div_pd(D512_TWO_THIRDS, h));
+    w = _mm512_fnmadd_pd(_mm512_sub_pd(rb, ra), c, w);
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_add_pd(w, w), &mut e);
+    let x_big = _mm512_div_pd(a, _mm512_fmadd_pd(b, e, a));
+
+    // otherwise: (a s p)^(1/a) below t/s and 1 - (b s (1 - p))^(1/b) above it, with t = (a/(a + b))^a/a,
+    // u = (b/(a + b))^b/b and s = t + u
+    let ab = _mm512_add_pd(a, b);
+    let mut t = D512_ZERO;
+    let mut u = D512_ZERO;
+    pow_intr(&_mm512_div_pd(a, ab), &a, &mut t);
+    pow_intr(&_mm512_div_pd(b, ab), &b, &mut u);
+    t = _mm512_div_pd(t, a);
+    u = _mm512_div_pd(u, b);
+    let s = _mm512_add_pd(t, u);
+    let mut x_low = D512_ZERO;
+    let mut x_high = D512_ZERO;
+    pow_intr(&_mm512_mul_pd(_mm512_mul_pd(a, s), p), &_mm512_div_pd(D512_ONE, a), &mut x_low);
+    pow_intr(&_mm512_mul_pd(_mm512_mul_pd(b, s), _mm512_sub_pd(D512_ONE, p)), &_mm512_div_pd(D512_ONE, b), &mut x_high);
+    x_high = _mm512_sub_pd(D512_ONE, x_high);
+    let x_small = _mm512_mask_blend_pd(_mm512_cmplt_pd_mask(_mm512_mul_pd(p, s), t), x_high, x_low);
+
+    // the normal approximation falls apart deep in the tails, where the power law takes over
+    let big_mask = big_mask & _mm512_cmp_pd_mask(x_big, D512_ZERO, _CMP_GT_OQ) & _mm512_cmplt_pd_mask(x_big, D512_ONE);
+    let mut x = _mm512_mask_blend_pd(big_mask, x_small, x_big);
+
+    // Then, as for the gamma function, match whichever of I and 1 - I is the smaller, in the log of both it and x.
+    let upper_mask = _mm512_cmp_pd_mask(p, D512_HALF, _CMP_GT_OQ);
+    let target = _mm512_mask_blend_pd(upper_mask, p, pc);
+    let ar = _mm512_mask_blend_pd(upper_mask, a, b);
+    let br = _mm512_mask_blend_pd(upper_mask, b, a);
+    let r_of = |x: __m512d| {
+        let xc = _mm512_sub_pd(D512_ONE, x);
+        beta_inc_xc(ar, br, _mm512_mask_blend_pd(upper_mask, x, xc), _mm512_mask_blend_pd(upper_mask, xc, x))
+    };
+    let mut lo = D512_LN_MIN;
+    let mut hi = D512_ZERO;
+    let mut active = valid_mask & _mm512_cmp_pd_mask(p, D512_ZERO, _CMP_GT_OQ);
+    let mut i = 0;
+    while active != 0 && i < MAX_NEWTON
+    {
+        let xc = _mm512_sub_pd(D512_ONE, x);
+        let r = r_of(x);
+
+        // x dI/dx = x^a (1 - x)^(b-1)/B(a, b)
+        let mut lx = D512_ZERO;
+        ln_intr(&x, &mut lx);
+        (lo, hi) = narrow(active, r, target, upper_mask, lx, lo, hi);
+        let mut lxc = D512_ZERO;
+        ln_intr(&xc, &mut lxc);
+        let mut xdens = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(_mm512_fmadd_pd(a, lx, _mm512_mul_pd(b1, lxc)), lbeta), &mut xdens);
+
+        let mut step = ln_newton_step(r, target, xdens);
+        step = _mm512_mask_sub_pd(step, upper_mask, D512_ZERO, step);
+        let mut e = D512_ZERO;
+        exp_intr(&_mm512_sub_pd(D512_ZERO, step), &mut e);
+        let mut xn = _mm512_mul_pd(x, e);
+        xn = _mm512_mask_mul_pd(xn, _mm512_cmp_pd_mask(xn, D512_ONE, _CMP_GE_OQ), _mm512_add_pd(x, D512_ONE), D512_HALF);
+        x = _mm512_mask_mov_pd(x, active, xn);
+
+        active &= _mm512_cmp_pd_mask(_mm512_abs_pd(step), D512_NEWTON_TOL, _CMP_GE_OQ);
+        i += 1;
+    }
+    x = bisect_ln(x, active, r_of, target, upper_mask, lo, hi);
+
+    x = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(p, D512_ZERO), x, D512_ZERO);
+    x = _mm512_mask_sub_pd(x, flip_mask, D512_ONE, x);
+    _mm512_mask_blend_pd(valid_mask, D512_NAN, x)
+}
+
+/// The Newton step in ln(x) for ln(r(x)) = ln(target), given x dr/dx. Where r has underflowed it steps up by one.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn ln_newton_step(r: __m512d, target: __m512d, xdens: __m512d) -> __m512d
+{
+    let step = _mm512_mul_pd(log1p(_mm512_div_pd(_mm512_sub_pd(r, target), target)), _mm512_div_pd(r, xdens));
+    let stuck_mask = _mm512_cmpunord_pd_mask(step, step) | _mm512_cmpeq_pd_mask(r, D512_ZERO);
+    let step = _mm512_min_pd(_mm512_max_pd(step, D512_NEG_MAX_STEP), D512_MAX_STEP);
+    _mm512_mask_blend_pd(stuck_mask, step, D512_NEGONE)
+}
+
+/// Narrows the bracket [lo, hi] on ln(x) in the active lanes with the point lx = ln(x), where r = r(x). r is increasing
+/// in x, or decreasing where upper_mask is set.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn narrow(active: __mmask8, r: __m512d, target: __m512d, upper_mask: __mmask8, lx: __m512d, lo: __m512d,
+    hi: __m512d) -> (__m512d, __m512d)
+{
+    let above_mask = (_mm512_cmp_pd_mask(r, target, _CMP_GT_OQ) & !upper_mask) | (_mm512_cmplt_pd_mask(r, target) & upper_mask);
+    (_mm512_mask_max_pd(lo, active & !above_mask, lx, lo), _mm512_mask_min_pd(hi, active & above_mask, lx, hi))
+}
+
+/// Finishes the active lanes, where Newton's method didn't converge within MAX_NEWTON steps, by bisecting ln(x) in
+/// the bracket [lo, hi] that the Newton steps have narrowed. The bracket starts out as every ln(x) a double can hold,
+/// so MAX_BISECT halvings always bring it down to below the Newton tolerance.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn bisect_ln<F>(x: __m512d, active: __mmask8, r_of: F, target: __m512d, upper_mask: __mmask8, mut lo: __m512d,
+    mut hi: __m512d) -> __m512d
+    where F: Fn(__m512d) -> __m512d
+{
+    if active == 0
+    {
+        return x;
+    }
+
+    let mut u = _mm512_mul_pd(_mm512_add_pd(lo, hi), D512_HALF);
+    let mut xm = D512_ZERO;
+    for _ in 0..MAX_BISECT
+    {
+        exp_intr(&u, &mut xm);
+        (lo, hi) = narrow(active, r_of(xm), target, upper_mask, u, lo, hi);
+        u = _mm512_mul_pd(_mm512_add_pd(lo, hi), D512_HALF);
+    }
+
+    exp_intr(&u, &mut xm);
+    _mm512_mask_mov_pd(x, active, xm)
+}
+
+/// ln(B(a, b)) = ln(gamma(a)) + ln(gamma(b)) - ln(gamma(a + b))
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn ln_beta(a: __m512d, b: __m512d) -> __m512d
+{
+    let mut la = D512_ZERO;
+    let mut lb = D512_ZERO;
+    let mut lab = D512_ZERO;
+    lgamma_intr(&a, &mut la);
+    lgamma_intr(&b, &mut lb);
+    lgamma_intr(&_mm512_add_pd(a, b), &mut lab);
+    _mm512_sub_pd(_mm512_add_pd(la, lb), lab)
+}
+
+/// Blends in v where mask is set, for x outside the support.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn outside(mask: __mmask8, y: __m512d, v: __m512d) -> __m512d
+{
+    _mm512_mask_blend_pd(mask, y, v)
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn positive(x: __m512d) -> __mmask8
+{
+    _mm512_cmp_pd_mask(x, D512_ZERO, _CMP_GT_OQ)
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn probability(x: __m512d) -> __mmask8
+{
+    _mm512_cmp_pd_mask(x, D512_ZERO, _CMP_GE_OQ) & _mm512_cmple_pd_mask(x, D512_ONE)
+}
+
+/// The non-negative integers.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn support(x: __m512d) -> __mmask8
+{
+    _mm512_cmp_pd_mask(x, D512_ZERO, _CMP_GE_OQ) & _mm512_cmpeq_pd_mask(x, _mm512_roundscale_pd(x, _MM_FROUND_TO_NEG_INF))
+        & finite(x)
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn binomial_valid(n: __m512d, p: __m512d) -> __mmask8
+{
+    support(n) & probability(p)
+}
+
+
+const MAX_NEWTON: usize = 32;
+const MAX_BISECT: usize = 64;
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_HALF: __m512d = m64x8_constant!(0.5);
+const D512_NEGHALF: __m512d = m64x8_constant!(-0.5);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_NEGONE: __m512d = m64x8_constant!(-1.0);
+const D512_TWO: __m512d = m64x8_constant!(2.0);
+const D512_THREE: __m512d = m64x8_constant!(3.0);
+const D512_ONE_THIRD: __m512d = m64x8_constant!(1.0 / 3.0);
+const D512_TWO_THIRDS: __m512d = m64x8_constant!(2.0 / 3.0);
+const D512_ONE_SIXTH: __m512d = m64x8_constant!(1.0 / 6.0);
+const D512_FIVE_SIXTHS: __m512d = m64x8_constant!(5.0 / 6.0);
+const D512_ONE_NINTH: __m512d = m64x8_constant!(1.0 / 9.0);
+const D512_THOUSANDTH: __m512d = m64x8_constant!(1e-3);
+const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_NEGATIVE_INFINITY: __m512d = m64x8_constant!(f64::NEG_INFINITY);
+const D512_PI: __m512d = m64x8_constant!(std::f64::consts::PI);
+const D512_ONE_OVER_SQRT_2PI: __m512d = m64x8_constant!(0.3989422804014327);
+const D512_NEG_LN_SQRT_2PI: __m512d = m64x8_constant!(-0.9189385332046728);
+const D512_IG_T0: __m512d = m64x8_constant!(0.253);
+const D512_IG_T1: __m512d = m64x8_constant!(0.12);
+const D512_NEWTON_TOL: __m512d = m64x8_constant!(1e-12);
+const D512_MAX_STEP: __m512d = m64x8_constant!(64.0);
+const D512_NEG_MAX_STEP: __m512d = m64x8_constant!(-64.0);
+const D512_LN_MIN: __m512d = m64x8_constant!(-745.2);
+const D512_LN_MAX: __m512d = m64x8_constant!(709.8);
@@ -0,0 +1,637 @@
+use std::arch::x86_64::*;
+use super::*;
+
+
+unroll_fn!(lgamma, lgamma_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(tgamma, tgamma_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(digamma, digamma_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(gamma_p, gamma_p_intr, [a, x] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(gamma_q, gamma_q_intr, [a, x] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(beta_inc, beta_inc_intr, [a, b, x] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_lgamma_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    lgamma_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_tgamma_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    tgamma_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_digamma_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    digamma_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_gamma_p_pd(a: __m512d, x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    gamma_p_intr(&a, &x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_gamma_q_pd(a: __m512d, x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    gamma_q_intr(&a, &x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_beta_inc_pd(a: __m512d, b: __m512d, x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    beta_inc_intr(&a, &b, &x, &mut y);
+    y
+}
+
+/// AVX-512 implementation of ln|gamma(x)|.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn lgamma_intr(x: &__m512d, y: &mut __m512d)
+{
+    // gamma(x)gamma(-x) = -pi/(x sin(pi x)) takes x < -1/2 over to -x > 1/2. Unlike 1 - x, -x is exact.
+    let reflect_mask = _mm512_cmplt_pd_mask(*x, D512_NEGHALF);
+    let xx = _mm512_mask_sub_pd(*x, reflect_mask, D512_ZERO, *x);
+    let mut yy = lgamma_positive(xx);
+
+    let s = _mm512_sinpi_pd(*x);
+    let mut l = D512_ZERO;
+    ln_intr(&_mm512_div_pd(D512_PI, _mm512_abs_pd(_mm512_mul_pd(*x, s))), &mut l);
+    yy = _mm512_mask_sub_pd(yy, reflect_mask, l, yy);
+
+    // the poles at the non-positive integers, and +-inf
+    let pole_mask = reflect_mask & (_mm512_cmpeq_pd_mask(s, D512_ZERO) | _mm512_cmpunord_pd_mask(s, s))
+        | _mm512_cmpeq_pd_mask(*x, D512_ZERO) | _mm512_cmpeq_pd_mask(*x, D512_POSITIVE_INFINITY);
+    yy = _mm512_mask_blend_pd(pole_mask, yy, D512_POSITIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x, *x), yy, *x);
+}
+
+/// AVX-512 implementation of the gamma function.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn tgamma_intr(x: &__m512d, y: &mut __m512d)
+{
+    // gamma(x) = -pi/(x sin(pi x)gamma(-x)) for x < -1/2. gamma(-x) is scaled by 2^-SCALE there so that it
+    // doesn't overflow for the x where gamma(x) is still a normal number.
+    let reflect_mask = _mm512_cmplt_pd_mask(*x, D512_NEGHALF);
+    let xx = _mm512_mask_sub_pd(*x, reflect_mask, D512_ZERO, *x);
+    let scale = _mm512_maskz_mov_pd(reflect_mask, D512_SCALE);
+    let g = tgamma_positive(xx, scale);
+
+    let s = _mm512_sinpi_pd(*x);
+    let mut yy = _mm512_mask_div_pd(g, reflect_mask, D512_NEGPI, _mm512_mul_pd(_mm512_mul_pd(*x, s), g));
+    yy = _mm512_scalef_pd(yy, _mm512_sub_pd(D512_ZERO, scale));
+
+    // the poles at the negative integers, and -inf
+    let pole_mask = reflect_mask & (_mm512_cmpeq_pd_mask(s, D512_ZERO) | _mm512_cmpunord_pd_mask(s, s));
+    yy = _mm512_mask_blend_pd(pole_mask, yy, D512_NAN);
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*x, D512_POSITIVE_INFINITY), yy, D512_POSITIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x, *x), yy, *x);
+}
+
+/// AVX-512 implementation of the digamma function, gamma'(x)/gamma(x).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn digamma_intr(x: &__m512d, y: &mut __m512d)
+{
+    // digamma(x) = digamma(-x) - 1/x - pi*cot(pi x) for x < -1/2.
+    let reflect_mask = _mm512_cmplt_pd_mask(*x, D512_NEGHALF);
+    let xx = _mm512_mask_sub_pd(*x, reflect_mask, D512_ZERO, *x);
+    let mut yy = digamma_positive(xx);
+
+    let s = _mm512_sinpi_pd(*x);
+    let c = _mm512_cospi_pd(*x);
+    let cot = _mm512_div_pd(_mm512_mul_pd(D512_PI, c), s);
+    yy = _mm512_mask_sub_pd(yy, reflect_mask, yy, _mm512_add_pd(_mm512_div_pd(D512_ONE, *x), cot));
+
+    // the poles at the negative integers, and -inf
+    let pole_mask = reflect_mask & (_mm512_cmpeq_pd_mask(s, D512_ZERO) | _mm512_cmpunord_pd_mask(s, s));
+    yy = _mm512_mask_blend_pd(pole_mask, yy, D512_NAN);
+    *y = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x, *x), yy, *x);
+}
+
+/// AVX-512 implementation of the regularized lower incomplete gamma function, P(a, x) = gamma(a, x)/gamma(a), for
+/// a > 0 and x >= 0.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gamma_p_intr(a: &__m512d, x: &__m512d, y: &mut __m512d)
+{
+    let (p, _) = gamma_pq(*a, *x);
+    *y = p;
+}
+
+/// AVX-512 implementation of the regularized upper incomplete gamma function, Q(a, x) = 1 - P(a, x), for a > 0 and
+/// x >= 0. Q is computed directly where it's small, so this is more precise than 1 - gamma_p_intr there.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn gamma_q_intr(a: &__m512d, x: &__m512d, y: &mut __m512d)
+{
+    let (_, q) = gamma_pq(*a, *x);
+    *y = q;
+}
+
+/// AVX-512 implementation of the regularized incomplete beta function, I_x(a, b), for a > 0, b > 0 and x in [0, 1].
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn beta_inc_intr(a: &__m512d, b: &__m512d, x: &__m512d, y: &mut __m512d)
+{
+    *y = beta_inc_xc(*a, *b, *x, _mm512_sub_pd(D512_ONE, *x));
+}
+
+/// I_x(a, b), for callers that know xc = 1 - x more precisely than it rounds to.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn beta_inc_xc(a: __m512d, b: __m512d, x: __m512d, xc: __m512d) -> __m512d
+{
+    let valid_mask = _mm512_cmp_pd_mask(a, D512_ZERO, _CMP_GT_OQ) & _mm512_cmp_pd_mask(b, D512_ZERO, _CMP_GT_OQ)
+        & _mm512_cmp_pd_mask(x, D512_ZERO, _CMP_GE_OQ) & _mm512_cmple_pd_mask(x, D512_ONE);
+
+    // x^a (1 - x)^b/(a B(a, b))
+    let mut lab = D512_ZERO;
+    let mut la = D512_ZERO;
+    let mut lb = D512_ZERO;
+    lgamma_intr(&_mm512_add_pd(a, b), &mut lab);
+    lgamma_intr(&a, &mut la);
+    lgamma_intr(&b, &mut lb);
+    let mut lx = D512_ZERO;
+    let mut lxc = D512_ZERO;
+    ln_intr(&x, &mut lx);
+    ln_intr(&xc, &mut lxc);
+    let mut lf = _mm512_sub_pd(lab, _mm512_add_pd(la, lb));
+    lf = _mm512_fmadd_pd(a, lx, lf);
+    lf = _mm512_fmadd_pd(b, lxc, lf);
+    let mut f = D512_ZERO;
+    exp_intr(&lf, &mut f);
+
+    // The continued fraction converges quickly for x < (a + 1)/(a + b + 2). Past that, I_x(a, b) = 1 - I_(1-x)(b, a).
+    let swap_mask = _mm512_cmp_pd_mask(_mm512_mul_pd(x, _mm512_add_pd(_mm512_add_pd(a, b), D512_TWO)),
+        _mm512_add_pd(a, D512_ONE), _CMP_GT_OQ);
+    let aa = _mm512_mask_blend_pd(swap_mask, a, b);
+    let bb = _mm512_mask_blend_pd(swap_mask, b, a);
+    let xx = _mm512_mask_blend_pd(swap_mask, x, xc);
+
+    let mut yy = _mm512_div_pd(_mm512_mul_pd(f, beta_cf(aa, bb, xx, valid_mask)), aa);
+    yy = _mm512_mask_sub_pd(yy, swap_mask, D512_ONE, yy);
+
+    _mm512_mask_blend_pd(valid_mask, D512_NAN, yy)
+}
+
+/// (P(a, x), Q(a, x)), from Temme's expansion for large a with x near a, from the series for P where x < a + 1
+/// and from the continued fraction for Q elsewhere.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn gamma_pq(a: __m512d, x: __m512d) -> (__m512d, __m512d)
+{
+    let valid_mask = _mm512_cmp_pd_mask(a, D512_ZERO, _CMP_GT_OQ) & _mm512_cmp_pd_mask(x, D512_ZERO, _CMP_GE_OQ);
+
+    // Near x = a the series and the continued fraction take O(sqrt(a)) steps, and x^a e^-x/gamma(a) loses
+    // about log2(a) bits to cancellation, so large a goes to Temme's expansion there instead.
+    let sigma = _mm512_div_pd(_mm512_sub_pd(x, a), a);
+    let temme_mask = valid_mask & _mm512_cmp_pd_mask(a, D512_TEMME_MIN_A, _CMP_GE_OQ)
+        & _mm512_cmplt_pd_mask(_mm512_abs_pd(sigma), D512_TEMME_MAX_SIGMA);
+    let series_mask = valid_mask & !temme_mask & _mm512_cmplt_pd_mask(x, _mm512_add_pd(a, D512_ONE));
+
+    // x^a e^-x/gamma(a)
+    let mut lx = D512_ZERO;
+    let mut la = D512_ZERO;
+    ln_intr(&x, &mut lx);
+    lgamma_intr(&a, &mut la);
+    let mut f = D512_ZERO;
+    exp_intr(&_mm512_sub_pd(_mm512_fmsub_pd(a, lx, x), la), &mut f);
+
+    // P(a, x) = f/a (1 + x/(a + 1) + x^2/((a + 1)(a + 2)) + ...). The loops run until every lane has converged
+    // or MAX_ITERATIONS is reached, and the lanes that still haven't converged by then come out as NaN.
+    let mut active = series_mask;
+    let mut ap = a;
+    let mut term = D512_ONE;
+    let mut sum = D512_ONE;
+    let mut i = 0;
+    while active != 0 && i < MAX_ITERATIONS
+    {
+        ap = _mm512_add_pd(ap, D512_ONE);
+        term = _mm512_mul_pd(term, _mm512_div_pd(x, ap));
+        sum = _mm512_mask_add_pd(sum, active, sum, term);
+        active &= _mm512_cmp_pd_mask(term, _mm512_mul_pd(sum, D512_EPSILON), _CMP_GT_OQ);
+        i += 1;
+    }
+    let p_series = _mm512_div_pd(_mm512_mul_pd(f, sum), a);
+    let mut unconverged = active;
+
+    // Q(a, x) = f/(x + 1 - a - 1(1 - a)/(x + 3 - a - 2(2 - a)/(x + 5 - a - ...))), by the modified Lentz method.
+    active = valid_mask & !series_mask & !temme_mask;
+    let mut b = _mm512_sub_pd(_mm512_add_pd(x, D512_ONE), a);
+    let mut c = D512_ONE_OVER_FPMIN;
+    let mut d = _mm512_div_pd(D512_ONE, b);
+    let mut h = d;
+    i = 0;
+    while active != 0 && i < MAX_ITERATIONS
+    {
+        i += 1;
+        let n = _mm512_set1_pd(i as f64);
+        let an = _mm512_mul_pd(n, _mm512_sub_pd(a, n));
+        b = _mm512_add_pd(b, D512_TWO);
+        d = fpmin_guard(_mm512_fmadd_pd(an, d, b));
+        c = fpmin_guard(_mm512_add_pd(b, _mm512_div_pd(an, c)));
+        d = _mm512_div_pd(D512_ONE, d);
+        let del = _mm512_mul_pd(d, c);
+        h = _mm512_mask_mul_pd(h, active, h, del);
+        active &= _mm512_cmp_pd_mask(_mm512_abs_pd(_mm512_sub_pd(del, D512_ONE)), D512_EPSILON, _CMP_GE_OQ);
+    }
+    let q_cf = _mm512_mul_pd(f, h);
+    unconverged |= active;
+
+    let mut p = _mm512_mask_blend_pd(series_mask, _mm512_sub_pd(D512_ONE, q_cf), p_series);
+    let mut q = _mm512_mask_blend_pd(series_mask, q_cf, _mm512_sub_pd(D512_ONE, p_series));
+    if temme_mask != 0
+    {
+        let (pt, qt) = gamma_pq_temme(a, sigma);
+        p = _mm512_mask_blend_pd(temme_mask, p, pt);
+        q = _mm512_mask_blend_pd(temme_mask, q, qt);
+    }
+    p = _mm512_mask_blend_pd(unconverged, p, D512_NAN);
+    q = _mm512_mask_blend_pd(unconverged, q, D512_NAN);
+
+    let inf_mask = valid_mask & _mm512_cmpeq_pd_mask(x, D512_POSITIVE_INFINITY);
+    p = _mm512_mask_blend_pd(inf_mask, p, D512_ONE);
+    q = _mm512_mask_blend_pd(inf_mask, q, D512_ZERO);
+    (_mm512_mask_blend_pd(valid_mask, D512_NAN, p), _mm512_mask_blend_pd(valid_mask, D512_NAN, q))
+}
+
+/// (P(a, x), Q(a, x)) from Temme's uniform asymptotic expansion (DLMF 8.12), with sigma = (x - a)/a:
+/// Q = erfc(z)/2 + R and P = erfc(-z)/2 - R, where z = sign(sigma) sqrt(-a ln1pmx(sigma)), eta = z sqrt(2/a) and
+/// R = e^(-z^2)/sqrt(2 pi a) sum C_k(eta) a^-k. The C_k tables are long enough for a >= 200 and |sigma| < 0.3.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn gamma_pq_temme(a: __m512d, sigma: __m512d) -> (__m512d, __m512d)
+{
+    let l = ln1pmx(sigma);
+    let y = _mm512_mul_pd(a, l);
+    let neg_mask = _mm512_cmplt_pd_mask(sigma, D512_ZERO);
+    let mut z = _mm512_sqrt_pd(_mm512_sub_pd(D512_ZERO, y));
+    z = _mm512_mask_sub_pd(z, neg_mask, D512_ZERO, z);
+    let mut eta = _mm512_sqrt_pd(_mm512_mul_pd(l, D512_NEGTWO));
+    eta = _mm512_mask_sub_pd(eta, neg_mask, D512_ZERO, eta);
+
+    let ainv = _mm512_div_pd(D512_ONE, a);
+    let mut sum = D512_ZERO;
+    for c in D512_TEMME.iter().rev()
+    {
+        sum = _mm512_fmadd_pd(sum, ainv, horner(eta, c));
+    }
+    let mut e = D512_ZERO;
+    exp_intr(&y, &mut e);
+    let r = _mm512_div_pd(_mm512_mul_pd(e, sum), _mm512_sqrt_pd(_mm512_mul_pd(a, D512_TWO_PI)));
+
+    let mut ep = D512_ZERO;
+    let mut eq = D512_ZERO;
+    erfc_intr(&_mm512_sub_pd(D512_ZERO, z), &mut ep);
+    erfc_intr(&z, &mut eq);
+    (_mm512_fmsub_pd(ep, D512_HALF, r), _mm512_fmadd_pd(eq, D512_HALF, r))
+}
+
+/// ln(1 + x) - x for |x| < 1/2, without the cancellation. With t = x/(2 + x), ln(1 + x) = 2 atanh(t) and
+/// 2t - x = -xt, so ln(1 + x) - x = 2t^3 (1/3 + t^2/5 + t^4/7 + ...) - xt.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn ln1pmx(x: __m512d) -> __m512d
+{
+    let t = _mm512_div_pd(x, _mm512_add_pd(D512_TWO, x));
+    let t2 = _mm512_mul_pd(t, t);
+    let t3 = _mm512_mul_pd(_mm512_add_pd(t, t), t2);
+    _mm512_fmsub_pd(t3, horner(t2, &D512_ATANH), _mm512_mul_pd(x, t))
+}
+
+/// The continued fraction 1/(1 + d1/(1 + d2/(1 + ...))) in I_x(a, b) = x^a (1 - x)^b/(a B(a, b)) * 1/(1 + ...), by
+/// the modified Lentz method, for the lanes in mask.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn beta_cf(a: __m512d, b: __m512d, x: __m512d, mask: __mmask8) -> __m512d
+{
+    let qab = _mm512_add_pd(a, b);
+    let qap = _mm512_add_pd(a, D512_ONE);
+    let qam = _mm512_sub_pd(a, D512_ONE);
+    let mut c = D512_ONE;
+    let mut d = fpmin_guard(_mm512_fnmadd_pd(_mm512_div_pd(qab, qap), x, D512_ONE));
+    d = _mm512_div_pd(D512_ONE, d);
+    let mut h = d;
+
+    let mut active = mask;
+    let mut i = 0;
+    while active != 0 && i < MAX_ITERATIONS
+    {
+        i += 1;
+        let m = _mm512_set1_pd(i as f64);
+        let m2 = _mm512_add_pd(m, m);
+
+        // d_2m = m(b - m)x/((a + 2m - 1)(a + 2m))
+        let mut aa = _mm512_mul_pd(_mm512_mul_pd(m, _mm512_sub_pd(b, m)), x);
+        aa = _mm512_div_pd(aa, _mm512_mul_pd(_mm512_add_pd(qam, m2), _mm512_add_pd(a, m2)));
+        d = fpmin_guard(_mm512_fmadd_pd(aa, d, D512_ONE));
+        c = fpmin_guard(_mm512_add_pd(D512_ONE, _mm512_div_pd(aa, c)));
+        d = _mm512_div_pd(D512_ONE, d);
+        h = _mm512_mask_mul_pd(h, active, h, _mm512_mul_pd(d, c));
+
+        // d_2m+1 = -(a + m)(a + b + m)x/((a + 2m)(a + 2m + 1))
+        aa = _mm512_mul_pd(_mm512_mul_pd(_mm512_add_pd(a, m), _mm512_add_pd(qab, m)), x);
+        aa = _mm512_div_pd(aa, _mm512_mul_pd(_mm512_add_pd(a, m2), _mm512_add_pd(qap, m2)));
+        d = fpmin_guard(_mm512_fnmadd_pd(aa, d, D512_ONE));
+        c = fpmin_guard(_mm512_sub_pd(D512_ONE, _mm512_div_pd(aa, c)));
+        d = _mm512_div_pd(D512_ONE, d);
+        let del = _mm512_mul_pd(d, c);
+        h = _mm512_mask_mul_pd(h, active, h, del);
+        active &= _mm512_cmp_pd_mask(_mm512_abs_pd(_mm512_sub_pd(del, D512_ONE)), D512_EPSILON, _CMP_GE_OQ);
+    }
+
+    h
+}
+
+/// Keeps the Lentz denominators away from zero.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn fpmin_guard(x: __m512d) -> __m512d
+{
+    _mm512_mask_blend_pd(_mm512_cmplt_pd_mask(_mm512_abs_pd(x), D512_FPMIN), x, D512_FPMIN)
+}
+
+/// ln|gamma(x)| for x >= -1/2.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn lgamma_positive(x: __m512d) -> __m512d
+{
+    let (p, num, den, small_mask) = shift_to_two(x);
+
+    // ln(gamma(2 + p)) = p*R(p) on [-1/2, 1/2]. ln(den) is taken from den itself, and the logs are good to
+    // more than double precision, which keeps the relative precision around the zero at x = 1.
+    let (l, _) = ln_dd(_mm512_mask_blend_pd(small_mask, x, num));
+    let (ld, _) = ln_dd(_mm512_abs_pd(den));
+
+    let yy = horner(p, &D512_LG);
+    let small = _mm512_fmadd_pd(p, yy, _mm512_sub_pd(l, ld));
+
+    // Stirling's series, (x - 1/2)ln(x) - x + ln(2pi)/2 + sum B_2k/(2k(2k - 1)x^(2k - 1)), for x >= 10
+    let w = _mm512_div_pd(D512_ONE, x);
+    let w2 = _mm512_mul_pd(w, w);
+    let s = horner(w2, &D512_STIRLING);
+    let large = _mm512_fmadd_pd(_mm512_sub_pd(x, D512_HALF), _mm512_sub_pd(l, D512_ONE), _mm512_fmadd_pd(s, w, D512_HALF_LN_2PI_LESS_HALF));
+
+    _mm512_mask_blend_pd(small_mask, large, small)
+}
+
+/// gamma(x)*2^-scale for x >= -1/2.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn tgamma_positive(x: __m512d, scale: __m512d) -> __m512d
+{
+    let (p, num, den, small_mask) = shift_to_two(x);
+
+    let yy = horner(p, &D512_LG);
+    let mut e = D512_ZERO;
+    exp_intr(&_mm512_mul_pd(p, yy), &mut e);
+    let small = _mm512_scalef_pd(_mm512_mul_pd(e, _mm512_div_pd(num, den)), _mm512_sub_pd(D512_ZERO, scale));
+
+    // Stirling's series again, but with (x - 1/2)ln(x) - x + ln(2pi)/2 - scale*ln(2) summed as a double-double.
+    // gamma(x) is exp() of it, so its rounding error would otherwise come out as relative error of up to 1e-13.
+    let w = _mm512_div_pd(D512_ONE, x);
+    let w2 = _mm512_mul_pd(w, w);
+    let s = horner(w2, &D512_STIRLING);
+    let m = _mm512_sub_pd(x, D512_HALF);
+    let (lh, ll) = ln_dd(x);
+    let mut hi = _mm512_mul_pd(m, lh);
+    let mut lo = _mm512_fmadd_pd(m, ll, _mm512_fmsub_pd(m, lh, hi));
+    let (hi0, e0) = two_sum(hi, _mm512_sub_pd(D512_ZERO, x));
+    let (hi1, e1) = two_sum(hi0, D512_HALF_LN_2PI);
+    let (hi2, e2) = two_sum(hi1, _mm512_mul_pd(scale, D512_NEG_LN2_HI));
+    hi = hi2;
+    lo = _mm512_add_pd(_mm512_add_pd(lo, _mm512_fmadd_pd(s, w, D512_HALF_LN_2PI_LO)), _mm512_add_pd(_mm512_add_pd(e0, e1), e2));
+    lo = _mm512_fmadd_pd(scale, D512_NEG_LN2_LO, lo);
+    let large = exp_dd(hi, lo);
+
+    _mm512_mask_blend_pd(small_mask, large, small)
+}
+
+/// digamma(x) for x >= -1/2.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn digamma_positive(x: __m512d) -> __m512d
+{
+    // digamma(x) = digamma(x + n) - sum 1/(x + k), k < n, with x + n >= 10
+    let mut u = x;
+    let mut acc = D512_ZERO;
+    for _ in 0..11
+    {
+        let m = _mm512_cmplt_pd_mask(u, D512_TEN);
+        acc = _mm512_mask_add_pd(acc, m, acc, _mm512_div_pd(D512_ONE, u));
+        u = _mm512_mask_add_pd(u, m, u, D512_ONE);
+    }
+
+    // ln(x) - 1/(2x) - sum B_2k/(2k x^2k) for x >= 10
+    let mut l = D512_ZERO;
+    ln_intr(&u, &mut l);
+    let w = _mm512_div_pd(D512_ONE, u);
+    let w2 = _mm512_mul_pd(w, w);
+    let mut s = _mm512_fmadd_pd(D512_PS8, w2, D512_PS7);
+    s = _mm512_fmadd_pd(s, w2, D512_PS6);
+    s = _mm512_fmadd_pd(s, w2, D512_PS5);
+    s = _mm512_fmadd_pd(s, w2, D512_PS4);
+    s = _mm512_fmadd_pd(s, w2, D512_PS3);
+    s = _mm512_fmadd_pd(s, w2, D512_PS2);
+    s = _mm512_fmadd_pd(s, w2, D512_PS1);
+    let mut yy = _mm512_fnmadd_pd(s, w2, _mm512_fnmadd_pd(D512_HALF, w, l));
+    yy = _mm512_sub_pd(yy, acc);
+
+    // (x - x0)P(x - x0) around the positive root x0, where the above loses its relative precision
+    let root_mask = _mm512_cmp_pd_mask(x, D512_ROOT_LOW, _CMP_GE_OQ) & _mm512_cmple_pd_mask(x, D512_ROOT_HIGH);
+    let h = _mm512_sub_pd(_mm512_sub_pd(x, D512_X0_HI), D512_X0_LO);
+    let root = {
+        let mut yy = _mm512_fmadd_pd(D512_DG15, h, D512_DG14);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG13);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG12);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG11);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG10);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG9);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG8);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG7);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG6);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG5);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG4);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG3);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG2);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG1);
+        yy = _mm512_fmadd_pd(yy, h, D512_DG0);
+        _mm512_mul_pd(h, yy)
+    };
+
+    _mm512_mask_blend_pd(root_mask, yy, root)
+}
+
+/// Splits x in [-1/2, 10) into gamma(x) = gamma(2 + p)*num/den with p in [-1/2, 1/2], num = (x - 1)...(2 + p) and
+/// den = x(x + 1)...(1 + p), all exact apart from the rounding of the products. The returned mask has the lanes
+/// with x < 10.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn shift_to_two(x: __m512d) -> (__m512d, __m512d, __m512d, __mmask8)
+{
+    let small_mask = _mm512_cmplt_pd_mask(x, D512_TEN);
+    let mut u = _mm512_mask_blend_pd(small_mask, D512_TWO, x);
+    let mut num = D512_ONE;
+    for _ in 0..8
+    {
+        let m = _mm512_cmp_pd_mask(u, D512_TWO_AND_HALF, _CMP_GE_OQ);
+        u = _mm512_mask_sub_pd(u, m, u, D512_ONE);
+        num = _mm512_mask_mul_pd(num, m, num, u);
+    }
+
+    // u - 1 and u are exact below 3/2, where u + 1 and u + 2 might not be.
+    let one_mask = _mm512_cmplt_pd_mask(u, D512_ONE_AND_HALF);
+    let zero_mask = _mm512_cmplt_pd_mask(u, D512_HALF);
+    let mut p = _mm512_sub_pd(u, D512_TWO);
+    p = _mm512_mask_sub_pd(p, one_mask, u, D512_ONE);
+    p = _mm512_mask_mov_pd(p, zero_mask, u);
+    let mut den = _mm512_mask_mov_pd(D512_ONE, one_mask, u);
+    den = _mm512_mask_mul_pd(den, zero_mask, u, _mm512_add_pd(u, D512_ONE));
+
+    (p, num, den, small_mask)
+}
+
+
+const MAX_ITERATIONS: usize = 10000;
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_HALF: __m512d = m64x8_constant!(0.5);
+const D512_NEGHALF: __m512d = m64x8_constant!(-0.5);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_ONE_AND_HALF: __m512d = m64x8_constant!(1.5);
+const D512_TWO: __m512d = m64x8_constant!(2.0);
+const D512_TWO_AND_HALF: __m512d = m64x8_constant!(2.5);
+const D512_TEN: __m512d = m64x8_constant!(10.0);
+const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_NEGPI: __m512d = m64x8_constant!(-std::f64::consts::PI);
+const D512_PI: __m512d = m64x8_constant!(std::f64::consts::PI);
+const D512_EPSILON: __m512d = m64x8_constant!(f64::EPSILON);
+const D512_FPMIN: __m512d = m64x8_constant!(1e-300);
+const D512_ONE_OVER_FPMIN: __m512d = m64x8_constant!(1e300);
+const D512_SCALE: __m512d = m64x8_constant!(256.0);
+const D512_NEG_LN2_HI: __m512d = m64x8_constant!(-6.93147180369123816490e-01);
+const D512_NEG_LN2_LO: __m512d = m64x8_constant!(-1.90821492927058770002e-10);
+const D512_HALF_LN_2PI: __m512d = m64x8_constant!(0.9189385332046728);
+const D512_HALF_LN_2PI_LO: __m512d = m64x8_constant!(-3.8782941580672414e-17);
+const D512_HALF_LN_2PI_LESS_HALF: __m512d = m64x8_constant!(0.4189385332046727);
+const D512_NEGTWO: __m512d = m64x8_constant!(-2.0);
+const D512_TWO_PI: __m512d = m64x8_constant!(2.0 * std::f64::consts::PI);
+const D512_TEMME_MIN_A: __m512d = m64x8_constant!(200.0);
+const D512_TEMME_MAX_SIGMA: __m512d = m64x8_constant!(0.3);
+const D512_ATANH: [__m512d; 11] = [
+    m64x8_constant!(1.0 / 3.0), m64x8_constant!(1.0 / 5.0),
+    m64x8_constant!(1.0 / 7.0), m64x8_constant!(1.0 / 9.0),
+    m64x8_constant!(1.0 / 11.0), m64x8_constant!(1.0 / 13.0),
+    m64x8_constant!(1.0 / 15.0), m64x8_constant!(1.0 / 17.0),
+    m64x8_constant!(1.0 / 19.0), m64x8_constant!(1.0 / 21.0),
+    m64x8_constant!(1.0 / 23.0)
+];
+const D512_TEMME0: [__m512d; 17] = [
+    m64x8_constant!(-0.3333333333333333), m64x8_constant!(0.08333333333333333),
+    m64x8_constant!(-0.014814814814814815), m64x8_constant!(0.0011574074074074073),
+    m64x8_constant!(0.0003527336860670194), m64x8_constant!(-0.0001787551440329218),
+    m64x8_constant!(3.919263178522438e-05), m64x8_constant!(-2.185448510679992e-06),
+    m64x8_constant!(-1.85406221071516e-06), m64x8_constant!(8.296711340953087e-07),
+    m64x8_constant!(-1.7665952736826078e-07), m64x8_constant!(6.707853543401498e-09),
+    m64x8_constant!(1.0261809784240309e-08), m64x8_constant!(-4.382036018453353e-09),
+    m64x8_constant!(9.14769958223679e-10), m64x8_constant!(-2.5514193994946248e-11),
+    m64x8_constant!(-5.830772132550426e-11)
+];
+const D512_TEMME1: [__m512d; 15] = [
+    m64x8_constant!(-0.001851851851851852), m64x8_constant!(-0.003472222222222222),
+    m64x8_constant!(0.0026455026455026454), m64x8_constant!(-0.0009902263374485596),
+    m64x8_constant!(0.00020576131687242798), m64x8_constant!(-4.018775720164609e-07),
+    m64x8_constant!(-1.8098550334489977e-05), m64x8_constant!(7.64916091608111e-06),
+    m64x8_constant!(-1.6120900894563446e-06), m64x8_constant!(4.647127802807434e-09),
+    m64x8_constant!(1.378633446915721e-07), m64x8_constant!(-5.752545603517705e-08),
+    m64x8_constant!(1.1951628599778148e-08), m64x8_constant!(-1.7543241719747647e-11),
+    m64x8_constant!(-1.0091543710600413e-09)
+];
+const D512_TEMME2: [__m512d; 12] = [
+    m64x8_constant!(0.004133597883597883), m64x8_constant!(-0.0026813271604938273),
+    m64x8_constant!(0.0007716049382716049), m64x8_constant!(2.0093878600823047e-06),
+    m64x8_constant!(-0.0001073665322636516), m64x8_constant!(5.2923448829120125e-05),
+    m64x8_constant!(-1.2760635188618728e-05), m64x8_constant!(3.423578734096138e-08),
+    m64x8_constant!(1.3721957309062934e-06), m64x8_constant!(-6.298992138380055e-07),
+    m64x8_constant!(1.4280614206064242e-07), m64x8_constant!(-2.0477098421990866e-10)
+];
+const D512_TEMME3: [__m512d; 9] = [
+    m64x8_constant!(0.0006494341563786008), m64x8_constant!(0.00022947209362139917),
+    m64x8_constant!(-0.0004691894943952557), m64x8_constant!(0.00026772063206283885),
+    m64x8_constant!(-7.561801671883977e-05), m64x8_constant!(-2.396505113867297e-07),
+    m64x8_constant!(1.1082654115347302e-05), m64x8_constant!(-5.6749528269915965e-06),
+    m64x8_constant!(1.4230900732435883e-06)
+];
+const D512_TEMME4: [__m512d; 7] = [
+    m64x8_constant!(-0.0008618882909167117), m64x8_constant!(0.0007840392217200666),
+    m64x8_constant!(-0.0002990724803031902), m64x8_constant!(-1.4638452578843418e-06),
+    m64x8_constant!(6.641498215465122e-05), m64x8_constant!(-3.968365047179435e-05),
+    m64x8_constant!(1.1375726970678419e-05)
+];
+const D512_TEMME5: [__m512d; 5] = [
+    m64x8_constant!(-0.00033679855336635813), m64x8_constant!(-6.972813758365857e-05),
+    m64x8_constant!(0.0002772753244959392), m64x8_constant!(-0.00019932570516188847),
+    m64x8_constant!(6.797780477937208e-05)
+];
+const D512_TEMME6: [__m512d; 2] = [
+    m64x8_constant!(0.0005313079364639922), m64x8_constant!(-0.0005921664373536939)
+];
+const D512_TEMME: [&[__m512d]; 7] = [
+    &D512_TEMME0, &D512_TEMME1, &D512_TEMME2, &D512_TEMME3, &D512_TEMME4, &D512_TEMME5, &D512_TEMME6
+];
+const D512_ROOT_LOW: __m512d = m64x8_constant!(1.25);
+const D512_ROOT_HIGH: __m512d = m64x8_constant!(1.75);
+const D512_X0_HI: __m512d = m64x8_constant!(1.4616321449683622);
+const D512_X0_LO: __m512d = m64x8_constant!(9.549995429965697e-17);
+const D512_STIRLING: [__m512d; 8] = [
+    m64x8_constant!(1.0 / 12.0), m64x8_constant!(-1.0 / 360.0),
+    m64x8_constant!(1.0 / 1260.0), m64x8_constant!(-1.0 / 1680.0),
+    m64x8_constant!(1.0 / 1188.0), m64x8_constant!(-691.0 / 360360.0),
+    m64x8_constant!(1.0 / 156.0), m64x8_constant!(-3617.0 / 122400.0)
+];
+const D512_PS1: __m512d = m64x8_constant!(1.0 / 12.0);
+const D512_PS2: __m512d = m64x8_constant!(-1.0 / 120.0);
+const D512_PS3: __m512d = m64x8_constant!(1.0 / 252.0);
+const D512_PS4: __m512d = m64x8_constant!(-1.0 / 240.0);
+const D512_PS5: __m512d = m64x8_constant!(1.0 / 132.0);
+const D512_PS6: __m512d = m64x8_constant!(-691.0 / 32760.0);
+const D512_PS7: __m512d = m64x8_constant!(1.0 / 12.0);
+const D512_PS8: __m512d = m64x8_constant!(-3617.0 / 8160.0);
+const D512_LG: [__m512d; 18] = [
+    m64x8_constant!(0.42278433509846713), m64x8_constant!(0.3224670334241132),
+    m64x8_constant!(-0.06735230105319563), m64x8_constant!(0.02058080842778338),
+    m64x8_constant!(-0.007385551028935545), m64x8_constant!(0.002890510330865901),
+    m64x8_constant!(-0.0011927539009983087), m64x8_constant!(0.0005096695196526673),
+    m64x8_constant!(-0.00022315497746292723), m64x8_constant!(9.945761692157133e-05),
+    m64x8_constant!(-4.4923727564247656e-05), m64x8_constant!(2.050601970174762e-05),
+    m64x8_constant!(-9.45628851510172e-06), m64x8_constant!(4.3828545383846665e-06),
+    m64x8_constant!(-1.9743106888421067e-06), m64x8_constant!(9.242852225162277e-07),
+    m64x8_constant!(-5.811277768921246e-07), m64x8_constant!(2.7475390051909663e-07)
+];
+const D512_DG0: __m512d = m64x8_constant!(0.9676722454476212);
+const D512_DG1: __m512d = m64x8_constant!(-0.44276316898359236);
+const D512_DG2: __m512d = m64x8_constant!(0.258499760955642);
+const D512_DG3: __m512d = m64x8_constant!(-0.1639427054422346);
+const D512_DG4: __m512d = m64x8_constant!(0.10782405069417873);
+const D512_DG5: __m512d = m64x8_constant!(-0.0721995612946594);
+const D512_DG6: __m512d = m64x8_constant!(0.04880428782670941);
+const D512_DG7: __m512d = m64x8_constant!(-0.0331611226215099);
+const D512_DG8: __m512d = m64x8_constant!(0.022597665299454416);
+const D512_DG9: __m512d = m64x8_constant!(-0.015424968880877337);
+const D512_DG10: __m512d = m64x8_constant!(0.010538446170040733);
+const D512_DG11: __m512d = m64x8_constant!(-0.0071987852260622185);
+const D512_DG12: __m512d = m64x8_constant!(0.004926161649260595);
+const D512_DG13: __m512d = m64x8_constant!(-0.0034512359279570623);
+const D512_DG14: __m512d = m64x8_constant!(0.0024193263397805785);
+const D512_DG15: __m512d = m64x8_constant!(-0.0011655614313017461);
@@ -0,0 +1,88 @@
+use std::arch::x86_64::*;
+use super::*;
+
+// Lane-wise building blocks that several modules share.
+
+/// Mask of the lanes that are neither infinite nor NaN.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn finite(x: __m512d) -> __mmask8
+{
+    _mm512_cmp_pd_mask(_mm512_abs_pd(x), D512_POSITIVE_INFINITY, _CMP_LT_OQ)
+}
+
+/// Mask of the first min(n, 8) lanes, for the tail of a loop over n remaining elements.
+#[inline]
+pub(crate) fn tail_mask(n: usize) -> __mmask8
+{
+    if n >= 8 { 0xff } else { ((1u32 << n) - 1) as __mmask8 }
+}
+
+/// ln(1 + x), keeping its relative precision for small x.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn log1p(x: __m512d) -> __m512d
+{
+    // ln(u)x/(u - 1) with u = 1 + x makes up for the rounding of u (Goldberg's trick).
+    let u = _mm512_add_pd(D512_ONE, x);
+    let (l, _) = ln_dd(u);
+    let mut yy = _mm512_mul_pd(l, _mm512_div_pd(x, _mm512_sub_pd(u, D512_ONE)));
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(u, D512_ONE), yy, x);
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(u, D512_ZERO), yy, D512_NEGATIVE_INFINITY);
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(x, D512_POSITIVE_INFINITY), yy, x);
+    _mm512_mask_blend_pd(_mm512_cmplt_pd_mask(u, D512_ZERO), yy, D512_NAN)
+}
+
+/// e^x - 1, keeping its relative precision for small x.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn expm1(x: __m512d) -> __m512d
+{
+    // (u - 1)x/ln(u) with u = e^x makes up for the rounding of u (Kahan's trick).
+    let mut u = D512_ZERO;
+    exp_intr(&x, &mut u);
+    let um = _mm512_sub_pd(u, D512_ONE);
+    let (l, _) = ln_dd(u);
+    let mut yy = _mm512_mul_pd(um, _mm512_div_pd(x, l));
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(u, D512_ONE), yy, x);
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(um, D512_NEGONE), yy, D512_NEGONE);
+    _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(x, D512_POSITIVE_INFINITY), yy, x)
+}
+
+/// Sum of c_k T_k(t), by Clenshaw's recurrence. c holds the Chebyshev coefficients from c_0 up.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn clenshaw(t: __m512d, c: &[__m512d]) -> __m512d
+{
+    let t2 = _mm512_add_pd(t, t);
+    let mut b1 = D512_ZERO;
+    let mut b2 = D512_ZERO;
+    for ck in c[1..].iter().rev()
+    {
+        let b0 = _mm512_fmadd_pd(t2, b1, _mm512_sub_pd(*ck, b2));
+        b2 = b1;
+        b1 = b0;
+    }
+    _mm512_fmadd_pd(t, b1, _mm512_sub_pd(c[0], b2))
+}
+
+/// Sum of c_k z^k, by Horner's rule. c holds the coefficients from c_0 up.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn horner(z: __m512d, c: &[__m512d]) -> __m512d
+{
+    let mut y = D512_ZERO;
+    for ck in c.iter().rev()
+    {
+        y = _mm512_fmadd_pd(y, z, *ck);
+    }
+    y
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_NEGONE: __m512d = m64x8_constant!(-1.0);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_NEGATIVE_INFINITY: __m512d = m64x8_constant!(f64::NEG_INFINITY);
+const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
@@ -0,0 +1,106 @@
+use std::arch::x86_64::*;
+use super::*;
+
+/// b = a^T, where a is a rows x cols row-major matrix and b the cols x rows result.
+///
+/// The matrix is walked in TRANSPOSE_BLOCK square blocks so that the rows being read and the rows being
+/// written both stay in cache, and each block is moved as 8x8 tiles transposed in registers. Tiles on the
+/// right and bottom edges are loaded and stored with masks, so any shape goes through the same kernel.
+pub fn transpose(a: &[f64], rows: usize, cols: usize, b: &mut [f64])
+{
+    assert_eq!(a.len(), rows * cols);
+    assert_eq!(b.len(), rows * cols);
+    unsafe{
+        transposeu(a, rows, cols, b);
+    }
+}
+
+/// Converts an array of structs of `fields` f64s each into structure-of-arrays form: field f of record r
+/// moves from aos[r * fields + f] to soa[f * count + r], so each field ends up in its own contiguous slice
+/// soa[f * count..(f + 1) * count] ready for the slice functions. This is the layout batch_det and friends
+/// expect, too.
+pub fn aos_to_soa(aos: &[f64], fields: usize, soa: &mut [f64])
+{
+    assert!(fields > 0);
+    assert_eq!(aos.len() % fields, 0);
+    transpose(aos, aos.len() / fields, fields, soa);
+}
+
+/// The inverse of aos_to_soa: field f of record r moves from soa[f * count + r] to aos[r * fields + f].
+pub fn soa_to_aos(soa: &[f64], fields: usize, aos: &mut [f64])
+{
+    assert!(fields > 0);
+    assert_eq!(soa.len() % fields, 0);
+    transpose(soa, fields, soa.len() / fields, aos);
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn transposeu(a: &[f64], rows: usize, cols: usize, b: &mut [f64])
+{
+    let ap = a.as_ptr();
+    let bp = b.as_mut_ptr();
+
+    for ib in (0..rows).step_by(TRANSPOSE_BLOCK) {
+        for jb in (0..cols).step_by(TRANSPOSE_BLOCK) {
+            for i in (ib..rows.min(ib + TRANSPOSE_BLOCK)).step_by(8) {
+                for j in (jb..cols.min(jb + TRANSPOSE_BLOCK)).step_by(8) {
+                    transpose_tile(ap.add(i * cols + j), cols, bp.add(j * rows + i), rows, (rows - i).min(8), (cols - j).min(8));
+                }
+            }
+        }
+    }
+}
+
+// Transposes the r x c (at most 8x8) tile at a, with row stride lda, into the c x r tile at b, with row
+// stride ldb. Only rows that exist are touched, and each is loaded or stored under a mask of its width.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn transpose_tile(a: *const f64, lda: usize, b: *mut f64, ldb: usize, r: usize, c: usize)
+{
+    let load_mask = tail_mask(c);
+    let store_mask = tail_mask(r);
+
+    let mut x = [_mm512_setzero_pd(); 8];
+    for (i, xi) in x.iter_mut().enumerate().take(r) {
+        *xi = _mm512_maskz_loadu_pd(load_mask, a.add(i * lda));
+    }
+
+    // Interleave pairs of rows, then pairs of pairs, then the two halves; after the three rounds x[j] holds
+    // column j.
+    let t0 = _mm512_unpacklo_pd(x[0], x[1]);
+    let t1 = _mm512_unpackhi_pd(x[0], x[1]);
+    let t2 = _mm512_unpacklo_pd(x[2], x[3]);
+    let t3 = _mm512_unpackhi_pd(x[2], x[3]);
+    let t4 = _mm512_unpacklo_pd(x[4], x[5]);
+    let t5 = _mm512_unpackhi_pd(x[4], x[5]);
+    let t6 = _mm512_unpacklo_pd(x[6], x[7]);
+    let t7 = _mm512_unpackhi_pd(x[6], x[7]);
+
+    let even = _mm512_set_epi64(13, 12, 5, 4, 9, 8, 1, 0);
+    let odd = _mm512_set_epi64(15, 14, 7, 6, 11, 10, 3, 2);
+    let u0 = _mm512_permutex2var_pd(t0, even, t2);
+    let u1 = _mm512_permutex2var_pd(t1, even, t3);
+    let u2 = _mm512_permutex2var_pd(t0, odd, t2);
+    let u3 = _mm512_permutex2var_pd(t1, odd, t3);
+    let u4 = _mm512_permutex2var_pd(t4, even, t6);
+    let u5 = _mm512_permutex2var_pd(t5, even, t7);
+    let u6 = _mm512_permutex2var_pd(t4, odd, t6);
+    let u7 = _mm512_permutex2var_pd(t5, odd, t7);
+
+    x[0] = _mm512_shuffle_f64x2(u0, u4, 0x44);
+    x[1] = _mm512_shuffle_f64x2(u1, u5, 0x44);
+    x[2] = _mm512_shuffle_f64x2(u2, u6, 0x44);
+    x[3] = _mm512_shuffle_f64x2(u3, u7, 0x44);
+    x[4] = _mm512_shuffle_f64x2(u0, u4, 0xee);
+    x[5] = _mm512_shuffle_f64x2(u1, u5, 0xee);
+    x[6] = _mm512_shuffle_f64x2(u2, u6, 0xee);
+    x[7] = _mm512_shuffle_f64x2(u3, u7, 0xee);
+
+    for (j, xj) in x.iter().enumerate().take(c) {
+        _mm512_mask_storeu_pd(b.add(j * ldb), store_mask, *xj);
+    }
+}
+
+
+const TRANSPOSE_BLOCK: usize = 64;
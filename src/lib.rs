@@ -4,12 +4,26 @@
 
 mod constants;
 mod unroller;
+mod kernels;
 mod exp;
 mod log;
 mod normdist;
 mod trig;
 mod linalg;
 mod root;
+mod pow;
+mod gamma;
+mod dist;
+mod bessel;
+mod round;
+mod arith;
+mod activation;
+mod softmax;
+mod summation;
+mod batch;
+mod layout;
+
+use kernels::*;
 
 pub use exp::*;
 pub use log::*;
@@ -17,4 +31,15 @@ pub use normdist::*;
 pub use trig::*;
 pub use unroller::*;
 pub use linalg::*;
-pub use root::*;
\ No newline at end of file
+pub use root::*;
+pub use pow::*;
+pub use gamma::*;
+pub use dist::*;
+pub use bessel::*;
+pub use round::*;
+pub use arith::*;
+pub use activation::*;
+pub use softmax::*;
+pub use summation::*;
+pub use batch::*;
+pub use layout::*;
\ No newline at end of file
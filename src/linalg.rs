@@ -86,6 +86,1444 @@ pub fn dot(x: &[f64], y: &[f64]) -> f64
     }
 }
 
+/// dot split across 32 rayon tasks.
+#[inline]
+pub fn dot_par(x: &[f64], y: &[f64]) -> f64
+{
+    dot_par_chunked(x, y, 32)
+}
+
+/// dot split into `chunks` equal pieces that rayon runs in parallel. The partial sums are added in order,
+/// so the result depends only on x, y and `chunks`, not on scheduling.
+pub fn dot_par_chunked(x: &[f64], y: &[f64], chunks: usize) -> f64
+{
+    use rayon::prelude::*;
+    assert_eq!(x.len(), y.len());
+    assert!(chunks > 0);
+
+    let chunk = x.len().div_ceil(chunks);
+
+    if chunks == 1 || chunk < 1024
+    {
+        return unsafe{ dot(x, y) };
+    }
+
+    let partial: Vec<f64> = x.par_chunks(chunk).zip(y.par_chunks(chunk)).map(|(xc, yc)| unsafe{ dot(xc, yc) }).collect();
+    partial.iter().sum()
+}
+
+/// Single precision dot product with f32 accumulators.
+#[inline]
+pub fn dot_f32(x: &[f32], y: &[f32]) -> f32
+{
+    unsafe{
+        dot_f32u(x, y)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn dot_f32u(x: &[f32], y: &[f32]) -> f32
+{
+    let nn = x.len();
+    const VSZ: usize = 16;
+    assert_eq!(nn, y.len());
+
+    let mut rr = _mm512_setzero_ps();
+    let mut rr1 = _mm512_setzero_ps();
+    let mut rr2 = _mm512_setzero_ps();
+    let mut rr3 = _mm512_setzero_ps();
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+    let yptr = y.as_ptr();
+
+    while i + 4 * VSZ <= nn
+    {
+        rr = _mm512_fmadd_ps(_mm512_loadu_ps(xptr.add(i)), _mm512_loadu_ps(yptr.add(i)), rr);
+        rr1 = _mm512_fmadd_ps(_mm512_loadu_ps(xptr.add(i + VSZ)), _mm512_loadu_ps(yptr.add(i + VSZ)), rr1);
+        rr2 = _mm512_fmadd_ps(_mm512_loadu_ps(xptr.add(i + 2 * VSZ)), _mm512_loadu_ps(yptr.add(i + 2 * VSZ)), rr2);
+        rr3 = _mm512_fmadd_ps(_mm512_loadu_ps(xptr.add(i + 3 * VSZ)), _mm512_loadu_ps(yptr.add(i + 3 * VSZ)), rr3);
+        i += 4 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        rr = _mm512_fmadd_ps(_mm512_loadu_ps(xptr.add(i)), _mm512_loadu_ps(yptr.add(i)), rr);
+        i += VSZ;
+    }
+
+    rr = _mm512_add_ps(_mm512_add_ps(rr, rr1), _mm512_add_ps(rr2, rr3));
+    let mut r = _mm512_reduce_add_ps(rr);
+
+    for (xi, yi) in x[i..].iter().zip(&y[i..]) {
+        r = xi.mul_add(*yi, r);
+    }
+
+    r
+}
+
+/// Dot product of f32 inputs, widened to f64 before multiplying so the products are exact and the sum
+/// carries double precision.
+#[inline]
+pub fn dot_f32_f64acc(x: &[f32], y: &[f32]) -> f64
+{
+    unsafe{
+        dot_f32_f64accu(x, y)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn dot_f32_f64accu(x: &[f32], y: &[f32]) -> f64
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+    assert_eq!(nn, y.len());
+
+    let mut rr = D512_ZERO;
+    let mut rr1 = D512_ZERO;
+    let mut rr2 = D512_ZERO;
+    let mut rr3 = D512_ZERO;
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+    let yptr = y.as_ptr();
+
+    while i + 4 * VSZ <= nn
+    {
+        rr = _mm512_fmadd_pd(_mm512_cvtps_pd(_mm256_loadu_ps(xptr.add(i))), _mm512_cvtps_pd(_mm256_loadu_ps(yptr.add(i))), rr);
+        rr1 = _mm512_fmadd_pd(_mm512_cvtps_pd(_mm256_loadu_ps(xptr.add(i + VSZ))), _mm512_cvtps_pd(_mm256_loadu_ps(yptr.add(i + VSZ))), rr1);
+        rr2 = _mm512_fmadd_pd(_mm512_cvtps_pd(_mm256_loadu_ps(xptr.add(i + 2 * VSZ))), _mm512_cvtps_pd(_mm256_loadu_ps(yptr.add(i + 2 * VSZ))), rr2);
+        rr3 = _mm512_fmadd_pd(_mm512_cvtps_pd(_mm256_loadu_ps(xptr.add(i + 3 * VSZ))), _mm512_cvtps_pd(_mm256_loadu_ps(yptr.add(i + 3 * VSZ))), rr3);
+        i += 4 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        rr = _mm512_fmadd_pd(_mm512_cvtps_pd(_mm256_loadu_ps(xptr.add(i))), _mm512_cvtps_pd(_mm256_loadu_ps(yptr.add(i))), rr);
+        i += VSZ;
+    }
+
+    rr = _mm512_add_pd(_mm512_add_pd(rr, rr1), _mm512_add_pd(rr2, rr3));
+    let mut r = _mm512_reduce_add_pd(rr);
+
+    for (xi, yi) in x[i..].iter().zip(&y[i..]) {
+        r += (*xi as f64) * (*yi as f64);
+    }
+
+    r
+}
+
+
+/// y = a x + y
+#[inline]
+pub fn axpy(a: f64, x: &[f64], y: &mut [f64])
+{
+    unsafe{
+        axpyu(a, x, y)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn axpyu(a: f64, x: &[f64], y: &mut [f64])
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+    assert_eq!(nn, y.len());
+
+    let aa = _mm512_set1_pd(a);
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+    let yptr = y.as_mut_ptr();
+
+    while i + 4 * VSZ <= nn
+    {
+        let yy = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i)), _mm512_loadu_pd(yptr.add(i)));
+        let yy1 = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i + VSZ)), _mm512_loadu_pd(yptr.add(i + VSZ)));
+        let yy2 = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i + 2 * VSZ)), _mm512_loadu_pd(yptr.add(i + 2 * VSZ)));
+        let yy3 = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i + 3 * VSZ)), _mm512_loadu_pd(yptr.add(i + 3 * VSZ)));
+        _mm512_storeu_pd(yptr.add(i), yy);
+        _mm512_storeu_pd(yptr.add(i + VSZ), yy1);
+        _mm512_storeu_pd(yptr.add(i + 2 * VSZ), yy2);
+        _mm512_storeu_pd(yptr.add(i + 3 * VSZ), yy3);
+        i += 4 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        _mm512_storeu_pd(yptr.add(i), _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i)), _mm512_loadu_pd(yptr.add(i))));
+        i += VSZ;
+    }
+
+    for ii in i..nn {
+        y[ii] = a.mul_add(x[ii], y[ii]);
+    }
+}
+
+/// y = a x + b y
+#[inline]
+pub fn axpby(a: f64, x: &[f64], b: f64, y: &mut [f64])
+{
+    unsafe{
+        axpbyu(a, x, b, y)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn axpbyu(a: f64, x: &[f64], b: f64, y: &mut [f64])
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+    assert_eq!(nn, y.len());
+
+    let aa = _mm512_set1_pd(a);
+    let bb = _mm512_set1_pd(b);
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+    let yptr = y.as_mut_ptr();
+
+    while i + 4 * VSZ <= nn
+    {
+        let yy = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i)), _mm512_mul_pd(bb, _mm512_loadu_pd(yptr.add(i))));
+        let yy1 = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i + VSZ)), _mm512_mul_pd(bb, _mm512_loadu_pd(yptr.add(i + VSZ))));
+        let yy2 = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i + 2 * VSZ)), _mm512_mul_pd(bb, _mm512_loadu_pd(yptr.add(i + 2 * VSZ))));
+        let yy3 = _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i + 3 * VSZ)), _mm512_mul_pd(bb, _mm512_loadu_pd(yptr.add(i + 3 * VSZ))));
+        _mm512_storeu_pd(yptr.add(i), yy);
+        _mm512_storeu_pd(yptr.add(i + VSZ), yy1);
+        _mm512_storeu_pd(yptr.add(i + 2 * VSZ), yy2);
+        _mm512_storeu_pd(yptr.add(i + 3 * VSZ), yy3);
+        i += 4 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        _mm512_storeu_pd(yptr.add(i), _mm512_fmadd_pd(aa, _mm512_loadu_pd(xptr.add(i)), _mm512_mul_pd(bb, _mm512_loadu_pd(yptr.add(i)))));
+        i += VSZ;
+    }
+
+    for ii in i..nn {
+        y[ii] = a.mul_add(x[ii], b * y[ii]);
+    }
+}
+
+/// x = a x
+#[inline]
+pub fn scal(a: f64, x: &mut [f64])
+{
+    unsafe{
+        scalu(a, x)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn scalu(a: f64, x: &mut [f64])
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+
+    let aa = _mm512_set1_pd(a);
+    let mut i: usize = 0;
+    let xptr = x.as_mut_ptr();
+
+    while i + 4 * VSZ <= nn
+    {
+        let xx = _mm512_mul_pd(aa, _mm512_loadu_pd(xptr.add(i)));
+        let xx1 = _mm512_mul_pd(aa, _mm512_loadu_pd(xptr.add(i + VSZ)));
+        let xx2 = _mm512_mul_pd(aa, _mm512_loadu_pd(xptr.add(i + 2 * VSZ)));
+        let xx3 = _mm512_mul_pd(aa, _mm512_loadu_pd(xptr.add(i + 3 * VSZ)));
+        _mm512_storeu_pd(xptr.add(i), xx);
+        _mm512_storeu_pd(xptr.add(i + VSZ), xx1);
+        _mm512_storeu_pd(xptr.add(i + 2 * VSZ), xx2);
+        _mm512_storeu_pd(xptr.add(i + 3 * VSZ), xx3);
+        i += 4 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        _mm512_storeu_pd(xptr.add(i), _mm512_mul_pd(aa, _mm512_loadu_pd(xptr.add(i))));
+        i += VSZ;
+    }
+
+    for xi in &mut x[i..] {
+        *xi *= a;
+    }
+}
+
+/// y = x. The standard library's memcpy is already as fast as anything we'd write by hand.
+#[inline]
+pub fn copy(x: &[f64], y: &mut [f64])
+{
+    assert_eq!(x.len(), y.len());
+    y.copy_from_slice(x);
+}
+
+/// Exchanges the contents of x and y.
+#[inline]
+pub fn swap(x: &mut [f64], y: &mut [f64])
+{
+    assert_eq!(x.len(), y.len());
+    x.swap_with_slice(y);
+}
+
+/// sum(|x|)
+#[inline]
+pub fn asum(x: &[f64]) -> f64
+{
+    unsafe{
+        asumu(x)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn asumu(x: &[f64]) -> f64
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+
+    let mut rr = D512_ZERO;
+    let mut rr1 = D512_ZERO;
+    let mut rr2 = D512_ZERO;
+    let mut rr3 = D512_ZERO;
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+
+    while i + 4 * VSZ <= nn
+    {
+        rr = _mm512_add_pd(rr, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i))));
+        rr1 = _mm512_add_pd(rr1, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i + VSZ))));
+        rr2 = _mm512_add_pd(rr2, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i + 2 * VSZ))));
+        rr3 = _mm512_add_pd(rr3, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i + 3 * VSZ))));
+        i += 4 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        rr = _mm512_add_pd(rr, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i))));
+        i += VSZ;
+    }
+
+    rr = _mm512_add_pd(_mm512_add_pd(rr, rr1), _mm512_add_pd(rr2, rr3));
+    let mut r = _mm512_reduce_add_pd(rr);
+
+    for xi in &x[i..] {
+        r += xi.abs();
+    }
+
+    r
+}
+
+/// The Euclidean norm sqrt(sum(x^2)). The sum of squares is taken after scaling x by a power of two that
+/// brings max(|x|) near 1, so it neither overflows nor loses tiny inputs to underflow.
+#[inline]
+pub fn nrm2(x: &[f64]) -> f64
+{
+    unsafe{
+        nrm2u(x)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn nrm2u(x: &[f64]) -> f64
+{
+    let amax = abs_max(x);
+
+    // max_pd doesn't reliably carry NaN, so fall back to a scalar search in the rare cases where the
+    // scaled sum below can't surface it for us.
+    if amax == 0.0 || amax.is_infinite()
+    {
+        return if x.iter().any(|v| v.is_nan()) { f64::NAN } else { amax };
+    }
+
+    // 2^-e where 2^e <= amax < 2^(e+1), clamped so that both the scale and its inverse are representable.
+    let e = if amax < f64::MIN_POSITIVE { ((amax * TWO_54).to_bits() >> 52) as i64 - 1023 - 54 } else { (amax.to_bits() >> 52) as i64 - 1023 };
+    let k = (-e).clamp(-1022, 1022);
+    let scale = f64::from_bits(((k + 1023) as u64) << 52);
+    let unscale = f64::from_bits(((1023 - k) as u64) << 52);
+
+    let nn = x.len();
+    const VSZ: usize = 8;
+
+    let ss = _mm512_set1_pd(scale);
+    let mut rr = D512_ZERO;
+    let mut rr1 = D512_ZERO;
+    let mut rr2 = D512_ZERO;
+    let mut rr3 = D512_ZERO;
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+
+    while i + 4 * VSZ <= nn
+    {
+        let xx = _mm512_mul_pd(ss, _mm512_loadu_pd(xptr.add(i)));
+        let xx1 = _mm512_mul_pd(ss, _mm512_loadu_pd(xptr.add(i + VSZ)));
+        let xx2 = _mm512_mul_pd(ss, _mm512_loadu_pd(xptr.add(i + 2 * VSZ)));
+        let xx3 = _mm512_mul_pd(ss, _mm512_loadu_pd(xptr.add(i + 3 * VSZ)));
+        rr = _mm512_fmadd_pd(xx, xx, rr);
+        rr1 = _mm512_fmadd_pd(xx1, xx1, rr1);
+        rr2 = _mm512_fmadd_pd(xx2, xx2, rr2);
+        rr3 = _mm512_fmadd_pd(xx3, xx3, rr3);
+        i += 4 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        let xx = _mm512_mul_pd(ss, _mm512_loadu_pd(xptr.add(i)));
+        rr = _mm512_fmadd_pd(xx, xx, rr);
+        i += VSZ;
+    }
+
+    rr = _mm512_add_pd(_mm512_add_pd(rr, rr1), _mm512_add_pd(rr2, rr3));
+    let mut r = _mm512_reduce_add_pd(rr);
+
+    for xi in &x[i..] {
+        let xs = xi * scale;
+        r = xs.mul_add(xs, r);
+    }
+
+    r.sqrt() * unscale
+}
+
+/// The index of the first element with the largest absolute value, or None if x is empty. NaNs are skipped.
+#[inline]
+pub fn iamax(x: &[f64]) -> Option<usize>
+{
+    unsafe{
+        abs_extreme_index::<_CMP_GT_OQ>(x)
+    }
+}
+
+/// The index of the first element with the smallest absolute value, or None if x is empty. NaNs are skipped.
+#[inline]
+pub fn iamin(x: &[f64]) -> Option<usize>
+{
+    unsafe{
+        abs_extreme_index::<_CMP_LT_OQ>(x)
+    }
+}
+
+/// Applies the plane rotation [c s; -s c] to the pairs (x[i], y[i]).
+#[inline]
+pub fn rot(x: &mut [f64], y: &mut [f64], c: f64, s: f64)
+{
+    unsafe{
+        rotu(x, y, c, s)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn rotu(x: &mut [f64], y: &mut [f64], c: f64, s: f64)
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+    assert_eq!(nn, y.len());
+
+    let cc = _mm512_set1_pd(c);
+    let ss = _mm512_set1_pd(s);
+    let mut i: usize = 0;
+    let xptr = x.as_mut_ptr();
+    let yptr = y.as_mut_ptr();
+
+    while i + 2 * VSZ <= nn
+    {
+        let xx = _mm512_loadu_pd(xptr.add(i));
+        let yy = _mm512_loadu_pd(yptr.add(i));
+        let xx1 = _mm512_loadu_pd(xptr.add(i + VSZ));
+        let yy1 = _mm512_loadu_pd(yptr.add(i + VSZ));
+        _mm512_storeu_pd(xptr.add(i), _mm512_fmadd_pd(cc, xx, _mm512_mul_pd(ss, yy)));
+        _mm512_storeu_pd(yptr.add(i), _mm512_fmsub_pd(cc, yy, _mm512_mul_pd(ss, xx)));
+        _mm512_storeu_pd(xptr.add(i + VSZ), _mm512_fmadd_pd(cc, xx1, _mm512_mul_pd(ss, yy1)));
+        _mm512_storeu_pd(yptr.add(i + VSZ), _mm512_fmsub_pd(cc, yy1, _mm512_mul_pd(ss, xx1)));
+        i += 2 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        let xx = _mm512_loadu_pd(xptr.add(i));
+        let yy = _mm512_loadu_pd(yptr.add(i));
+        _mm512_storeu_pd(xptr.add(i), _mm512_fmadd_pd(cc, xx, _mm512_mul_pd(ss, yy)));
+        _mm512_storeu_pd(yptr.add(i), _mm512_fmsub_pd(cc, yy, _mm512_mul_pd(ss, xx)));
+        i += VSZ;
+    }
+
+    for ii in i..nn {
+        let (xi, yi) = (x[ii], y[ii]);
+        x[ii] = c.mul_add(xi, s * yi);
+        y[ii] = c.mul_add(yi, -(s * xi));
+    }
+}
+
+/// Constructs the Givens rotation that zeroes b, following the reference BLAS drotg: on return a holds r,
+/// b holds the reconstruction value z, and [c s; -s c] [a b]^T = [r 0]^T.
+pub fn rotg(a: &mut f64, b: &mut f64, c: &mut f64, s: &mut f64)
+{
+    let anorm = a.abs();
+    let bnorm = b.abs();
+
+    if bnorm == 0.0
+    {
+        *c = 1.0;
+        *s = 0.0;
+        *b = 0.0;
+    }
+    else if anorm == 0.0
+    {
+        *c = 0.0;
+        *s = 1.0;
+        *a = *b;
+        *b = 1.0;
+    }
+    else
+    {
+        let sigma = if anorm > bnorm { a.signum() } else { b.signum() };
+        let r = sigma * a.hypot(*b);
+        *c = *a / r;
+        *s = *b / r;
+        let z = if anorm > bnorm { *s } else if *c != 0.0 { 1.0 / *c } else { 1.0 };
+        *a = r;
+        *b = z;
+    }
+}
+
+/// How the matrix arguments are stored. RowMajor keeps each row contiguous, with consecutive rows a leading
+/// dimension apart; ColMajor does the same with columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout
+{
+    RowMajor,
+    ColMajor,
+}
+
+/// Whether a matrix argument is used as stored or transposed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transpose
+{
+    NoTrans,
+    Trans,
+}
+
+/// Which triangle of a matrix argument holds the triangular matrix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Triangle
+{
+    Lower,
+    Upper,
+}
+
+/// Whether a triangular matrix argument has an implicit unit diagonal, in which case its stored diagonal
+/// isn't read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Diagonal
+{
+    NonUnit,
+    Unit,
+}
+
+/// Why a factorization gave up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinalgError
+{
+    /// The leading minor ending at this column isn't positive definite.
+    NotPositiveDefinite(usize),
+    /// Every candidate pivot in this column is zero.
+    Singular(usize),
+}
+
+impl std::fmt::Display for LinalgError
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+    {
+        match self
+        {
+            LinalgError::NotPositiveDefinite(j) => write!(f, "matrix is not positive definite (failed at column {})", j),
+            LinalgError::Singular(j) => write!(f, "matrix is singular (zero pivot in column {})", j),
+        }
+    }
+}
+
+impl std::error::Error for LinalgError {}
+
+/// y = alpha op(A) x + beta y, where A is stored as layout says with leading dimension lda and op(A) is A or
+/// A^T. The shape of op(A) comes from x and y: it is y.len() x x.len(). When beta is zero y is only written,
+/// never read, so it may start out holding NaNs.
+#[allow(clippy::too_many_arguments)]
+pub fn gemv(layout: Layout, trans: Transpose, alpha: f64, a: &[f64], lda: usize, x: &[f64], beta: f64, y: &mut [f64])
+{
+    let trans = row_major_trans(layout, trans);
+    check_gemv(trans, a.len(), lda, x.len(), y.len());
+
+    match trans
+    {
+        Transpose::NoTrans => {
+            for (i, yi) in y.iter_mut().enumerate() {
+                *yi = gemv_row(alpha, &a[i * lda..i * lda + x.len()], x, beta, *yi);
+            }
+        },
+        Transpose::Trans => gemv_trans_columns(alpha, a, lda, x, beta, y, 0),
+    }
+}
+
+/// gemv with the elements of y spread across rayon tasks.
+#[allow(clippy::too_many_arguments)]
+pub fn gemv_par(layout: Layout, trans: Transpose, alpha: f64, a: &[f64], lda: usize, x: &[f64], beta: f64, y: &mut [f64])
+{
+    use rayon::prelude::*;
+    let trans = row_major_trans(layout, trans);
+    check_gemv(trans, a.len(), lda, x.len(), y.len());
+
+    match trans
+    {
+        Transpose::NoTrans => {
+            y.par_iter_mut().enumerate().for_each(|(i, yi)| {
+                *yi = gemv_row(alpha, &a[i * lda..i * lda + x.len()], x, beta, *yi);
+            });
+        },
+        Transpose::Trans => {
+            // Each task owns a contiguous block of y and runs the axpy sweep over the matching columns of A.
+            let chunk = (y.len() / 32).max(256);
+            y.par_chunks_mut(chunk).enumerate().for_each(|(index, yc)| gemv_trans_columns(alpha, a, lda, x, beta, yc, index * chunk));
+        },
+    }
+}
+
+// A column-major matrix is the row-major storage of its transpose, so everything below works on row-major
+// storage and column-major arguments come in with trans (and, for triangles, uplo) flipped.
+fn row_major_trans(layout: Layout, trans: Transpose) -> Transpose
+{
+    match (layout, trans)
+    {
+        (Layout::RowMajor, _) => trans,
+        (Layout::ColMajor, Transpose::NoTrans) => Transpose::Trans,
+        (Layout::ColMajor, Transpose::Trans) => Transpose::NoTrans,
+    }
+}
+
+fn row_major_triangle(layout: Layout, uplo: Triangle) -> Triangle
+{
+    match (layout, uplo)
+    {
+        (Layout::RowMajor, _) => uplo,
+        (Layout::ColMajor, Triangle::Lower) => Triangle::Upper,
+        (Layout::ColMajor, Triangle::Upper) => Triangle::Lower,
+    }
+}
+
+fn check_gemv(trans: Transpose, alen: usize, lda: usize, xlen: usize, ylen: usize)
+{
+    let (rows, cols) = match trans { Transpose::NoTrans => (ylen, xlen), Transpose::Trans => (xlen, ylen) };
+    assert!(lda >= cols);
+    assert!(rows == 0 || alen >= (rows - 1) * lda + cols);
+}
+
+#[inline]
+fn gemv_row(alpha: f64, row: &[f64], x: &[f64], beta: f64, y: f64) -> f64
+{
+    let d = alpha * unsafe{ dot(row, x) };
+    if beta == 0.0 { d } else { beta.mul_add(y, d) }
+}
+
+// y = alpha A[:, col0..col0 + y.len()]^T x + beta y, one axpy per row of A.
+fn gemv_trans_columns(alpha: f64, a: &[f64], lda: usize, x: &[f64], beta: f64, y: &mut [f64], col0: usize)
+{
+    if beta == 0.0
+    {
+        y.fill(0.0);
+    }
+    else if beta != 1.0
+    {
+        scal(beta, y);
+    }
+
+    let n = y.len();
+
+    for (i, xi) in x.iter().enumerate() {
+        axpy(alpha * xi, &a[i * lda + col0..i * lda + col0 + n], y);
+    }
+}
+
+/// C = alpha op(A) op(B) + beta C, where op(A) is m x k, op(B) is k x n and C is m x n, all stored as layout
+/// says, each with its own leading dimension. When beta is zero C is only written, never read.
+///
+/// This is the usual Goto/BLIS scheme: B is packed into KC x NC blocks of 16 column wide panels, A into
+/// MC x KC blocks of 8 row panels with alpha folded in, and an 8x16 register tile walks over the packed
+/// panels, so the inner loop only ever streams contiguous memory.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm(layout: Layout, transa: Transpose, transb: Transpose, m: usize, n: usize, k: usize, alpha: f64, a: &[f64],
+    lda: usize, b: &[f64], ldb: usize, beta: f64, c: &mut [f64], ldc: usize)
+{
+    // In column-major storage C^T = op(B)^T op(A)^T is row-major, with A and B trading places.
+    match layout
+    {
+        Layout::RowMajor => gemm_row_major(transa, transb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc),
+        Layout::ColMajor => gemm_row_major(transb, transa, n, m, k, alpha, b, ldb, a, lda, beta, c, ldc),
+    }
+}
+
+/// gemm with the MC row blocks of C (row-major) spread across rayon tasks. Every element of C goes through the
+/// same operations in the same order as in gemm, so the results are identical.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm_par(layout: Layout, transa: Transpose, transb: Transpose, m: usize, n: usize, k: usize, alpha: f64,
+    a: &[f64], lda: usize, b: &[f64], ldb: usize, beta: f64, c: &mut [f64], ldc: usize)
+{
+    match layout
+    {
+        Layout::RowMajor => gemm_par_row_major(transa, transb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc),
+        Layout::ColMajor => gemm_par_row_major(transb, transa, n, m, k, alpha, b, ldb, a, lda, beta, c, ldc),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gemm_row_major(transa: Transpose, transb: Transpose, m: usize, n: usize, k: usize, alpha: f64, a: &[f64], lda: usize,
+    b: &[f64], ldb: usize, beta: f64, c: &mut [f64], ldc: usize)
+{
+    check_gemm(transa, transb, m, n, k, a.len(), lda, b.len(), ldb, c.len(), ldc);
+
+    if m == 0 || n == 0
+    {
+        return;
+    }
+
+    scale_c(m, n, beta, c, ldc);
+
+    if k == 0 || alpha == 0.0
+    {
+        return;
+    }
+
+    let mut bpack = vec![0.0; GEMM_KC * GEMM_NC];
+    let mut apack = vec![0.0; GEMM_MC * GEMM_KC];
+
+    for jc in (0..n).step_by(GEMM_NC) {
+        let nc = GEMM_NC.min(n - jc);
+        for pc in (0..k).step_by(GEMM_KC) {
+            let kc = GEMM_KC.min(k - pc);
+            pack_b(transb, b, ldb, pc, kc, jc, nc, &mut bpack);
+            for (index, cc) in c.chunks_mut(GEMM_MC * ldc).enumerate().take(m.div_ceil(GEMM_MC)) {
+                let ic = index * GEMM_MC;
+                let mc = GEMM_MC.min(m - ic);
+                pack_a(transa, alpha, a, lda, ic, mc, pc, kc, &mut apack);
+                unsafe{ macro_kernel(mc, nc, kc, &apack, &bpack, &mut cc[jc..], ldc) };
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn gemm_par_row_major(transa: Transpose, transb: Transpose, m: usize, n: usize, k: usize, alpha: f64, a: &[f64],
+    lda: usize, b: &[f64], ldb: usize, beta: f64, c: &mut [f64], ldc: usize)
+{
+    use rayon::prelude::*;
+    check_gemm(transa, transb, m, n, k, a.len(), lda, b.len(), ldb, c.len(), ldc);
+
+    if m == 0 || n == 0
+    {
+        return;
+    }
+
+    scale_c(m, n, beta, c, ldc);
+
+    if k == 0 || alpha == 0.0
+    {
+        return;
+    }
+
+    let mut bpack = vec![0.0; GEMM_KC * GEMM_NC];
+
+    for jc in (0..n).step_by(GEMM_NC) {
+        let nc = GEMM_NC.min(n - jc);
+        for pc in (0..k).step_by(GEMM_KC) {
+            let kc = GEMM_KC.min(k - pc);
+            pack_b(transb, b, ldb, pc, kc, jc, nc, &mut bpack);
+            let bpack = &bpack;
+            c.par_chunks_mut(GEMM_MC * ldc).enumerate().take(m.div_ceil(GEMM_MC)).for_each(|(index, cc)| {
+                let ic = index * GEMM_MC;
+                let mc = GEMM_MC.min(m - ic);
+                let mut apack = vec![0.0; GEMM_MC * GEMM_KC];
+                pack_a(transa, alpha, a, lda, ic, mc, pc, kc, &mut apack);
+                unsafe{ macro_kernel(mc, nc, kc, &apack, bpack, &mut cc[jc..], ldc) };
+            });
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_gemm(transa: Transpose, transb: Transpose, m: usize, n: usize, k: usize, alen: usize, lda: usize, blen: usize,
+    ldb: usize, clen: usize, ldc: usize)
+{
+    let check = |trans: Transpose, rows: usize, cols: usize, len: usize, ld: usize| {
+        let (r, c) = if trans == Transpose::NoTrans { (rows, cols) } else { (cols, rows) };
+        assert!(ld >= c);
+        assert!(r == 0 || c == 0 || len >= (r - 1) * ld + c);
+    };
+
+    check(transa, m, k, alen, lda);
+    check(transb, k, n, blen, ldb);
+    check(Transpose::NoTrans, m, n, clen, ldc);
+}
+
+fn scale_c(m: usize, n: usize, beta: f64, c: &mut [f64], ldc: usize)
+{
+    if beta == 1.0
+    {
+        return;
+    }
+
+    for row in c.chunks_mut(ldc).take(m) {
+        if beta == 0.0 { row[..n].fill(0.0) } else { scal(beta, &mut row[..n]) }
+    }
+}
+
+// Packs the kc x nc block of op(B) at (pc, jc) into panels of GEMM_NR columns. Panel q holds, for each of
+// the kc rows in turn, the GEMM_NR entries of that row, zero padded past the edge of B.
+#[allow(clippy::too_many_arguments)]
+fn pack_b(transb: Transpose, b: &[f64], ldb: usize, pc: usize, kc: usize, jc: usize, nc: usize, bpack: &mut [f64])
+{
+    for (q, panel) in bpack.chunks_mut(GEMM_NR * kc).take(nc.div_ceil(GEMM_NR)).enumerate() {
+        let j0 = jc + q * GEMM_NR;
+        let nr = GEMM_NR.min(jc + nc - j0);
+        for (p, dst) in panel.chunks_mut(GEMM_NR).enumerate() {
+            match transb
+            {
+                Transpose::NoTrans => dst[..nr].copy_from_slice(&b[(pc + p) * ldb + j0..(pc + p) * ldb + j0 + nr]),
+                Transpose::Trans => dst[..nr].iter_mut().enumerate().for_each(|(j, d)| *d = b[(j0 + j) * ldb + pc + p]),
+            }
+            dst[nr..].fill(0.0);
+        }
+    }
+}
+
+// Packs alpha times the mc x kc block of op(A) at (ic, pc) into panels of GEMM_MR rows. Panel r holds, for
+// each of the kc columns in turn, the GEMM_MR entries of that column, zero padded past the edge of A.
+#[allow(clippy::too_many_arguments)]
+fn pack_a(transa: Transpose, alpha: f64, a: &[f64], lda: usize, ic: usize, mc: usize, pc: usize, kc: usize, apack: &mut [f64])
+{
+    for (r, panel) in apack.chunks_mut(GEMM_MR * kc).take(mc.div_ceil(GEMM_MR)).enumerate() {
+        let i0 = ic + r * GEMM_MR;
+        let mr = GEMM_MR.min(ic + mc - i0);
+        for (p, dst) in panel.chunks_mut(GEMM_MR).enumerate() {
+            match transa
+            {
+                Transpose::NoTrans => dst[..mr].iter_mut().enumerate().for_each(|(i, d)| *d = alpha * a[(i0 + i) * lda + pc + p]),
+                Transpose::Trans => dst[..mr].iter_mut().enumerate().for_each(|(i, d)| *d = alpha * a[(pc + p) * lda + i0 + i]),
+            }
+            dst[mr..].fill(0.0);
+        }
+    }
+}
+
+// C[0..mc, 0..nc] += the packed A block times the packed B block, one register tile at a time.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn macro_kernel(mc: usize, nc: usize, kc: usize, apack: &[f64], bpack: &[f64], c: &mut [f64], ldc: usize)
+{
+    for jr in (0..nc).step_by(GEMM_NR) {
+        let bp = bpack.as_ptr().add(jr * kc);
+        for ir in (0..mc).step_by(GEMM_MR) {
+            let ap = apack.as_ptr().add(ir * kc);
+            let cp = c.as_mut_ptr().add(ir * ldc + jr);
+            micro_kernel(kc, ap, bp, cp, ldc, GEMM_MR.min(mc - ir), GEMM_NR.min(nc - jr));
+        }
+    }
+}
+
+// The 8x16 register tile: two accumulators per row of C, one broadcast of A per row and two loads of B per
+// step of k. Partial tiles on the edges of C are written back with masked loads and stores.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn micro_kernel(kc: usize, ap: *const f64, bp: *const f64, c: *mut f64, ldc: usize, mr: usize, nr: usize)
+{
+    let mut acc0 = [D512_ZERO; GEMM_MR];
+    let mut acc1 = [D512_ZERO; GEMM_MR];
+
+    for p in 0..kc {
+        let b0 = _mm512_loadu_pd(bp.add(p * GEMM_NR));
+        let b1 = _mm512_loadu_pd(bp.add(p * GEMM_NR + 8));
+        for r in 0..GEMM_MR {
+            let aa = _mm512_set1_pd(*ap.add(p * GEMM_MR + r));
+            acc0[r] = _mm512_fmadd_pd(aa, b0, acc0[r]);
+            acc1[r] = _mm512_fmadd_pd(aa, b1, acc1[r]);
+        }
+    }
+
+    let mask0 = if nr >= 8 { 0xff } else { ((1u32 << nr) - 1) as __mmask8 };
+    let mask1 = if nr >= 16 { 0xff } else if nr > 8 { ((1u32 << (nr - 8)) - 1) as __mmask8 } else { 0 };
+
+    for r in 0..mr {
+        let crow = c.add(r * ldc);
+        let c0 = _mm512_maskz_loadu_pd(mask0, crow);
+        let c1 = _mm512_maskz_loadu_pd(mask1, crow.wrapping_add(8));
+        _mm512_mask_storeu_pd(crow, mask0, _mm512_add_pd(c0, acc0[r]));
+        _mm512_mask_storeu_pd(crow.wrapping_add(8), mask1, _mm512_add_pd(c1, acc1[r]));
+    }
+}
+
+/// Overwrites the lower triangle of the symmetric positive definite n x n row-major matrix a with its Cholesky
+/// factor L, so that A = L L^T, and zeroes the strict upper triangle. Only the lower triangle of A is read.
+/// Fails, leaving a partly overwritten, if a leading minor isn't positive definite.
+pub fn cholesky(a: &mut [f64], n: usize) -> Result<(), LinalgError>
+{
+    assert!(a.len() >= n * n);
+
+    // Row by row: L[i][j] = (A[i][j] - L[i][0..j] . L[j][0..j]) / L[j][j], so every inner product runs along
+    // two contiguous rows.
+    for i in 0..n {
+        let (done, rest) = a.split_at_mut(i * n);
+        let row = &mut rest[..n];
+        for j in 0..i {
+            row[j] = (row[j] - unsafe{ dot(&row[..j], &done[j * n..j * n + j]) }) / done[j * n + j];
+        }
+
+        let d = row[i] - unsafe{ dot(&row[..i], &row[..i]) };
+
+        if d <= 0.0 || d.is_nan()
+        {
+            return Err(LinalgError::NotPositiveDefinite(i));
+        }
+
+        row[i] = d.sqrt();
+        row[i + 1..].fill(0.0);
+    }
+
+    Ok(())
+}
+
+/// Solves A x = b given the Cholesky factor L from cholesky, overwriting b with x.
+pub fn cholesky_solve(l: &[f64], n: usize, b: &mut [f64])
+{
+    assert_eq!(b.len(), n);
+    trsv(Layout::RowMajor, Triangle::Lower, Transpose::NoTrans, Diagonal::NonUnit, l, n, b);
+    trsv(Layout::RowMajor, Triangle::Lower, Transpose::Trans, Diagonal::NonUnit, l, n, b);
+}
+
+/// Factors the n x n row-major matrix a as P A = L U with partial pivoting, overwriting a with U on and above
+/// the diagonal and the unit lower triangular L below it. Returns the pivots LAPACK style: row k was
+/// swapped with row piv[k] at step k. Fails, leaving a partly factored, on an exactly zero pivot.
+pub fn lu_factor(a: &mut [f64], n: usize) -> Result<Vec<usize>, LinalgError>
+{
+    assert!(a.len() >= n * n);
+    let mut piv = vec![0; n];
+
+    for k in 0..n {
+        let mut p = k;
+        for i in k + 1..n {
+            if a[i * n + k].abs() > a[p * n + k].abs()
+            {
+                p = i;
+            }
+        }
+
+        piv[k] = p;
+
+        if a[p * n + k] == 0.0
+        {
+            return Err(LinalgError::Singular(k));
+        }
+
+        if p != k
+        {
+            let (top, bottom) = a.split_at_mut(p * n);
+            swap(&mut top[k * n..k * n + n], &mut bottom[..n]);
+        }
+
+        // The trailing update is one axpy per row: row i -= l_ik row k, right of the diagonal.
+        let (top, bottom) = a.split_at_mut((k + 1) * n);
+        let pivot_row = &top[k * n + k..k * n + n];
+        for row in bottom.chunks_mut(n).take(n - k - 1) {
+            let l = row[k] / pivot_row[0];
+            row[k] = l;
+            axpy(-l, &pivot_row[1..], &mut row[k + 1..]);
+        }
+    }
+
+    Ok(piv)
+}
+
+/// Solves A x = b given the factors and pivots from lu_factor, overwriting b with x.
+pub fn lu_solve(lu: &[f64], n: usize, piv: &[usize], b: &mut [f64])
+{
+    assert_eq!(b.len(), n);
+    assert_eq!(piv.len(), n);
+
+    for (k, p) in piv.iter().enumerate() {
+        b.swap(k, *p);
+    }
+
+    trsv(Layout::RowMajor, Triangle::Lower, Transpose::NoTrans, Diagonal::Unit, lu, n, b);
+    trsv(Layout::RowMajor, Triangle::Upper, Transpose::NoTrans, Diagonal::NonUnit, lu, n, b);
+}
+
+/// The determinant of the n x n row-major matrix a, from its LU factorization.
+pub fn det(a: &[f64], n: usize) -> f64
+{
+    let mut lu = a[..n * n].to_vec();
+
+    match lu_factor(&mut lu, n)
+    {
+        Ok(piv) => {
+            let swaps = piv.iter().enumerate().filter(|(k, p)| *k != **p).count();
+            let d: f64 = (0..n).map(|i| lu[i * n + i]).product();
+            if swaps % 2 == 0 { d } else { -d }
+        },
+        Err(_) => 0.0,
+    }
+}
+
+/// The inverse of the n x n row-major matrix a, solving A X = I with the LU factors.
+pub fn inverse(a: &[f64], n: usize) -> Result<Vec<f64>, LinalgError>
+{
+    let mut lu = a[..n * n].to_vec();
+    let piv = lu_factor(&mut lu, n)?;
+
+    let mut x = vec![0.0; n * n];
+    for i in 0..n {
+        x[i * n + i] = 1.0;
+    }
+
+    for (k, p) in piv.iter().enumerate() {
+        if k != *p
+        {
+            let (top, bottom) = x.split_at_mut(*p * n);
+            swap(&mut top[k * n..k * n + n], &mut bottom[..n]);
+        }
+    }
+
+    trsm(Layout::RowMajor, Triangle::Lower, Transpose::NoTrans, Diagonal::Unit, n, n, 1.0, &lu, n, &mut x, n);
+    trsm(Layout::RowMajor, Triangle::Upper, Transpose::NoTrans, Diagonal::NonUnit, n, n, 1.0, &lu, n, &mut x, n);
+
+    Ok(x)
+}
+
+/// The eigenvalues, in ascending order, and orthonormal eigenvectors of the symmetric n x n row-major matrix
+/// a, of which only the lower triangle is read. Eigenvector j is column j of the returned row-major matrix V,
+/// so A = V diag(w) V^T.
+///
+/// A is first reduced to a tridiagonal T = Q^T A Q with Householder reflections, then T is diagonalized with
+/// the implicit QL algorithm (tql2 from EISPACK). Q^T is built and rotated row by row, which keeps every
+/// reflector and rotation update a dot, axpy or rot over contiguous rows.
+pub fn sym_eigen(a: &[f64], n: usize) -> (Vec<f64>, Vec<f64>)
+{
+    assert!(a.len() >= n * n);
+
+    let mut w = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            w[i * n + j] = if j <= i { a[i * n + j] } else { a[j * n + i] };
+        }
+    }
+
+    let (mut d, mut e, mut qt) = tridiagonalize(&mut w, n);
+    tridiagonal_ql(&mut d, &mut e, &mut qt, n);
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| d[*i].total_cmp(&d[*j]));
+
+    let values = order.iter().map(|i| d[*i]).collect();
+    let mut vectors = vec![0.0; n * n];
+    for (j, src) in order.iter().enumerate() {
+        for i in 0..n {
+            vectors[i * n + j] = qt[src * n + i];
+        }
+    }
+
+    (values, vectors)
+}
+
+// Householder reduction of the symmetric matrix w (both triangles stored), which is overwritten. Returns the
+// diagonal d, the superdiagonal e (with e[n - 1] = 0) and Q^T.
+fn tridiagonalize(w: &mut [f64], n: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>)
+{
+    let mut d = vec![0.0; n];
+    let mut e = vec![0.0; n];
+    let mut qt = vec![0.0; n * n];
+    for i in 0..n {
+        qt[i * n + i] = 1.0;
+    }
+
+    let mut p = vec![0.0; n];
+    let mut u = vec![0.0; n];
+
+    for k in 0..n {
+        d[k] = w[k * n + k];
+
+        if k + 2 >= n
+        {
+            if k + 1 < n
+            {
+                e[k] = w[k * n + k + 1];
+            }
+            continue;
+        }
+
+        // The reflector H = I - tau v v^T maps row k right of the diagonal, x, onto alpha e_1.
+        let m = n - k - 1;
+        let mut v = w[k * n + k + 1..k * n + n].to_vec();
+        let sigma = nrm2(&v[1..]);
+
+        if sigma == 0.0
+        {
+            e[k] = v[0];
+            continue;
+        }
+
+        let alpha = if v[0] >= 0.0 { -v[0].hypot(sigma) } else { v[0].hypot(sigma) };
+        v[0] -= alpha;
+        let tau = 2.0 / (v[0] * v[0] + sigma * sigma);
+        e[k] = alpha;
+
+        // The trailing block B becomes H B H = B - v z^T - z v^T with p = tau B v and z = p - (tau/2)(v.p) v.
+        let trailing = &mut w[(k + 1) * n..];
+        let p = &mut p[..m];
+        for (i, pi) in p.iter_mut().enumerate() {
+            *pi = tau * unsafe{ dot(&trailing[i * n + k + 1..i * n + n], &v) };
+        }
+        let half = -0.5 * tau * unsafe{ dot(&v, p) };
+        axpy(half, &v, p);
+        for (i, row) in trailing.chunks_mut(n).take(m).enumerate() {
+            let row = &mut row[k + 1..];
+            axpy(-v[i], p, row);
+            axpy(-p[i], &v, row);
+        }
+
+        // Q^T = H Q^T on rows k + 1 onwards.
+        u.fill(0.0);
+        let rows = &mut qt[(k + 1) * n..];
+        for (i, row) in rows.chunks(n).enumerate() {
+            axpy(v[i], row, &mut u);
+        }
+        for (i, row) in rows.chunks_mut(n).enumerate() {
+            axpy(-tau * v[i], &u, row);
+        }
+    }
+
+    (d, e, qt)
+}
+
+// Implicit QL iterations with Wilkinson shifts on the tridiagonal (d, e), applying each plane rotation to the
+// rows of vt. On return d holds the eigenvalues (unsorted) and row i of vt the eigenvector for d[i].
+fn tridiagonal_ql(d: &mut [f64], e: &mut [f64], vt: &mut [f64], n: usize)
+{
+    const MAX_ITERATIONS: usize = 60;
+
+    let mut f = 0.0;
+    let mut tst1: f64 = 0.0;
+
+    for l in 0..n {
+        tst1 = tst1.max(d[l].abs() + e[l].abs());
+
+        let mut m = l;
+        while m + 1 < n && e[m].abs() > f64::EPSILON * tst1 {
+            m += 1;
+        }
+
+        if m > l
+        {
+            for _ in 0..MAX_ITERATIONS {
+                let g = d[l];
+                let mut p = (d[l + 1] - g) / (2.0 * e[l]);
+                let r = if p < 0.0 { -p.hypot(1.0) } else { p.hypot(1.0) };
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+                let dl1 = d[l + 1];
+                let h = g - d[l];
+                for di in &mut d[l + 2..] {
+                    *di -= h;
+                }
+                f += h;
+
+                p = d[m];
+                let mut c = 1.0;
+                let mut c2 = c;
+                let mut c3 = c;
+                let el1 = e[l + 1];
+                let mut s = 0.0;
+                let mut s2 = 0.0;
+
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    let g = c * e[i];
+                    let h = c * p;
+                    let r = p.hypot(e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+
+                    let (top, bottom) = vt.split_at_mut((i + 1) * n);
+                    rot(&mut bottom[..n], &mut top[i * n..], c, s);
+                }
+
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+
+                if e[l].abs() <= f64::EPSILON * tst1 || e[l].is_nan()
+                {
+                    break;
+                }
+            }
+        }
+
+        d[l] += f;
+        e[l] = 0.0;
+    }
+}
+
+/// Solves op(A) x = b for x, where A is an n x n triangular matrix stored as layout says with leading dimension
+/// lda, and n = x.len(). x holds b on entry. Solves that run along the stored rows of A use dot, the others
+/// sweep its rows into x with axpy.
+pub fn trsv(layout: Layout, uplo: Triangle, trans: Transpose, diag: Diagonal, a: &[f64], lda: usize, x: &mut [f64])
+{
+    let uplo = row_major_triangle(layout, uplo);
+    let trans = row_major_trans(layout, trans);
+    let n = x.len();
+    assert!(lda >= n);
+    assert!(n == 0 || a.len() >= (n - 1) * lda + n);
+
+    let pivot = |i: usize| if diag == Diagonal::Unit { 1.0 } else { a[i * lda + i] };
+
+    match (uplo, trans)
+    {
+        (Triangle::Lower, Transpose::NoTrans) => {
+            for i in 0..n {
+                let (solved, rest) = x.split_at_mut(i);
+                rest[0] = (rest[0] - unsafe{ dot(&a[i * lda..i * lda + i], solved) }) / pivot(i);
+            }
+        },
+        (Triangle::Upper, Transpose::NoTrans) => {
+            for i in (0..n).rev() {
+                let (head, solved) = x.split_at_mut(i + 1);
+                head[i] = (head[i] - unsafe{ dot(&a[i * lda + i + 1..i * lda + n], solved) }) / pivot(i);
+            }
+        },
+        (Triangle::Lower, Transpose::Trans) => {
+            for i in (0..n).rev() {
+                let (head, rest) = x.split_at_mut(i);
+                rest[0] /= pivot(i);
+                axpy(-rest[0], &a[i * lda..i * lda + i], head);
+            }
+        },
+        (Triangle::Upper, Transpose::Trans) => {
+            for i in 0..n {
+                let (head, tail) = x.split_at_mut(i + 1);
+                head[i] /= pivot(i);
+                axpy(-head[i], &a[i * lda + i + 1..i * lda + n], tail);
+            }
+        },
+    }
+}
+
+/// Solves op(A) X = alpha B for X, where A is an n x n triangular matrix and B is n x nrhs, both stored as
+/// layout says with leading dimensions lda and ldb. X overwrites B. In row-major storage every update is an axpy
+/// between whole rows of B; in column-major storage each column of B is contiguous and gets its own trsv.
+#[allow(clippy::too_many_arguments)]
+pub fn trsm(layout: Layout, uplo: Triangle, trans: Transpose, diag: Diagonal, n: usize, nrhs: usize, alpha: f64,
+    a: &[f64], lda: usize, b: &mut [f64], ldb: usize)
+{
+    match layout
+    {
+        Layout::RowMajor => trsm_row_major(uplo, trans, diag, n, nrhs, alpha, a, lda, b, ldb),
+        Layout::ColMajor => {
+            assert!(ldb >= n);
+            assert!(n == 0 || nrhs == 0 || b.len() >= (nrhs - 1) * ldb + n);
+            for j in 0..nrhs {
+                let col = &mut b[j * ldb..j * ldb + n];
+                if alpha != 1.0
+                {
+                    scal(alpha, col);
+                }
+                trsv(layout, uplo, trans, diag, a, lda, col);
+            }
+        },
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn trsm_row_major(uplo: Triangle, trans: Transpose, diag: Diagonal, n: usize, nrhs: usize, alpha: f64, a: &[f64],
+    lda: usize, b: &mut [f64], ldb: usize)
+{
+    assert!(lda >= n && ldb >= nrhs);
+    assert!(n == 0 || a.len() >= (n - 1) * lda + n);
+    assert!(n == 0 || nrhs == 0 || b.len() >= (n - 1) * ldb + nrhs);
+
+    if n == 0 || nrhs == 0
+    {
+        return;
+    }
+
+    let pivot = |i: usize| if diag == Diagonal::Unit { 1.0 } else { a[i * lda + i] };
+
+    if alpha != 1.0
+    {
+        for i in 0..n {
+            scal(alpha, &mut b[i * ldb..i * ldb + nrhs]);
+        }
+    }
+
+    // B[dst] += f B[src]
+    let update = |b: &mut [f64], dst: usize, src: usize, f: f64| {
+        let (d, s) = two_rows(b, ldb, nrhs, dst, src);
+        axpy(f, s, d);
+    };
+
+    match (uplo, trans)
+    {
+        (Triangle::Lower, Transpose::NoTrans) => {
+            for i in 0..n {
+                for j in 0..i {
+                    update(b, i, j, -a[i * lda + j]);
+                }
+                scal(1.0 / pivot(i), &mut b[i * ldb..i * ldb + nrhs]);
+            }
+        },
+        (Triangle::Upper, Transpose::NoTrans) => {
+            for i in (0..n).rev() {
+                for j in i + 1..n {
+                    update(b, i, j, -a[i * lda + j]);
+                }
+                scal(1.0 / pivot(i), &mut b[i * ldb..i * ldb + nrhs]);
+            }
+        },
+        (Triangle::Lower, Transpose::Trans) => {
+            for i in (0..n).rev() {
+                scal(1.0 / pivot(i), &mut b[i * ldb..i * ldb + nrhs]);
+                for j in 0..i {
+                    update(b, j, i, -a[i * lda + j]);
+                }
+            }
+        },
+        (Triangle::Upper, Transpose::Trans) => {
+            for i in 0..n {
+                scal(1.0 / pivot(i), &mut b[i * ldb..i * ldb + nrhs]);
+                for j in i + 1..n {
+                    update(b, j, i, -a[i * lda + j]);
+                }
+            }
+        },
+    }
+}
+
+// Row dst of the row-major matrix b mutably, alongside a different row src.
+fn two_rows(b: &mut [f64], ld: usize, len: usize, dst: usize, src: usize) -> (&mut [f64], &[f64])
+{
+    if dst < src
+    {
+        let (lo, hi) = b.split_at_mut(src * ld);
+        (&mut lo[dst * ld..dst * ld + len], &hi[..len])
+    }
+    else
+    {
+        let (lo, hi) = b.split_at_mut(dst * ld);
+        (&mut hi[..len], &lo[src * ld..src * ld + len])
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn abs_max(x: &[f64]) -> f64
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+
+    let mut rr = D512_ZERO;
+    let mut rr1 = D512_ZERO;
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+
+    while i + 2 * VSZ <= nn
+    {
+        rr = _mm512_max_pd(rr, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i))));
+        rr1 = _mm512_max_pd(rr1, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i + VSZ))));
+        i += 2 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        rr = _mm512_max_pd(rr, _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i))));
+        i += VSZ;
+    }
+
+    let mut r = _mm512_reduce_max_pd(_mm512_max_pd(rr, rr1));
+
+    for xi in &x[i..] {
+        r = r.max(xi.abs());
+    }
+
+    r
+}
+
+// Each lane keeps the best |x| it has seen and where. Strict comparisons keep the earliest index within a
+// lane, and ties between lanes go to the smallest index.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn abs_extreme_index<const CMP: i32>(x: &[f64]) -> Option<usize>
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+
+    if nn == 0
+    {
+        return None;
+    }
+
+    // Start from a value that every non-NaN |x| beats (or, for iamin, ties), so a leading NaN is skipped too.
+    let better = |v: f64, best: f64| if CMP == _CMP_GT_OQ { v > best } else { v < best };
+    let mut best = if CMP == _CMP_GT_OQ { -1.0 } else { f64::INFINITY };
+    let mut besti = 0;
+    let mut i: usize = 0;
+
+    if nn >= VSZ
+    {
+        let xptr = x.as_ptr();
+        let mut bb = _mm512_set1_pd(best);
+        let mut bi = _mm512_setzero_si512();
+        let mut ii = _mm512_set_epi64(7, 6, 5, 4, 3, 2, 1, 0);
+        let step = _mm512_set1_epi64(VSZ as i64);
+
+        while i + VSZ <= nn
+        {
+            let xx = _mm512_abs_pd(_mm512_loadu_pd(xptr.add(i)));
+            let mask = _mm512_cmp_pd_mask::<CMP>(xx, bb);
+            bb = _mm512_mask_blend_pd(mask, bb, xx);
+            bi = _mm512_mask_blend_epi64(mask, bi, ii);
+            ii = _mm512_add_epi64(ii, step);
+            i += VSZ;
+        }
+
+        let lanes: [f64; 8] = std::mem::transmute(bb);
+        let idx: [i64; 8] = std::mem::transmute(bi);
+
+        for l in 0..VSZ {
+            if better(lanes[l], best) || (lanes[l] == best && (idx[l] as usize) < besti)
+            {
+                best = lanes[l];
+                besti = idx[l] as usize;
+            }
+        }
+    }
+
+    for (ii, xi) in x.iter().enumerate().skip(i) {
+        if better(xi.abs(), best)
+        {
+            best = xi.abs();
+            besti = ii;
+        }
+    }
+
+    Some(besti)
+}
+
 
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const TWO_54: f64 = 18014398509481984.0;
 
-const D512_ZERO: __m512d = m64x8_constant!(0.0);
\ No newline at end of file
+const GEMM_MR: usize = 8;
+const GEMM_NR: usize = 16;
+const GEMM_KC: usize = 256;
+const GEMM_MC: usize = 128;
+const GEMM_NC: usize = 2048;
\ No newline at end of file
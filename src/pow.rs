@@ -0,0 +1,279 @@
+use std::arch::x86_64::*;
+use super::*;
+
+
+unroll_fn_n!(pow, pow_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(powx, powx_intr, [x] [b: f64] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(pown, pown_intr, [x] [n: i32] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_pow_pd(x: __m512d, b: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    pow_intr(&x, &b, &mut y);
+    y
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_powx_pd(x: __m512d, b: f64) -> __m512d
+{
+    let mut y = D512_ZERO;
+    powx_intr(&x, b, &mut y);
+    y
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_pown_pd(x: __m512d, n: i32) -> __m512d
+{
+    let mut y = D512_ZERO;
+    pown_intr(&x, n, &mut y);
+    y
+}
+
+/// AVX-512 implementation of x^b, with the special cases of IEEE 754 pow().
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn pow_intr(x: &__m512d, b: &__m512d, y: &mut __m512d)
+{
+    // x^b = exp(b * ln|x|). Doing this in plain double precision loses roughly log2(|b * ln|x||) bits of
+    // precision, so ln|x| is kept as a double-double and the product is formed exactly before exponentiating.
+    let ax = _mm512_abs_pd(*x);
+    let (lhi, llo) = ln_dd(ax);
+
+    let th = _mm512_mul_pd(*b, lhi);
+    let mut tl = _mm512_fmadd_pd(*b, llo, _mm512_fmsub_pd(*b, lhi, th));
+    tl = _mm512_maskz_mov_pd(_mm512_cmp_pd_mask(_mm512_abs_pd(th), D512_EXP_HIGH, _CMP_LT_OQ), tl);
+
+    let mut yy = exp_dd(th, tl);
+
+    // b is an odd integer if it is an integer and b/2 is not. Every double bigger than 2^53 is even.
+    let half_b = _mm512_mul_pd(*b, D512_HALF);
+    let b_int_mask = _mm512_cmpeq_pd_mask(_mm512_roundscale_pd(*b, _MM_FROUND_TO_ZERO), *b);
+    let b_odd_mask = b_int_mask & _mm512_cmpneq_pd_mask(_mm512_roundscale_pd(half_b, _MM_FROUND_TO_ZERO), half_b);
+    let b_neg_mask = _mm512_cmplt_pd_mask(*b, D512_ZERO);
+    let b_inf_mask = _mm512_cmpeq_pd_mask(_mm512_abs_pd(*b), D512_POSITIVE_INFINITY);
+
+    // x = +-0 and x = +-inf. 0^b and inf^(-b) are 0 when b > 0, and infinite when b < 0.
+    let zero_mask = _mm512_cmpeq_pd_mask(ax, D512_ZERO);
+    let xinf_mask = _mm512_cmpeq_pd_mask(ax, D512_POSITIVE_INFINITY);
+    let to_inf_mask = (zero_mask & b_neg_mask) | (xinf_mask & !b_neg_mask);
+    yy = _mm512_mask_blend_pd(zero_mask | xinf_mask, yy, _mm512_maskz_mov_pd(to_inf_mask, D512_POSITIVE_INFINITY));
+
+    // b = +-inf. The result is 0 or inf depending on whether |x| is on the same side of 1 as b is of 0.
+    let ax_lt_one_mask = _mm512_cmplt_pd_mask(ax, D512_ONE);
+    let ax_gt_one_mask = _mm512_cmp_pd_mask(ax, D512_ONE, _CMP_GT_OQ);
+    let binf_to_inf_mask = (ax_lt_one_mask & b_neg_mask) | (ax_gt_one_mask & !b_neg_mask);
+    yy = _mm512_mask_blend_pd(b_inf_mask, yy, _mm512_maskz_mov_pd(binf_to_inf_mask, D512_POSITIVE_INFINITY));
+    yy = _mm512_mask_blend_pd(b_inf_mask & _mm512_cmpeq_pd_mask(ax, D512_ONE), yy, D512_ONE);
+
+    // A negative x to an odd power flips the sign. A finite negative x to a finite non-integer power is NaN.
+    let x_signed_mask = _mm512_test_epi64_mask(_mm512_castpd_si512(*x), I512_SIGN_BIT);
+    yy = _mm512_mask_mul_pd(yy, x_signed_mask & b_odd_mask, yy, D512_NEGONE);
+    let x_finite_neg_mask = _mm512_cmplt_pd_mask(*x, D512_ZERO) & !xinf_mask;
+    yy = _mm512_mask_blend_pd(x_finite_neg_mask & !b_int_mask & !b_inf_mask, yy, D512_NAN);
+
+    // NaNs propagate, except that x^0 = 1 and 1^b = 1 for any x and b.
+    yy = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(*x, *b), yy, D512_NAN);
+    *y = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*b, D512_ZERO) | _mm512_cmpeq_pd_mask(*x, D512_ONE), yy, D512_ONE);
+}
+
+/// AVX-512 implementation of x^b for a scalar b. This is MKL's Powx.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn powx_intr(x: &__m512d, b: f64, y: &mut __m512d)
+{
+    pow_intr(x, &_mm512_set1_pd(b), y);
+}
+
+/// AVX-512 implementation of x^n for an integer n by repeated squaring. Every squaring doubles the relative
+/// error of the base, so for very large |n| pow_intr is the more accurate choice.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn pown_intr(x: &__m512d, n: i32, y: &mut __m512d)
+{
+    // The powers are carried as a mantissa in [1, 2) and a separate exponent, so no intermediate product
+    // overflows or underflows on the way to a representable result, and scalef rounds only once at the end,
+    // also into the subnormals. Zeros and infinities need no special casing: getexp gives them an exponent
+    // of -inf and +inf, which carries through the sums and the final scalef.
+    let mut k = n.unsigned_abs();
+    let mut base = _mm512_getmant_pd(*x, _MM_MANT_NORM_1_2, _MM_MANT_SIGN_SRC);
+    let mut base_e = _mm512_getexp_pd(*x);
+    let mut yy = D512_ONE;
+    let mut yy_e = D512_ZERO;
+
+    while k != 0
+    {
+        if k & 1 == 1
+        {
+            (yy, yy_e) = mul_split(yy, yy_e, base, base_e);
+        }
+        k >>= 1;
+        if k != 0
+        {
+            (base, base_e) = mul_split(base, base_e, base, base_e);
+        }
+    }
+
+    if n < 0
+    {
+        yy = _mm512_div_pd(D512_ONE, yy);
+        yy_e = _mm512_sub_pd(D512_ZERO, yy_e);
+    }
+
+    *y = _mm512_scalef_pd(yy, yy_e);
+}
+
+/// (a*2^ae)*(b*2^be) split again into a mantissa in [1, 2) and an exponent.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn mul_split(a: __m512d, ae: __m512d, b: __m512d, be: __m512d) -> (__m512d, __m512d)
+{
+    let p = _mm512_mul_pd(a, b);
+    let e = _mm512_add_pd(_mm512_add_pd(ae, be), _mm512_getexp_pd(p));
+    (_mm512_getmant_pd(p, _MM_MANT_NORM_1_2, _MM_MANT_SIGN_SRC), e)
+}
+
+/// ln(x) for x > 0 as a double-double hi + lo.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn ln_dd(x: __m512d) -> (__m512d, __m512d)
+{
+    // x = m*2^e where m is in [sqrt(1/2), sqrt(2)), then ln(m) = 2atanh(s) with s = (m-1)/(m+1), and
+    // |s| < 0.172. The leading 2s term is kept in double-double; the rest of the series only has to be
+    // good to a double.
+    let mut m = _mm512_getmant_pd(x, _MM_MANT_NORM_1_2, _MM_MANT_SIGN_ZERO);
+    let mut e = _mm512_getexp_pd(x);
+    let big_mask = _mm512_cmp_pd_mask(m, D512_SQRT2, _CMP_GT_OQ);
+    m = _mm512_mask_mul_pd(m, big_mask, m, D512_HALF);
+    e = _mm512_mask_add_pd(e, big_mask, e, D512_ONE);
+
+    let num = _mm512_sub_pd(m, D512_ONE);
+    let (den, den_lo) = two_sum(m, D512_ONE);
+    let s = _mm512_div_pd(num, den);
+    let mut r = _mm512_fnmadd_pd(s, den, num);
+    r = _mm512_fnmadd_pd(s, den_lo, r);
+    let s_lo = _mm512_div_pd(r, den);
+
+    // (2/3)s^3 is up to 1% of ln(m), so it is also carried as a double-double. Everything past it is
+    // small enough to be evaluated in plain double precision.
+    let ssq = _mm512_mul_pd(s, s);
+    let ssq_lo = _mm512_fmadd_pd(_mm512_add_pd(s, s), s_lo, _mm512_fmsub_pd(s, s, ssq));
+    let s3 = _mm512_mul_pd(ssq, s);
+    let s3_lo = _mm512_fmadd_pd(ssq, s_lo, _mm512_fmadd_pd(ssq_lo, s, _mm512_fmsub_pd(ssq, s, s3)));
+    let t3 = _mm512_mul_pd(s3, D512_L3);
+    let t3_lo = _mm512_fmadd_pd(s3_lo, D512_L3, _mm512_fmadd_pd(s3, D512_L3_LO, _mm512_fmsub_pd(s3, D512_L3, t3)));
+
+    let mut p = _mm512_fmadd_pd(D512_L23, ssq, D512_L21);
+    p = _mm512_fmadd_pd(p, ssq, D512_L19);
+    p = _mm512_fmadd_pd(p, ssq, D512_L17);
+    p = _mm512_fmadd_pd(p, ssq, D512_L15);
+    p = _mm512_fmadd_pd(p, ssq, D512_L13);
+    p = _mm512_fmadd_pd(p, ssq, D512_L11);
+    p = _mm512_fmadd_pd(p, ssq, D512_L9);
+    p = _mm512_fmadd_pd(p, ssq, D512_L7);
+    p = _mm512_fmadd_pd(p, ssq, D512_L5);
+    p = _mm512_mul_pd(_mm512_mul_pd(s3, ssq), p);
+
+    let (hi, lo0) = two_sum(_mm512_mul_pd(e, D512_LN2_HI), _mm512_add_pd(s, s));
+    let (hi, lo1) = two_sum(hi, t3);
+    let mut lo = _mm512_fmadd_pd(e, D512_LN2_LO, _mm512_fmadd_pd(D512_TWO, s_lo, p));
+    lo = _mm512_add_pd(_mm512_add_pd(lo0, lo1), _mm512_add_pd(lo, t3_lo));
+
+    fast_two_sum(hi, lo)
+}
+
+/// exp(hi + lo), where lo is a correction term much smaller than hi.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn exp_dd(hi: __m512d, lo: __m512d) -> __m512d
+{
+    // Bound the argument so that n below stays small; everything outside of this over- or underflows anyway.
+    let xx = _mm512_max_pd(_mm512_min_pd(hi, D512_EXP_HIGH), D512_EXP_LOW);
+
+    // exp(x) = 2^n * exp(r) with |r| <= ln(2)/2.
+    let n = _mm512_roundscale_pd(_mm512_mul_pd(xx, D512_LOG2E), _MM_FROUND_TO_NEAREST_INT);
+    let mut r = _mm512_fnmadd_pd(n, D512_LN2_HI, xx);
+    r = _mm512_fnmadd_pd(n, D512_LN2_LO, r);
+    r = _mm512_add_pd(r, lo);
+
+    let mut y = _mm512_fmadd_pd(D512_E14, r, D512_E13);
+    y = _mm512_fmadd_pd(y, r, D512_E12);
+    y = _mm512_fmadd_pd(y, r, D512_E11);
+    y = _mm512_fmadd_pd(y, r, D512_E10);
+    y = _mm512_fmadd_pd(y, r, D512_E9);
+    y = _mm512_fmadd_pd(y, r, D512_E8);
+    y = _mm512_fmadd_pd(y, r, D512_E7);
+    y = _mm512_fmadd_pd(y, r, D512_E6);
+    y = _mm512_fmadd_pd(y, r, D512_E5);
+    y = _mm512_fmadd_pd(y, r, D512_E4);
+    y = _mm512_fmadd_pd(y, r, D512_E3);
+    y = _mm512_fmadd_pd(y, r, D512_HALF);
+    y = _mm512_mul_pd(_mm512_mul_pd(y, r), r);
+    y = _mm512_add_pd(D512_ONE, _mm512_add_pd(r, y));
+
+    _mm512_scalef_pd(y, n)
+}
+
+/// Error-free a + b = s + e.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn two_sum(a: __m512d, b: __m512d) -> (__m512d, __m512d)
+{
+    let s = _mm512_add_pd(a, b);
+    let bb = _mm512_sub_pd(s, a);
+    let e = _mm512_add_pd(_mm512_sub_pd(a, _mm512_sub_pd(s, bb)), _mm512_sub_pd(b, bb));
+    (s, e)
+}
+
+/// Error-free a + b = s + e, given |a| >= |b|.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn fast_two_sum(a: __m512d, b: __m512d) -> (__m512d, __m512d)
+{
+    let s = _mm512_add_pd(a, b);
+    let e = _mm512_sub_pd(b, _mm512_sub_pd(s, a));
+    (s, e)
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_TWO: __m512d = m64x8_constant!(2.0);
+const D512_HALF: __m512d = m64x8_constant!(0.5);
+const D512_NEGONE: __m512d = m64x8_constant!(-1.0);
+const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_SQRT2: __m512d = m64x8_constant!(std::f64::consts::SQRT_2);
+const D512_LOG2E: __m512d = m64x8_constant!(std::f64::consts::LOG2_E);
+const D512_LN2_HI: __m512d = m64x8_constant!(6.93147180369123816490e-01);
+const D512_LN2_LO: __m512d = m64x8_constant!(1.90821492927058770002e-10);
+const D512_EXP_HIGH: __m512d = m64x8_constant!(710.0);
+const D512_EXP_LOW: __m512d = m64x8_constant!(-746.0);
+const I512_SIGN_BIT: __m512i = m64x8_constant!(i64::MIN);
+const D512_L3: __m512d = m64x8_constant!(2.0 / 3.0);
+const D512_L3_LO: __m512d = m64x8_constant!(3.700743415417188e-17);
+const D512_L5: __m512d = m64x8_constant!(2.0 / 5.0);
+const D512_L7: __m512d = m64x8_constant!(2.0 / 7.0);
+const D512_L9: __m512d = m64x8_constant!(2.0 / 9.0);
+const D512_L11: __m512d = m64x8_constant!(2.0 / 11.0);
+const D512_L13: __m512d = m64x8_constant!(2.0 / 13.0);
+const D512_L15: __m512d = m64x8_constant!(2.0 / 15.0);
+const D512_L17: __m512d = m64x8_constant!(2.0 / 17.0);
+const D512_L19: __m512d = m64x8_constant!(2.0 / 19.0);
+const D512_L21: __m512d = m64x8_constant!(2.0 / 21.0);
+const D512_L23: __m512d = m64x8_constant!(2.0 / 23.0);
+const D512_E3: __m512d = m64x8_constant!(1.0 / 6.0);
+const D512_E4: __m512d = m64x8_constant!(1.0 / 24.0);
+const D512_E5: __m512d = m64x8_constant!(1.0 / 120.0);
+const D512_E6: __m512d = m64x8_constant!(1.0 / 720.0);
+const D512_E7: __m512d = m64x8_constant!(1.0 / 5040.0);
+const D512_E8: __m512d = m64x8_constant!(1.0 / 40320.0);
+const D512_E9: __m512d = m64x8_constant!(1.0 / 362880.0);
+const D512_E10: __m512d = m64x8_constant!(1.0 / 3628800.0);
+const D512_E11: __m512d = m64x8_constant!(1.0 / 39916800.0);
+const D512_E12: __m512d = m64x8_constant!(1.0 / 479001600.0);
+const D512_E13: __m512d = m64x8_constant!(1.0 / 6227020800.0);
+const D512_E14: __m512d = m64x8_constant!(1.0 / 87178291200.0);
@@ -3,10 +3,210 @@ use super::*;
 
 
 unroll_fn!(sqrt, sqrt_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(cbrt, cbrt_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(invsqrt, invsqrt_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(invsqrt_fast, invsqrt_fast_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(inv, inv_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(inv_fast, inv_fast_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(hypot, hypot_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
 
 
 #[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
 pub unsafe fn sqrt_intr(x: &__m512d, y: &mut __m512d)
 {
     *y = _mm512_sqrt_pd(*x);
-}
\ No newline at end of file
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_cbrt_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    cbrt_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_invsqrt_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    invsqrt_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_inv_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    inv_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_hypot_pd(x0: __m512d, x1: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    hypot_intr(&x0, &x1, &mut y);
+    y
+}
+
+/// AVX-512 implementation of the cube root.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn cbrt_intr(x: &__m512d, y: &mut __m512d)
+{
+    // |x| = m*2^(3q + r) with m in [1, 2) and r in {0, 1, 2}, so cbrt(|x|) = cbrt(m*2^r) * 2^q. A polynomial
+    // gets cbrt(m) to ~1e-4, then one Halley and one Newton step finish cbrt(m*2^r) off.
+    let ax = _mm512_abs_pd(*x);
+    let m = _mm512_getmant_pd(ax, _MM_MANT_NORM_1_2, _MM_MANT_SIGN_ZERO);
+    let e = _mm512_getexp_pd(ax);
+    let q = _mm512_roundscale_pd(_mm512_fmadd_pd(e, D512_ONE_THIRD, D512_ONE_SIXTH), _MM_FROUND_TO_NEG_INF);
+    let r = _mm512_fnmadd_pd(q, D512_THREE, e);
+    let a = _mm512_scalef_pd(m, r);
+
+    let mut g = _mm512_fmadd_pd(D512_CB3, m, D512_CB2);
+    g = _mm512_fmadd_pd(g, m, D512_CB1);
+    g = _mm512_fmadd_pd(g, m, D512_CB0);
+    g = _mm512_mask_mul_pd(g, _mm512_cmpeq_pd_mask(r, D512_ONE), g, D512_CBRT2);
+    g = _mm512_mask_mul_pd(g, _mm512_cmpeq_pd_mask(r, D512_TWO), g, D512_CBRT4);
+
+    // Halley: g = g(g^3 + 2a)/(2g^3 + a)
+    let g3 = _mm512_mul_pd(_mm512_mul_pd(g, g), g);
+    g = _mm512_mul_pd(g, _mm512_div_pd(_mm512_fmadd_pd(D512_TWO, a, g3), _mm512_fmadd_pd(D512_TWO, g3, a)));
+
+    // Newton: g = g - (g^3 - a)/(3g^2)
+    let g2 = _mm512_mul_pd(g, g);
+    g = _mm512_sub_pd(g, _mm512_div_pd(_mm512_fmsub_pd(g2, g, a), _mm512_mul_pd(D512_THREE, g2)));
+
+    g = _mm512_scalef_pd(g, q);
+
+    // 0, inf and NaN are their own cube roots.
+    g = _mm512_mask_blend_pd(_mm512_cmp_pd_mask(ax, D512_ZERO, _CMP_EQ_OQ) | _mm512_cmp_pd_mask(ax, D512_POSITIVE_INFINITY, _CMP_EQ_UQ), g, ax);
+    *y = _mm512_or_pd(g, _mm512_and_pd(*x, D512_NEGATIVE_ZERO));
+}
+
+/// AVX-512 implementation of 1/sqrt(x). This uses the 14 bit hardware estimate and two Newton steps to get to
+/// full precision.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn invsqrt_intr(x: &__m512d, y: &mut __m512d)
+{
+    let y0 = _mm512_rsqrt14_pd(*x);
+    let mut yy = invsqrt_newton(x, &y0);
+    yy = invsqrt_newton(x, &yy);
+    *y = invsqrt_special(x, &y0, &yy);
+}
+
+/// AVX-512 implementation of 1/sqrt(x) to roughly single precision (~1e-8). This uses the 14 bit hardware
+/// estimate and one Newton step.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn invsqrt_fast_intr(x: &__m512d, y: &mut __m512d)
+{
+    let y0 = _mm512_rsqrt14_pd(*x);
+    let yy = invsqrt_newton(x, &y0);
+    *y = invsqrt_special(x, &y0, &yy);
+}
+
+/// AVX-512 implementation of 1/x. This uses the 14 bit hardware estimate and two Newton steps to get to
+/// full precision.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn inv_intr(x: &__m512d, y: &mut __m512d)
+{
+    // 1/x = 1/m * 2^-e, which keeps the estimate from overflowing for tiny x.
+    let m = _mm512_getmant_pd(*x, _MM_MANT_NORM_1_2, _MM_MANT_SIGN_SRC);
+    let e = _mm512_getexp_pd(*x);
+    let y0 = _mm512_rcp14_pd(m);
+    let mut yy = inv_newton(&m, &y0);
+    yy = inv_newton(&m, &yy);
+    yy = _mm512_scalef_pd(yy, _mm512_sub_pd(D512_ZERO, e));
+    *y = inv_special(x, &_mm512_rcp14_pd(*x), &yy);
+}
+
+/// AVX-512 implementation of 1/x to roughly single precision (~1e-8). This uses the 14 bit hardware estimate
+/// and one Newton step.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn inv_fast_intr(x: &__m512d, y: &mut __m512d)
+{
+    let m = _mm512_getmant_pd(*x, _MM_MANT_NORM_1_2, _MM_MANT_SIGN_SRC);
+    let e = _mm512_getexp_pd(*x);
+    let y0 = _mm512_rcp14_pd(m);
+    let mut yy = inv_newton(&m, &y0);
+    yy = _mm512_scalef_pd(yy, _mm512_sub_pd(D512_ZERO, e));
+    *y = inv_special(x, &_mm512_rcp14_pd(*x), &yy);
+}
+
+/// AVX-512 implementation of sqrt(x0^2 + x1^2) that doesn't overflow or underflow in the intermediate steps.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn hypot_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    let a0 = _mm512_abs_pd(*x0);
+    let a1 = _mm512_abs_pd(*x1);
+    let big = _mm512_max_pd(a0, a1);
+    let small = _mm512_min_pd(a0, a1);
+
+    // Scale both by the exponent of the bigger one so that the squares are in [1, 4) and [0, 4).
+    let e = _mm512_getexp_pd(big);
+    let b = _mm512_scalef_pd(big, _mm512_sub_pd(D512_ZERO, e));
+    let s = _mm512_scalef_pd(small, _mm512_sub_pd(D512_ZERO, e));
+    let mut yy = _mm512_scalef_pd(_mm512_sqrt_pd(_mm512_fmadd_pd(b, b, _mm512_mul_pd(s, s))), e);
+
+    // hypot(0, 0) = 0, NaNs propagate, and hypot(+-inf, NaN) = inf.
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(big, D512_ZERO), yy, D512_ZERO);
+    yy = _mm512_mask_blend_pd(_mm512_cmpunord_pd_mask(a0, a1), yy, D512_NAN);
+    let inf_mask = _mm512_cmpeq_pd_mask(a0, D512_POSITIVE_INFINITY) | _mm512_cmpeq_pd_mask(a1, D512_POSITIVE_INFINITY);
+    *y = _mm512_mask_blend_pd(inf_mask, yy, D512_POSITIVE_INFINITY);
+}
+
+/// One Newton step of y = 1/sqrt(x): y + y(1 - xy^2)/2
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn invsqrt_newton(x: &__m512d, y: &__m512d) -> __m512d
+{
+    let e = _mm512_fnmadd_pd(_mm512_mul_pd(*x, *y), *y, D512_ONE);
+    _mm512_fmadd_pd(_mm512_mul_pd(*y, D512_HALF), e, *y)
+}
+
+/// The Newton steps turn 0*inf into NaN for x = 0 and x = inf, where the estimate is already exact. Negative
+/// x have to be NaN.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn invsqrt_special(x: &__m512d, y0: &__m512d, y: &__m512d) -> __m512d
+{
+    let exact_mask = _mm512_cmpeq_pd_mask(*x, D512_ZERO) | _mm512_cmpeq_pd_mask(*x, D512_POSITIVE_INFINITY);
+    let yy = _mm512_mask_blend_pd(exact_mask, *y, *y0);
+    _mm512_mask_blend_pd(_mm512_cmplt_pd_mask(*x, D512_ZERO), yy, D512_NAN)
+}
+
+/// One Newton step of y = 1/x: y + y(1 - xy)
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn inv_newton(x: &__m512d, y: &__m512d) -> __m512d
+{
+    let e = _mm512_fnmadd_pd(*x, *y, D512_ONE);
+    _mm512_fmadd_pd(*y, e, *y)
+}
+
+/// The Newton steps turn 0*inf into NaN for x = +-0 and x = +-inf, where the estimate is already exact.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn inv_special(x: &__m512d, y0: &__m512d, y: &__m512d) -> __m512d
+{
+    let ax = _mm512_abs_pd(*x);
+    let exact_mask = _mm512_cmpeq_pd_mask(ax, D512_ZERO) | _mm512_cmpeq_pd_mask(ax, D512_POSITIVE_INFINITY);
+    _mm512_mask_blend_pd(exact_mask, *y, *y0)
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_TWO: __m512d = m64x8_constant!(2.0);
+const D512_THREE: __m512d = m64x8_constant!(3.0);
+const D512_HALF: __m512d = m64x8_constant!(0.5);
+const D512_ONE_THIRD: __m512d = m64x8_constant!(1.0 / 3.0);
+const D512_ONE_SIXTH: __m512d = m64x8_constant!(1.0 / 6.0);
+const D512_NEGATIVE_ZERO: __m512d = m64x8_constant!(-0.0);
+const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_CBRT2: __m512d = m64x8_constant!(1.2599210498948731647672106);
+const D512_CBRT4: __m512d = m64x8_constant!(1.5874010519681994747517056);
+const D512_CB0: __m512d = m64x8_constant!(0.5557909602691388);
+const D512_CB1: __m512d = m64x8_constant!(0.5808263911380952);
+const D512_CB2: __m512d = m64x8_constant!(-0.1586624600531909);
+const D512_CB3: __m512d = m64x8_constant!(0.022148699208245196);
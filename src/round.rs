@@ -0,0 +1,237 @@
+use std::arch::x86_64::*;
+use super::*;
+
+
+unroll_fn!(floor, floor_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(ceil, ceil_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(trunc, trunc_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(round, round_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(rint, rint_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(modf, modf_intr, [x] -> [ip, fp], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(fmod, fmod_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(remainder, remainder_intr, [x0, x1] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(frexp, frexp_intr, [x] -> [m, e], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn_n!(ldexp, ldexp_intr, [x, e] -> [y], _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_floor_pd(x: __m512d) -> __m512d
+{
+    _mm512_roundscale_pd(x, _MM_FROUND_TO_NEG_INF)
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_ceil_pd(x: __m512d) -> __m512d
+{
+    _mm512_roundscale_pd(x, _MM_FROUND_TO_POS_INF)
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_trunc_pd(x: __m512d) -> __m512d
+{
+    _mm512_roundscale_pd(x, _MM_FROUND_TO_ZERO)
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_round_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    round_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_rint_pd(x: __m512d) -> __m512d
+{
+    _mm512_roundscale_pd(x, _MM_FROUND_TO_NEAREST_INT)
+}
+
+/// Returns (integer part, fractional part).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_modf_pd(x: __m512d) -> (__m512d, __m512d)
+{
+    let mut ip = D512_ZERO;
+    let mut fp = D512_ZERO;
+    modf_intr(&x, &mut ip, &mut fp);
+    (ip, fp)
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_fmod_pd(x0: __m512d, x1: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    fmod_intr(&x0, &x1, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_remainder_pd(x0: __m512d, x1: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    remainder_intr(&x0, &x1, &mut y);
+    y
+}
+
+/// Returns (mantissa, exponent).
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_frexp_pd(x: __m512d) -> (__m512d, __m512d)
+{
+    let mut m = D512_ZERO;
+    let mut e = D512_ZERO;
+    frexp_intr(&x, &mut m, &mut e);
+    (m, e)
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_ldexp_pd(x: __m512d, e: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    ldexp_intr(&x, &e, &mut y);
+    y
+}
+
+
+/// AVX-512 implementation of floor(x), the largest integer not above x.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn floor_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_roundscale_pd(*x, _MM_FROUND_TO_NEG_INF);
+}
+
+/// AVX-512 implementation of ceil(x), the smallest integer not below x.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn ceil_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_roundscale_pd(*x, _MM_FROUND_TO_POS_INF);
+}
+
+/// AVX-512 implementation of trunc(x), x rounded toward zero.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn trunc_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_roundscale_pd(*x, _MM_FROUND_TO_ZERO);
+}
+
+/// AVX-512 implementation of round(x), x rounded to the nearest integer with halves away from zero, like f64::round.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn round_intr(x: &__m512d, y: &mut __m512d)
+{
+    // x - trunc(x) is exact, so comparing it with 1/2 can't be fooled the way trunc(x + 1/2) can
+    let t = _mm512_roundscale_pd(*x, _MM_FROUND_TO_ZERO);
+    let up_mask = _mm512_cmp_pd_mask(_mm512_abs_pd(_mm512_sub_pd(*x, t)), D512_HALF, _CMP_GE_OQ);
+    let one = _mm512_or_pd(D512_ONE, _mm512_and_pd(*x, D512_NEGATIVE_ZERO));
+    *y = _mm512_mask_add_pd(t, up_mask, t, one);
+}
+
+/// AVX-512 implementation of rint(x), x rounded to the nearest integer with halves to even.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn rint_intr(x: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_roundscale_pd(*x, _MM_FROUND_TO_NEAREST_INT);
+}
+
+/// AVX-512 implementation of modf(x), which splits x into its integer part and its fractional part, both with the
+/// sign of x. The fractional part of an infinity is zero.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn modf_intr(x: &__m512d, ip: &mut __m512d, fp: &mut __m512d)
+{
+    *ip = _mm512_roundscale_pd(*x, _MM_FROUND_TO_ZERO);
+    let f = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(_mm512_abs_pd(*x), D512_POSITIVE_INFINITY), _mm512_sub_pd(*x, *ip), D512_ZERO);
+    *fp = _mm512_or_pd(f, _mm512_and_pd(*x, D512_NEGATIVE_ZERO));
+}
+
+/// AVX-512 implementation of fmod(x0, x1), x0 - n x1 for n = trunc(x0/x1). The result is exact, and has the sign of x0.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn fmod_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    let r = fmod_abs(_mm512_abs_pd(*x0), _mm512_abs_pd(*x1));
+    *y = fmod_specials(*x0, *x1, _mm512_or_pd(r, _mm512_and_pd(*x0, D512_NEGATIVE_ZERO)));
+}
+
+/// AVX-512 implementation of the IEEE remainder(x0, x1), x0 - n x1 for n the nearest integer to x0/x1 with halves to
+/// even. The result is exact, and is no bigger than |x1|/2.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn remainder_intr(x0: &__m512d, x1: &__m512d, y: &mut __m512d)
+{
+    // |x0| mod 2|x1| tells whether n is odd. Where 2|x1| overflows it is above |x0|, and fmod_abs passes |x0| through.
+    let a1 = _mm512_abs_pd(*x1);
+    let mut r = fmod_abs(_mm512_abs_pd(*x0), _mm512_add_pd(a1, a1));
+    let odd_mask = _mm512_cmp_pd_mask(r, a1, _CMP_GE_OQ);
+    r = _mm512_mask_sub_pd(r, odd_mask, r, a1);
+
+    // 2r is exact where r/2 might not be
+    let r2 = _mm512_add_pd(r, r);
+    let down_mask = _mm512_cmp_pd_mask(r2, a1, _CMP_GT_OQ) | (odd_mask & _mm512_cmpeq_pd_mask(r2, a1));
+    r = _mm512_mask_sub_pd(r, down_mask, r, a1);
+
+    *y = fmod_specials(*x0, *x1, _mm512_xor_pd(r, _mm512_and_pd(*x0, D512_NEGATIVE_ZERO)));
+}
+
+/// AVX-512 implementation of frexp(x), which splits x into m 2^e with |m| in [1/2, 1). Zero, infinity and NaN come back
+/// as themselves with an exponent of 0.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn frexp_intr(x: &__m512d, m: &mut __m512d, e: &mut __m512d)
+{
+    let special_mask = _mm512_cmpeq_pd_mask(*x, D512_ZERO) | !finite(*x);
+    let mm = _mm512_mul_pd(_mm512_getmant_pd(*x, _MM_MANT_NORM_1_2, _MM_MANT_SIGN_SRC), D512_HALF);
+    let ee = _mm512_add_pd(_mm512_getexp_pd(*x), D512_ONE);
+    *m = _mm512_mask_blend_pd(special_mask, mm, *x);
+    *e = _mm512_mask_blend_pd(special_mask, ee, D512_ZERO);
+}
+
+/// AVX-512 implementation of ldexp(x, e), x 2^e without rounding the power of 2. A non-integer e is floored, which is
+/// what _mm512_scalef_pd does.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn ldexp_intr(x: &__m512d, e: &__m512d, y: &mut __m512d)
+{
+    *y = _mm512_scalef_pd(*x, *e);
+}
+
+/// a mod b for a, b >= 0, exactly. b 2^k is taken off a with k set so that the quotient stays below 2^53, which
+/// makes a - q b 2^k exact, and each pass takes up to 52 bits off the exponent gap.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn fmod_abs(a: __m512d, b: __m512d) -> __m512d
+{
+    let mut r = a;
+    let valid_mask = finite(a) & _mm512_cmp_pd_mask(b, D512_ZERO, _CMP_GT_OQ);
+    let eb = _mm512_getexp_pd(b);
+    loop
+    {
+        let mask = valid_mask & _mm512_cmp_pd_mask(r, b, _CMP_GE_OQ);
+        if mask == 0
+        {
+            break;
+        }
+
+        let k = _mm512_max_pd(_mm512_sub_pd(_mm512_sub_pd(_mm512_getexp_pd(r), eb), D512_FIFTY_TWO), D512_ZERO);
+        let bk = _mm512_scalef_pd(b, k);
+
+        // the rounded quotient is at most one too big, never too small, so the remainder is in (-bk, bk)
+        let q = _mm512_roundscale_pd(_mm512_div_pd(r, bk), _MM_FROUND_TO_ZERO);
+        let mut rr = _mm512_fnmadd_pd(q, bk, r);
+        rr = _mm512_mask_add_pd(rr, _mm512_cmplt_pd_mask(rr, D512_ZERO), rr, bk);
+        r = _mm512_mask_mov_pd(r, mask, rr);
+    }
+    r
+}
+
+/// fmod and remainder are NaN for an infinite or NaN x0, a zero or NaN x1, and x0 for an infinite x1.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn fmod_specials(x0: __m512d, x1: __m512d, y: __m512d) -> __m512d
+{
+    let inf_mask = _mm512_cmpeq_pd_mask(_mm512_abs_pd(x1), D512_POSITIVE_INFINITY);
+    let yy = _mm512_mask_blend_pd(inf_mask, y, x0);
+    let nan_mask = !finite(x0) | _mm512_cmpeq_pd_mask(x1, D512_ZERO) | _mm512_cmpunord_pd_mask(x1, x1);
+    _mm512_mask_blend_pd(nan_mask, yy, D512_NAN)
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_NEGATIVE_ZERO: __m512d = m64x8_constant!(-0.0);
+const D512_HALF: __m512d = m64x8_constant!(0.5);
+const D512_ONE: __m512d = m64x8_constant!(1.0);
+const D512_FIFTY_TWO: __m512d = m64x8_constant!(52.0);
+const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
@@ -0,0 +1,261 @@
+use std::arch::x86_64::*;
+use super::*;
+
+// All of the reductions here work in two or three passes over the input: find the maximum m, accumulate
+// sum(e^(x - m)) in registers, then (for softmax and log_softmax) write the output. Subtracting m keeps
+// every exponent <= 0, so nothing overflows and at least one term of the sum is exactly 1. softmax writes
+// e^(x - m) straight into y during the second pass and rescales it in place, so no temporary is needed.
+
+/// log(sum(e^x)). Returns -inf for an empty slice.
+pub fn logsumexp(x: &[f64]) -> f64
+{
+    unsafe{
+        let m = shift(max_of(x));
+        m + sum_exp(x, m).ln()
+    }
+}
+
+pub fn logsumexp_par(x: &[f64]) -> f64
+{
+    use rayon::prelude::*;
+    let chunk = par_chunk(x.len());
+    let m = shift(x.par_chunks(chunk).map(|c| unsafe{ max_of(c) }).reduce(|| f64::NEG_INFINITY, f64::max));
+    m + x.par_chunks(chunk).map(|c| unsafe{ sum_exp(c, m) }).sum::<f64>().ln()
+}
+
+/// y = e^x / sum(e^x).
+pub fn softmax(x: &[f64], y: &mut [f64])
+{
+    assert_eq!(x.len(), y.len());
+    unsafe{
+        let m = shift(max_of(x));
+        let s = store_exp(x, m, y);
+        scale(y, 1.0 / s);
+    }
+}
+
+pub fn softmax_par(x: &[f64], y: &mut [f64])
+{
+    use rayon::prelude::*;
+    assert_eq!(x.len(), y.len());
+    let chunk = par_chunk(x.len());
+    let m = shift(x.par_chunks(chunk).map(|c| unsafe{ max_of(c) }).reduce(|| f64::NEG_INFINITY, f64::max));
+    let s: f64 = x.par_chunks(chunk).zip(y.par_chunks_mut(chunk)).map(|(xc, yc)| unsafe{ store_exp(xc, m, yc) }).sum();
+    let r = 1.0 / s;
+    y.par_chunks_mut(chunk).for_each(|yc| unsafe{ scale(yc, r) });
+}
+
+/// y = x - logsumexp(x).
+pub fn log_softmax(x: &[f64], y: &mut [f64])
+{
+    assert_eq!(x.len(), y.len());
+    unsafe{
+        let m = shift(max_of(x));
+        let l = sum_exp(x, m).ln();
+        subtract(x, m, l, y);
+    }
+}
+
+pub fn log_softmax_par(x: &[f64], y: &mut [f64])
+{
+    use rayon::prelude::*;
+    assert_eq!(x.len(), y.len());
+    let chunk = par_chunk(x.len());
+    let m = shift(x.par_chunks(chunk).map(|c| unsafe{ max_of(c) }).reduce(|| f64::NEG_INFINITY, f64::max));
+    let l = x.par_chunks(chunk).map(|c| unsafe{ sum_exp(c, m) }).sum::<f64>().ln();
+    x.par_chunks(chunk).zip(y.par_chunks_mut(chunk)).for_each(|(xc, yc)| unsafe{ subtract(xc, m, l, yc) });
+}
+
+/// logsumexp of every row of the row-major matrix x with `cols` columns. y has one entry per row.
+pub fn logsumexp_rows(x: &[f64], cols: usize, y: &mut [f64])
+{
+    assert_rows(x.len(), cols, y.len() * cols);
+    x.chunks(cols).zip(y.iter_mut()).for_each(|(xr, yr)| *yr = logsumexp(xr));
+}
+
+pub fn logsumexp_rows_par(x: &[f64], cols: usize, y: &mut [f64])
+{
+    use rayon::prelude::*;
+    assert_rows(x.len(), cols, y.len() * cols);
+    x.par_chunks(cols).zip(y.par_iter_mut()).for_each(|(xr, yr)| *yr = logsumexp(xr));
+}
+
+/// softmax of every row of the row-major matrix x with `cols` columns.
+pub fn softmax_rows(x: &[f64], cols: usize, y: &mut [f64])
+{
+    assert_rows(x.len(), cols, y.len());
+    x.chunks(cols).zip(y.chunks_mut(cols)).for_each(|(xr, yr)| softmax(xr, yr));
+}
+
+pub fn softmax_rows_par(x: &[f64], cols: usize, y: &mut [f64])
+{
+    use rayon::prelude::*;
+    assert_rows(x.len(), cols, y.len());
+    x.par_chunks(cols).zip(y.par_chunks_mut(cols)).for_each(|(xr, yr)| softmax(xr, yr));
+}
+
+/// log_softmax of every row of the row-major matrix x with `cols` columns.
+pub fn log_softmax_rows(x: &[f64], cols: usize, y: &mut [f64])
+{
+    assert_rows(x.len(), cols, y.len());
+    x.chunks(cols).zip(y.chunks_mut(cols)).for_each(|(xr, yr)| log_softmax(xr, yr));
+}
+
+pub fn log_softmax_rows_par(x: &[f64], cols: usize, y: &mut [f64])
+{
+    use rayon::prelude::*;
+    assert_rows(x.len(), cols, y.len());
+    x.par_chunks(cols).zip(y.par_chunks_mut(cols)).for_each(|(xr, yr)| log_softmax(xr, yr));
+}
+
+fn assert_rows(n: usize, cols: usize, m: usize)
+{
+    assert!(cols > 0);
+    assert_eq!(n % cols, 0);
+    assert_eq!(n, m);
+}
+
+fn par_chunk(n: usize) -> usize
+{
+    (n / 32).max(1024)
+}
+
+// An infinite or NaN maximum can't be subtracted out, so fall back to no shift and let the infinities
+// and NaNs flow through the sum.
+fn shift(m: f64) -> f64
+{
+    if m.is_finite() { m } else { 0.0 }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn max_of(x: &[f64]) -> f64
+{
+    let n = x.len();
+    let xptr = x.as_ptr();
+    let mut mm = D512_NEGATIVE_INFINITY;
+    let mut mm1 = D512_NEGATIVE_INFINITY;
+    let mut i = 0;
+
+    while i + 16 <= n
+    {
+        mm = _mm512_max_pd(mm, _mm512_loadu_pd(xptr.add(i)));
+        mm1 = _mm512_max_pd(mm1, _mm512_loadu_pd(xptr.add(i + 8)));
+        i += 16;
+    }
+
+    if i + 8 <= n
+    {
+        mm = _mm512_max_pd(mm, _mm512_loadu_pd(xptr.add(i)));
+        i += 8;
+    }
+
+    if i < n
+    {
+        mm1 = _mm512_max_pd(mm1, _mm512_mask_loadu_pd(D512_NEGATIVE_INFINITY, tail_mask(n - i), xptr.add(i)));
+    }
+
+    _mm512_reduce_max_pd(_mm512_max_pd(mm, mm1))
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn sum_exp(x: &[f64], m: f64) -> f64
+{
+    let n = x.len();
+    let xptr = x.as_ptr();
+    let mm = _mm512_set1_pd(m);
+    let mut ss = D512_ZERO;
+    let mut ss1 = D512_ZERO;
+    let mut e = D512_ZERO;
+    let mut e1 = D512_ZERO;
+    let mut i = 0;
+
+    while i + 16 <= n
+    {
+        exp_intr(&_mm512_sub_pd(_mm512_loadu_pd(xptr.add(i)), mm), &mut e);
+        exp_intr(&_mm512_sub_pd(_mm512_loadu_pd(xptr.add(i + 8)), mm), &mut e1);
+        ss = _mm512_add_pd(ss, e);
+        ss1 = _mm512_add_pd(ss1, e1);
+        i += 16;
+    }
+
+    while i < n
+    {
+        let k = tail_mask(n - i);
+        exp_intr(&_mm512_sub_pd(_mm512_mask_loadu_pd(D512_NEGATIVE_INFINITY, k, xptr.add(i)), mm), &mut e);
+        ss = _mm512_add_pd(ss, _mm512_maskz_mov_pd(k, e));
+        i += 8;
+    }
+
+    _mm512_reduce_add_pd(_mm512_add_pd(ss, ss1))
+}
+
+/// Writes y = e^(x - m) and returns sum(y).
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn store_exp(x: &[f64], m: f64, y: &mut [f64]) -> f64
+{
+    let n = x.len();
+    let xptr = x.as_ptr();
+    let yptr = y.as_mut_ptr();
+    let mm = _mm512_set1_pd(m);
+    let mut ss = D512_ZERO;
+    let mut e = D512_ZERO;
+    let mut i = 0;
+
+    while i < n
+    {
+        let k = tail_mask(n - i);
+        exp_intr(&_mm512_sub_pd(_mm512_mask_loadu_pd(D512_NEGATIVE_INFINITY, k, xptr.add(i)), mm), &mut e);
+        e = _mm512_maskz_mov_pd(k, e);
+        _mm512_mask_storeu_pd(yptr.add(i), k, e);
+        ss = _mm512_add_pd(ss, e);
+        i += 8;
+    }
+
+    _mm512_reduce_add_pd(ss)
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn scale(y: &mut [f64], r: f64)
+{
+    let n = y.len();
+    let yptr = y.as_mut_ptr();
+    let rr = _mm512_set1_pd(r);
+    let mut i = 0;
+
+    while i < n
+    {
+        let k = tail_mask(n - i);
+        let yy = _mm512_maskz_loadu_pd(k, yptr.add(i));
+        _mm512_mask_storeu_pd(yptr.add(i), k, _mm512_mul_pd(yy, rr));
+        i += 8;
+    }
+}
+
+/// Writes y = (x - m) - l.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn subtract(x: &[f64], m: f64, l: f64, y: &mut [f64])
+{
+    let n = x.len();
+    let xptr = x.as_ptr();
+    let yptr = y.as_mut_ptr();
+    let mm = _mm512_set1_pd(m);
+    let ll = _mm512_set1_pd(l);
+    let mut i = 0;
+
+    while i < n
+    {
+        let k = tail_mask(n - i);
+        let xx = _mm512_maskz_loadu_pd(k, xptr.add(i));
+        _mm512_mask_storeu_pd(yptr.add(i), k, _mm512_sub_pd(_mm512_sub_pd(xx, mm), ll));
+        i += 8;
+    }
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
+const D512_NEGATIVE_INFINITY: __m512d = m64x8_constant!(f64::NEG_INFINITY);
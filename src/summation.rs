@@ -0,0 +1,382 @@
+use std::arch::x86_64::*;
+use super::*;
+
+/// sum(x) with a compensated (Kahan–Babuška) running error, so the result is as accurate as if it had been
+/// summed in twice the working precision and then rounded.
+#[inline]
+pub fn sum_kahan(x: &[f64]) -> f64
+{
+    unsafe{
+        sum_kahanu(x)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn sum_kahanu(x: &[f64]) -> f64
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+
+    let mut ss = D512_ZERO;
+    let mut cc = D512_ZERO;
+    let mut ss1 = D512_ZERO;
+    let mut cc1 = D512_ZERO;
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+
+    while i + 2 * VSZ <= nn
+    {
+        let (s, e) = two_sum(ss, _mm512_loadu_pd(xptr.add(i)));
+        let (s1, e1) = two_sum(ss1, _mm512_loadu_pd(xptr.add(i + VSZ)));
+        ss = s;
+        ss1 = s1;
+        cc = _mm512_add_pd(cc, e);
+        cc1 = _mm512_add_pd(cc1, e1);
+        i += 2 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        let (s, e) = two_sum(ss, _mm512_loadu_pd(xptr.add(i)));
+        ss = s;
+        cc = _mm512_add_pd(cc, e);
+        i += VSZ;
+    }
+
+    let (s, e) = two_sum(ss, ss1);
+    let cc = _mm512_add_pd(_mm512_add_pd(cc, cc1), e);
+    let (mut r, mut c) = sum_lanes(s, cc);
+
+    for xi in &x[i..] {
+        let (s, e) = two_sum_f64(r, *xi);
+        r = s;
+        c += e;
+    }
+
+    r + c
+}
+
+/// Ogita, Rump and Oishi's Dot2: every product is split exactly into p + e with an FMA and accumulated with
+/// TwoSum, so the result is as accurate as a dot product computed in twice the working precision.
+#[inline]
+pub fn dot_compensated(x: &[f64], y: &[f64]) -> f64
+{
+    unsafe{
+        dot_compensatedu(x, y)
+    }
+}
+
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn dot_compensatedu(x: &[f64], y: &[f64]) -> f64
+{
+    let nn = x.len();
+    const VSZ: usize = 8;
+    assert_eq!(nn, y.len());
+
+    let mut ss = D512_ZERO;
+    let mut cc = D512_ZERO;
+    let mut ss1 = D512_ZERO;
+    let mut cc1 = D512_ZERO;
+    let mut i: usize = 0;
+    let xptr = x.as_ptr();
+    let yptr = y.as_ptr();
+
+    while i + 2 * VSZ <= nn
+    {
+        let xx = _mm512_loadu_pd(xptr.add(i));
+        let yy = _mm512_loadu_pd(yptr.add(i));
+        let xx1 = _mm512_loadu_pd(xptr.add(i + VSZ));
+        let yy1 = _mm512_loadu_pd(yptr.add(i + VSZ));
+        let p = _mm512_mul_pd(xx, yy);
+        let p1 = _mm512_mul_pd(xx1, yy1);
+        let ep = _mm512_fmsub_pd(xx, yy, p);
+        let ep1 = _mm512_fmsub_pd(xx1, yy1, p1);
+        let (s, e) = two_sum(ss, p);
+        let (s1, e1) = two_sum(ss1, p1);
+        ss = s;
+        ss1 = s1;
+        cc = _mm512_add_pd(cc, _mm512_add_pd(e, ep));
+        cc1 = _mm512_add_pd(cc1, _mm512_add_pd(e1, ep1));
+        i += 2 * VSZ;
+    }
+
+    while i + VSZ <= nn
+    {
+        let xx = _mm512_loadu_pd(xptr.add(i));
+        let yy = _mm512_loadu_pd(yptr.add(i));
+        let p = _mm512_mul_pd(xx, yy);
+        let (s, e) = two_sum(ss, p);
+        ss = s;
+        cc = _mm512_add_pd(cc, _mm512_add_pd(e, _mm512_fmsub_pd(xx, yy, p)));
+        i += VSZ;
+    }
+
+    let (s, e) = two_sum(ss, ss1);
+    let cc = _mm512_add_pd(_mm512_add_pd(cc, cc1), e);
+    let (mut r, mut c) = sum_lanes(s, cc);
+
+    for (xi, yi) in x[i..].iter().zip(&y[i..]) {
+        let p = xi * yi;
+        let (s, e) = two_sum_f64(r, p);
+        r = s;
+        c += e + xi.mul_add(*yi, -p);
+    }
+
+    r + c
+}
+
+/// The dot product rounded once, to nearest, from its exact value. Since the exact value doesn't depend on
+/// the order of the terms, neither does the result: it's bit-identical to dot_reproducible_par for any
+/// chunking.
+pub fn dot_reproducible(x: &[f64], y: &[f64]) -> f64
+{
+    assert_eq!(x.len(), y.len());
+    let mut acc = Accumulator::new();
+    acc.add_dot(x, y);
+    acc.round()
+}
+
+/// dot_reproducible split across 32 rayon tasks.
+pub fn dot_reproducible_par(x: &[f64], y: &[f64]) -> f64
+{
+    dot_reproducible_par_chunked(x, y, 32)
+}
+
+/// dot_reproducible split into `chunks` pieces that rayon runs in parallel.
+pub fn dot_reproducible_par_chunked(x: &[f64], y: &[f64], chunks: usize) -> f64
+{
+    use rayon::prelude::*;
+    assert_eq!(x.len(), y.len());
+    assert!(chunks > 0);
+
+    let chunk = x.len().div_ceil(chunks).max(1);
+
+    x.par_chunks(chunk).zip(y.par_chunks(chunk)).map(|(xc, yc)| {
+        let mut acc = Accumulator::new();
+        acc.add_dot(xc, yc);
+        acc
+    }).reduce(Accumulator::new, |mut a, b| { a.merge(&b); a }).round()
+}
+
+// A fixed point number wide enough to hold any sum of up to 2^29 products of doubles exactly. Limb i holds
+// 32 bits of weight 2^(32 i - 2148); 2^-2148 is the lowest bit of the product of two subnormals. The limbs
+// are i64 so that carries can be put off until the limbs could overflow, and negative terms are just
+// subtracted.
+const LIMB_BITS: i32 = 32;
+const LIMBS: usize = 136;
+const LOWEST_BIT: i32 = -2148;
+const TERMS_BEFORE_CARRY: usize = 1 << 29;
+
+struct Accumulator
+{
+    limbs: Vec<i64>,
+    pending: usize,
+    nan: bool,
+    pos_inf: bool,
+    neg_inf: bool,
+}
+
+impl Accumulator
+{
+    fn new() -> Accumulator
+    {
+        Accumulator { limbs: vec![0; LIMBS], pending: 0, nan: false, pos_inf: false, neg_inf: false }
+    }
+
+    fn add_dot(&mut self, x: &[f64], y: &[f64])
+    {
+        for (xi, yi) in x.iter().zip(y) {
+            self.add_product(*xi, *yi);
+        }
+    }
+
+    fn add_product(&mut self, x: f64, y: f64)
+    {
+        if !x.is_finite() || !y.is_finite()
+        {
+            let p = x * y;
+            self.nan |= p.is_nan();
+            self.pos_inf |= p == f64::INFINITY;
+            self.neg_inf |= p == f64::NEG_INFINITY;
+            return;
+        }
+
+        let (mx, ex) = decompose(x);
+        let (my, ey) = decompose(y);
+
+        if mx == 0 || my == 0
+        {
+            return;
+        }
+
+        // The exact product as a 106 bit integer times 2^(ex + ey), spread over the five limbs it touches.
+        let m = (mx as u128) * (my as u128);
+        let bit = ex + ey - LOWEST_BIT;
+        let limb = (bit / LIMB_BITS) as usize;
+        let shift = (bit % LIMB_BITS) as u32;
+        let lo = m << shift;
+        let hi = if shift == 0 { 0 } else { m >> (128 - shift) };
+        let negative = (x < 0.0) != (y < 0.0);
+
+        let digits = [lo as u32, (lo >> 32) as u32, (lo >> 64) as u32, (lo >> 96) as u32, hi as u32];
+
+        for (k, d) in digits.iter().enumerate() {
+            if negative { self.limbs[limb + k] -= *d as i64; } else { self.limbs[limb + k] += *d as i64; }
+        }
+
+        self.pending += 1;
+
+        if self.pending == TERMS_BEFORE_CARRY
+        {
+            self.carry();
+        }
+    }
+
+    fn merge(&mut self, other: &Accumulator)
+    {
+        self.carry();
+        for (a, b) in self.limbs.iter_mut().zip(&other.limbs) {
+            *a += *b;
+        }
+        // other has at most TERMS_BEFORE_CARRY terms outstanding, which the fresh carry above leaves room for.
+        self.pending = other.pending + 1;
+        self.nan |= other.nan;
+        self.pos_inf |= other.pos_inf;
+        self.neg_inf |= other.neg_inf;
+    }
+
+    // Propagates carries so every limb but the top one is in [0, 2^32).
+    fn carry(&mut self)
+    {
+        for i in 0..LIMBS - 1 {
+            let c = self.limbs[i] >> LIMB_BITS;
+            self.limbs[i] -= c << LIMB_BITS;
+            self.limbs[i + 1] += c;
+        }
+        self.pending = 0;
+    }
+
+    fn round(mut self) -> f64
+    {
+        if self.nan || (self.pos_inf && self.neg_inf)
+        {
+            return f64::NAN;
+        }
+        else if self.pos_inf
+        {
+            return f64::INFINITY;
+        }
+        else if self.neg_inf
+        {
+            return f64::NEG_INFINITY;
+        }
+
+        self.carry();
+
+        let negative = self.limbs[LIMBS - 1] < 0;
+
+        if negative
+        {
+            for l in self.limbs.iter_mut() {
+                *l = -*l;
+            }
+            self.carry();
+        }
+
+        let top = match self.limbs.iter().rposition(|l| *l != 0) {
+            Some(top) => top,
+            None => return 0.0,
+        };
+
+        // The leading four limbs as a 128 bit integer m, so the value is m * 2^e with everything below folded
+        // into a sticky bit.
+        let limb = |i: isize| if i < 0 { 0 } else { self.limbs[i as usize] as u128 };
+        let top = top as isize;
+        let m = (limb(top) << 96) | (limb(top - 1) << 64) | (limb(top - 2) << 32) | limb(top - 3);
+        let mut sticky = top > 3 && self.limbs[..(top - 3) as usize].iter().any(|l| *l != 0);
+        let e = LIMB_BITS * (top as i32 - 3) + LOWEST_BIT;
+
+        // Keep 53 significant bits, or fewer if the result is subnormal.
+        let msb = 127 - m.leading_zeros() as i32;
+        let drop = (msb - 52).max(-1074 - e);
+
+        let mantissa = if drop <= 0
+        {
+            m as u64
+        }
+        else if drop > 128
+        {
+            0
+        }
+        else
+        {
+            let q = if drop == 128 { 0 } else { (m >> drop) as u64 };
+            let half = (m >> (drop - 1)) & 1 == 1;
+            sticky |= m & ((1u128 << (drop - 1)) - 1) != 0;
+            if half && (sticky || q & 1 == 1) { q + 1 } else { q }
+        };
+
+        let r = scale_by_power_of_two(mantissa as f64, e + drop.max(0));
+
+        if negative { -r } else { r }
+    }
+}
+
+// x = m * 2^e with m an integer below 2^53.
+fn decompose(x: f64) -> (u64, i32)
+{
+    let bits = x.to_bits();
+    let biased = ((bits >> 52) & 0x7ff) as i32;
+    let frac = bits & ((1u64 << 52) - 1);
+
+    if biased == 0 { (frac, -1074) } else { (frac | (1u64 << 52), biased - 1075) }
+}
+
+// x * 2^e for an integer x below 2^54, exact whenever the result is representable.
+fn scale_by_power_of_two(mut x: f64, mut e: i32) -> f64
+{
+    while e > 1000
+    {
+        x *= f64::from_bits(((1000 + 1023) as u64) << 52);
+        e -= 1000;
+    }
+
+    while e < -1000
+    {
+        x *= f64::from_bits(((-1000 + 1023) as u64) << 52);
+        e += 1000;
+    }
+
+    x * f64::from_bits(((e + 1023) as u64) << 52)
+}
+
+#[inline]
+fn two_sum_f64(a: f64, b: f64) -> (f64, f64)
+{
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+// Adds the lanes of s + c, returning the leading sum and the accumulated error.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn sum_lanes(s: __m512d, c: __m512d) -> (f64, f64)
+{
+    let lanes: [f64; 8] = std::mem::transmute(s);
+    let mut r = 0.0;
+    let mut err = _mm512_reduce_add_pd(c);
+
+    for l in lanes {
+        let (t, e) = two_sum_f64(r, l);
+        r = t;
+        err += e;
+    }
+
+    (r, err)
+}
+
+
+const D512_ZERO: __m512d = m64x8_constant!(0.0);
@@ -5,99 +5,201 @@ use super::*;
 unroll_fn!(sin, sin_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
 unroll_fn!(tan, tan_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
 unroll_fn!(atan, atan_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(sinpi, sinpi_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(cospi, cospi_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
+unroll_fn!(tanpi, tanpi_intr, _mm512_loadu_pd, _mm512_storeu_pd, __m512d, f64);
 
 
 #[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
-pub unsafe fn sin_intr(x: &__m512d, y: &mut __m512d)
+pub unsafe fn _mm512_sinpi_pd(x: __m512d) -> __m512d
+{
+    let mut y = D512_ZERO;
+    sinpi_intr(&x, &mut y);
+    y
+}
+
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn _mm512_cospi_pd(x: __m512d) -> __m512d
 {
-    // Since sin() is periodic around 2pi, this converts x into the range of [0, 2pi]
-    let mut xt = _mm512_sub_pd(*x, _mm512_mul_pd(D512_TWOPI, _mm512_roundscale_pd(_mm512_mul_pd(*x, D512_ONE_OVER_TWOPI), _MM_FROUND_TO_NEG_INF)));
-
-    // Since sin() in [0, 2pi] is an odd function around pi, this converts the range to [0, pi], then stores whether
-    // or not the result needs to be negated in negend.
-    let negend_mask = _mm512_cmp_pd_mask(xt, D512_PI, _CMP_GT_OS);
-    xt = _mm512_mask_sub_pd(xt, negend_mask, xt, D512_PI);
-
-    // Since sin() on [0, pi] is an even function around pi/2, this "folds" the range into [0, pi/2]. I.e. 3pi/5 becomes 2pi/5.
-    xt = _mm512_sub_pd(D512_HALFPI, _mm512_abs_pd(_mm512_sub_pd(xt, D512_HALFPI)));
-
-    let xsq = _mm512_mul_pd(xt, xt);
-
-    // This is an odd-only Taylor series approximation of sin() on [0, pi/2]. 
-    let mut yy = _mm512_fmadd_pd(D512_P15, xsq, D512_P13);
-    yy = _mm512_fmadd_pd(yy, xsq, D512_P11);
-    yy = _mm512_fmadd_pd(yy, xsq, D512_P9);
-    yy = _mm512_fmadd_pd(yy, xsq, D512_P7);
-    yy = _mm512_fmadd_pd(yy, xsq, D512_P5);
-    yy = _mm512_fmadd_pd(yy, xsq, D512_P3);
-    yy = _mm512_fmadd_pd(yy, xsq, D512_ONE);
-    yy = _mm512_mul_pd(yy, xt);
-    
-    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*x, *x), D512_NAN, yy);
-    *y = _mm512_mask_mul_pd(yy, negend_mask, yy, D512_NEGONE);
+    let mut y = D512_ZERO;
+    cospi_intr(&x, &mut y);
+    y
 }
 
 #[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
-unsafe fn sin_in_zero_to_quarter_pi(x: &__m512d, y: &mut __m512d)
+pub unsafe fn _mm512_tanpi_pd(x: __m512d) -> __m512d
 {
-    let xsq = _mm512_mul_pd(*x, *x);
-
-    // This is an odd-only Taylor series approximation of sin() on [0, pi/4]. 
-    *y = _mm512_fmadd_pd(D512_SQP13, xsq, D512_SQP11);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_SQP9);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_SQP7);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_SQP5);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_SQP3);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_ONE);
-    *y = _mm512_mul_pd(*y, *x);
+    let mut y = D512_ZERO;
+    tanpi_intr(&x, &mut y);
+    y
 }
 
 
+/// AVX-512 implementation of sin(x). The argument is reduced by pi/2 with a three part Cody-Waite constant for
+/// |x| <= 2^30 and with Payne-Hanek's multi-word 2/pi above that, so it stays accurate for all finite x.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn sin_intr(x: &__m512d, y: &mut __m512d)
+{
+    let (hi, lo, q) = rem_pio2(*x);
+    let s = sin_kernel(hi, lo);
+    let c = cos_kernel(hi, lo);
+
+    // sin(q pi/2 + r) is sin(r), cos(r), -sin(r), -cos(r) for q = 0, 1, 2, 3
+    let swap_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_THREE);
+    let neg_mask = _mm512_cmp_pd_mask(q, D512_TWO, _CMP_GE_OQ);
+    let mut yy = _mm512_mask_blend_pd(swap_mask, s, c);
+    yy = _mm512_mask_sub_pd(yy, neg_mask, D512_ZERO, yy);
+
+    // The reduction turns -0 into +0, so zeros are passed through to keep their sign.
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*x, D512_ZERO), yy, *x);
+    *y = _mm512_mask_blend_pd(finite(*x), D512_NAN, yy);
+}
+
+/// AVX-512 implementation of tan(x), with the same argument reduction as sin_intr.
 #[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
 pub unsafe fn tan_intr(x: &__m512d, y: &mut __m512d)
 {
-    // Calculation:
-    //     Move to range [0, Pi] with no adjustments
-    //     Use oddness around Pi/2 to make range [0, Pi/2] 
-    //     do_inverse_mask = avx.gt(Pi/4)
-    //     do_not_inverse_mask = avx.lte(Pi / 4)
-    //     mirror around Pi/4
-    //     calculate tan(x) = sin(x) / sqrt(1-sin(x)^2)
-    //     y = and(do_inverse, 1/y) + and(no_inverse, y)
-
-    // Since tan() is periodic around pi, this converts x into the range of [0, pi]
-    let mut xt = _mm512_sub_pd(*x, _mm512_mul_pd(D512_PI, _mm512_roundscale_pd(_mm512_mul_pd(*x, D512_ONE_OVER_PI), _MM_FROUND_TO_NEG_INF)));
-
-    // Since tan() in [0, pi] is an odd function around pi/2, this converts the range to [0, pi/2], then stores whether
-    // or not the result needs to be negated in negend.
-    let negend_mask = _mm512_cmp_pd_mask(xt, D512_HALFPI, _CMP_GT_OS);
-    xt = _mm512_mask_add_pd(xt, negend_mask, _mm512_mul_pd(D512_NEGATIVE_TWO, _mm512_sub_pd(xt, D512_HALFPI)), xt);
-
-    // Since tan() on [0, pi/2] is an inversed function around pi/4, this "folds" the range into [0, pi/4]. I.e. 3pi/10 becomes 2pi/10.
-    let do_inv_mask = _mm512_cmp_pd_mask(xt, D512_QUARTERPI, _CMP_GT_OS);
-    xt = _mm512_sub_pd(D512_QUARTERPI, _mm512_abs_pd(_mm512_sub_pd(xt, D512_QUARTERPI)));
-
-    // tan(x) = sin(x) / sqrt(1-sin(x)^2)
-    let mut xx = D512_CT11;
-    sin_in_zero_to_quarter_pi(&xt, &mut xx);
-
-    let xsq = _mm512_mul_pd(xt, xt);
-
-    // This is an odd-only Taylor series approximation of tan() on [0, 0.07]. 
-    *y = _mm512_fmadd_pd(D512_CT11, xsq, D512_CT9);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_CT7);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_CT5);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_CT3);
-    *y = _mm512_fmadd_pd(*y, xsq, D512_CT1);
-    *y = _mm512_mul_pd(*y, xt);
-
-    xt = _mm512_sqrt_pd(_mm512_sub_pd(D512_ONE, _mm512_mul_pd(xx, xx)));
-
-    xx = _mm512_mask_blend_pd(do_inv_mask, _mm512_div_pd(xx, xt), _mm512_div_pd(xt, xx));
-    *y = _mm512_mask_blend_pd(do_inv_mask, *y, _mm512_div_pd(D512_ONE, *y));
-    *y = _mm512_mask_blend_pd(_mm512_cmple_pd_mask(xt, D512_SMALLCONDITION), xx, *y);
-
-    *y = _mm512_mask_mul_pd(*y, negend_mask, D512_NEGONE, *y);
+    let (hi, lo, q) = rem_pio2(*x);
+    let s = sin_kernel(hi, lo);
+    let c = cos_kernel(hi, lo);
+
+    // tan(q pi/2 + r) is tan(r) for even q and -cot(r) for odd q
+    let odd_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_THREE);
+    let mut yy = _mm512_mask_blend_pd(odd_mask, _mm512_div_pd(s, c), _mm512_div_pd(_mm512_sub_pd(D512_ZERO, c), s));
+
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(*x, D512_ZERO), yy, *x);
+    *y = _mm512_mask_blend_pd(finite(*x), D512_NAN, yy);
+}
+
+/// (sin(x), cos(x)) for finite x, with the same argument reduction as sin_intr.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub(crate) unsafe fn sincos(x: __m512d) -> (__m512d, __m512d)
+{
+    let (hi, lo, q) = rem_pio2(x);
+    let s = sin_kernel(hi, lo);
+    let c = cos_kernel(hi, lo);
+
+    // (sin, cos) of q pi/2 + r is (s, c), (c, -s), (-s, -c), (-c, s) for q = 0, 1, 2, 3
+    let swap_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_THREE);
+    let sin_neg_mask = _mm512_cmp_pd_mask(q, D512_TWO, _CMP_GE_OQ);
+    let cos_neg_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_TWO);
+    let ss = _mm512_mask_blend_pd(swap_mask, s, c);
+    let cc = _mm512_mask_blend_pd(swap_mask, c, s);
+    (_mm512_mask_sub_pd(ss, sin_neg_mask, D512_ZERO, ss), _mm512_mask_sub_pd(cc, cos_neg_mask, D512_ZERO, cc))
+}
+
+/// (hi, lo, q) with x = n pi/2 + hi + lo, |hi + lo| <= ~pi/4 and q = n mod 4.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn rem_pio2(x: __m512d) -> (__m512d, __m512d, __m512d)
+{
+    let mut n = _mm512_roundscale_pd(_mm512_mul_pd(x, D512_TWO_OVER_PI), _MM_FROUND_TO_NEAREST_INT);
+
+    // x - n PIO2_1 is exact, since it is a multiple of ulp(x) or of ulp(PIO2_1) that is below 1. The rest of pi/2 is
+    // taken off in double-double.
+    let r = _mm512_fnmadd_pd(n, D512_PIO2_1, x);
+    let p = _mm512_mul_pd(n, D512_PIO2_2);
+    let pe = _mm512_fmsub_pd(n, D512_PIO2_2, p);
+    let (s, e) = two_sum(r, _mm512_sub_pd(D512_ZERO, p));
+    let (mut hi, mut lo) = fast_two_sum(s, _mm512_fnmadd_pd(n, D512_PIO2_3, _mm512_sub_pd(e, pe)));
+
+    let big_mask = !_mm512_cmple_pd_mask(_mm512_abs_pd(x), D512_CODY_WAITE_MAX);
+    if big_mask != 0
+    {
+        let (bhi, blo, bn) = payne_hanek(x);
+        hi = _mm512_mask_mov_pd(hi, big_mask, bhi);
+        lo = _mm512_mask_mov_pd(lo, big_mask, blo);
+        n = _mm512_mask_mov_pd(n, big_mask, bn);
+    }
+
+    let q = _mm512_fnmadd_pd(_mm512_roundscale_pd(_mm512_mul_pd(n, D512_QUARTER), _MM_FROUND_TO_NEG_INF), D512_FOUR, n);
+    (hi, lo, q)
+}
+
+/// (hi, lo, n) with x = n pi/2 + hi + lo for |x| > 2^30, where n is only right modulo 4. x 2/pi is summed modulo 4
+/// from the 24 bit words of 2/pi that can still reach below the units place, which for x = m 2^e starts at word
+/// (e - 2)/24. NaN and infinity give garbage, but never read out of the table.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn payne_hanek(x: __m512d) -> (__m512d, __m512d, __m512d)
+{
+    let e = _mm512_sub_pd(_mm512_getexp_pd(x), D512_FIFTY_TWO);
+    let mut i0 = _mm512_roundscale_pd(_mm512_mul_pd(_mm512_sub_pd(e, D512_TWO), D512_ONE_TWENTYFOURTH), _MM_FROUND_TO_NEG_INF);
+    i0 = _mm512_min_pd(_mm512_max_pd(i0, D512_ZERO), D512_PAYNE_HANEK_MAX_WORD);
+    let index = _mm512_cvtpd_epi64(i0);
+
+    // x 2^-24 i0 is exact, and so is its product with each word as a double-double
+    let xs = _mm512_scalef_pd(x, _mm512_mul_pd(i0, D512_NEG_TWENTYFOUR));
+    let mut hi = D512_ZERO;
+    let mut lo = D512_ZERO;
+    let mut n = D512_ZERO;
+    let mut scale = D512_TWO_POW_NEG_TWENTYFOUR;
+    for j in 0..PAYNE_HANEK_WORDS
+    {
+        let word = _mm512_i64gather_pd(_mm512_add_epi64(index, _mm512_set1_epi64(j as i64)), TWO_OVER_PI_WORDS.as_ptr() as *const _, 8);
+        let xj = _mm512_mul_pd(xs, scale);
+        let ph = _mm512_mul_pd(xj, word);
+        let pl = _mm512_fmsub_pd(xj, word, ph);
+        for part in [ph, pl]
+        {
+            // part less a multiple of 4 is exact, and keeping the units of the sum in n leaves hi in [-1/2, 1/2]
+            let part = _mm512_fnmadd_pd(_mm512_roundscale_pd(_mm512_mul_pd(part, D512_QUARTER), _MM_FROUND_TO_NEAREST_INT), D512_FOUR, part);
+            let (s, err) = two_sum(hi, part);
+            lo = _mm512_add_pd(lo, err);
+            let k = _mm512_roundscale_pd(s, _MM_FROUND_TO_NEAREST_INT);
+            hi = _mm512_sub_pd(s, k);
+            n = _mm512_add_pd(n, k);
+        }
+        scale = _mm512_mul_pd(scale, D512_TWO_POW_NEG_TWENTYFOUR);
+    }
+    let (s, l) = fast_two_sum(hi, lo);
+    let k = _mm512_roundscale_pd(s, _MM_FROUND_TO_NEAREST_INT);
+    n = _mm512_add_pd(n, k);
+    let (f, fl) = fast_two_sum(_mm512_sub_pd(s, k), l);
+
+    // the fraction times pi/2
+    let rh = _mm512_mul_pd(f, D512_PIO2_1);
+    let rl = _mm512_add_pd(_mm512_fmsub_pd(f, D512_PIO2_1, rh), _mm512_fmadd_pd(f, D512_PIO2_2, _mm512_mul_pd(fl, D512_PIO2_1)));
+    let (rh, rl) = fast_two_sum(rh, rl);
+    (rh, rl, n)
+}
+
+/// sin(hi + lo) for |hi + lo| <= ~pi/4, after fdlibm's __kernel_sin.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn sin_kernel(x: __m512d, lo: __m512d) -> __m512d
+{
+    let z = _mm512_mul_pd(x, x);
+    let v = _mm512_mul_pd(z, x);
+    let mut r = _mm512_fmadd_pd(D512_S6, z, D512_S5);
+    r = _mm512_fmadd_pd(r, z, D512_S4);
+    r = _mm512_fmadd_pd(r, z, D512_S3);
+    r = _mm512_fmadd_pd(r, z, D512_S2);
+
+    // x - ((z (lo/2 - v r) - lo) - v S1)
+    let t = _mm512_fmsub_pd(z, _mm512_fnmadd_pd(v, r, _mm512_mul_pd(lo, D512_HALF)), lo);
+    _mm512_sub_pd(x, _mm512_fnmadd_pd(v, D512_S1, t))
+}
+
+/// cos(hi + lo) for |hi + lo| <= ~pi/4, after fdlibm's __kernel_cos.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn cos_kernel(x: __m512d, lo: __m512d) -> __m512d
+{
+    let z = _mm512_mul_pd(x, x);
+    let mut r = _mm512_fmadd_pd(D512_C6, z, D512_C5);
+    r = _mm512_fmadd_pd(r, z, D512_C4);
+    r = _mm512_fmadd_pd(r, z, D512_C3);
+    r = _mm512_fmadd_pd(r, z, D512_C2);
+    r = _mm512_fmadd_pd(r, z, D512_C1);
+    r = _mm512_mul_pd(r, z);
+
+    // 1 - z/2 rounds, so what it lost is carried along with the rest of the series
+    let hz = _mm512_mul_pd(z, D512_HALF);
+    let w = _mm512_sub_pd(D512_ONE, hz);
+    let tail = _mm512_add_pd(_mm512_sub_pd(_mm512_sub_pd(D512_ONE, w), hz), _mm512_fmsub_pd(z, r, _mm512_mul_pd(x, lo)));
+    _mm512_add_pd(w, tail)
 }
 
 
@@ -152,25 +254,104 @@ pub unsafe fn atan_intr(x: &__m512d, y: &mut __m512d)
 }
 
 
-const D512_TWOPI: __m512d = m64x8_constant!(2.0 * std::f64::consts::PI);
-const D512_ONE_OVER_TWOPI: __m512d = m64x8_constant!(0.5 / std::f64::consts::PI);
-const D512_ONE_OVER_PI: __m512d = m64x8_constant!(1.0 / std::f64::consts::PI);
+/// AVX-512 implementation of sin(pi x). The argument reduction is exact, so sinpi(n) is zero for every integer n and
+/// the result stays accurate however large x is.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn sinpi_intr(x: &__m512d, y: &mut __m512d)
+{
+    let (s, c, q) = sincospi_reduced(*x);
+
+    // sin(pi(q/2 + r)) is sin(pi r), cos(pi r), -sin(pi r), -cos(pi r) for q = 0, 1, 2, 3
+    let swap_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_THREE);
+    let neg_mask = _mm512_cmp_pd_mask(q, D512_TWO, _CMP_GE_OQ);
+    let mut yy = _mm512_mask_blend_pd(swap_mask, s, c);
+    yy = _mm512_mask_sub_pd(yy, neg_mask, D512_ZERO, yy);
+
+    // sinpi(n) takes the sign of x, like sin() does at 0
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(yy, D512_ZERO), yy, _mm512_and_pd(*x, D512_NEGATIVE_ZERO));
+    *y = _mm512_mask_blend_pd(finite(*x), D512_NAN, yy);
+}
+
+/// AVX-512 implementation of cos(pi x). The argument reduction is exact, so cospi(n + 1/2) is zero for every integer
+/// n and the result stays accurate however large x is.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn cospi_intr(x: &__m512d, y: &mut __m512d)
+{
+    let (s, c, q) = sincospi_reduced(*x);
+
+    // cos(pi(q/2 + r)) is cos(pi r), -sin(pi r), -cos(pi r), sin(pi r) for q = 0, 1, 2, 3
+    let swap_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_THREE);
+    let neg_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_TWO);
+    let mut yy = _mm512_mask_blend_pd(swap_mask, c, s);
+    yy = _mm512_mask_sub_pd(yy, neg_mask, D512_ZERO, yy);
+
+    // adding +0 turns the -0 at n + 1/2 into +0 and leaves everything else alone
+    yy = _mm512_add_pd(yy, D512_ZERO);
+    *y = _mm512_mask_blend_pd(finite(*x), D512_NAN, yy);
+}
+
+/// AVX-512 implementation of tan(pi x). The argument reduction is exact, so tanpi(n) is zero and tanpi(n + 1/2) is
+/// infinite for every integer n, +inf for even n and -inf for odd n.
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+pub unsafe fn tanpi_intr(x: &__m512d, y: &mut __m512d)
+{
+    let (s, c, q) = sincospi_reduced(*x);
+
+    // tan(pi(q/2 + r)) is tan(pi r) for even q and -cot(pi r) for odd q
+    let odd_mask = _mm512_cmpeq_pd_mask(q, D512_ONE) | _mm512_cmpeq_pd_mask(q, D512_THREE);
+    let mut yy = _mm512_mask_blend_pd(odd_mask, _mm512_div_pd(s, c), _mm512_div_pd(_mm512_sub_pd(D512_ZERO, c), s));
+
+    // -cot(pi r) at r = 0 has the sign of the zero r happens to be, so the poles are set outright
+    let pole_mask = odd_mask & _mm512_cmpeq_pd_mask(s, D512_ZERO);
+    let pole = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(q, D512_ONE), D512_NEGATIVE_INFINITY, D512_POSITIVE_INFINITY);
+    yy = _mm512_mask_blend_pd(pole_mask, yy, pole);
+
+    // tanpi(n) takes the sign of x, like sinpi does
+    yy = _mm512_mask_blend_pd(_mm512_cmpeq_pd_mask(yy, D512_ZERO), yy, _mm512_and_pd(*x, D512_NEGATIVE_ZERO));
+    *y = _mm512_mask_blend_pd(finite(*x), D512_NAN, yy);
+}
+
+/// (sin(pi r), cos(pi r), q) with x = n/2 + r, |r| <= 1/4 and q = n mod 4. r and q are exact for all finite x.
+#[inline]
+#[target_feature(enable ="avx512f,avx512dq,avx512vl,avx512vpopcntdq,avx512vpclmulqdq,avx512cd,avx512bw")]
+unsafe fn sincospi_reduced(x: __m512d) -> (__m512d, __m512d, __m512d)
+{
+    // x - round(2x)/2 without forming 2x, which could overflow
+    let r = _mm512_reduce_pd(x, (1 << 4) | _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC);
+
+    // n/2 is a multiple of 1/2 and n/4 of 1/4, so the fractional part of n/4 is q/4, exactly
+    let quarter_n = _mm512_mul_pd(_mm512_sub_pd(x, r), D512_HALF);
+    let q = _mm512_mul_pd(_mm512_reduce_pd(quarter_n, _MM_FROUND_TO_NEG_INF | _MM_FROUND_NO_EXC), D512_FOUR);
+
+    let z = _mm512_mul_pd(r, r);
+
+    // Taylor series of sin(pi r)/r and cos(pi r) in r^2, which are good to an ulp on |r| <= 1/4
+    let mut s = _mm512_fmadd_pd(D512_SINPI17, z, D512_SINPI15);
+    s = _mm512_fmadd_pd(s, z, D512_SINPI13);
+    s = _mm512_fmadd_pd(s, z, D512_SINPI11);
+    s = _mm512_fmadd_pd(s, z, D512_SINPI9);
+    s = _mm512_fmadd_pd(s, z, D512_SINPI7);
+    s = _mm512_fmadd_pd(s, z, D512_SINPI5);
+    s = _mm512_fmadd_pd(s, z, D512_SINPI3);
+    s = _mm512_fmadd_pd(s, z, D512_PI);
+    s = _mm512_mul_pd(s, r);
+
+    let mut c = _mm512_fmadd_pd(D512_COSPI18, z, D512_COSPI16);
+    c = _mm512_fmadd_pd(c, z, D512_COSPI14);
+    c = _mm512_fmadd_pd(c, z, D512_COSPI12);
+    c = _mm512_fmadd_pd(c, z, D512_COSPI10);
+    c = _mm512_fmadd_pd(c, z, D512_COSPI8);
+    c = _mm512_fmadd_pd(c, z, D512_COSPI6);
+    c = _mm512_fmadd_pd(c, z, D512_COSPI4);
+    c = _mm512_fmadd_pd(c, z, D512_COSPI2);
+    c = _mm512_fmadd_pd(c, z, D512_ONE);
+
+    (s, c, q)
+}
+
+
 const D512_PI: __m512d = m64x8_constant!(std::f64::consts::PI);
 const D512_HALFPI: __m512d = m64x8_constant!(0.5 * std::f64::consts::PI);
-const D512_QUARTERPI: __m512d = m64x8_constant!(0.25 * std::f64::consts::PI);
-const D512_P3: __m512d = m64x8_constant!(-0.166666666666663509013977  );
-const D512_P5: __m512d = m64x8_constant!(0.008333333333299304989001   );
-const D512_P7: __m512d = m64x8_constant!(-0.00019841269828860068271   );
-const D512_P9: __m512d = m64x8_constant!(0.00000275573170815073144    );
-const D512_P11: __m512d = m64x8_constant!(-0.00000002505191090496049   );
-const D512_P13: __m512d = m64x8_constant!(0.000000000160490521296459   );
-const D512_P15: __m512d = m64x8_constant!(-0.0000000000007384998082865);
-const D512_SQP3: __m512d = m64x8_constant!(-0.1666666666666663969165095 );
-const D512_SQP5: __m512d = m64x8_constant!(0.008333333333324419158220   );
-const D512_SQP7: __m512d = m64x8_constant!(-0.00019841269831470328245   );
-const D512_SQP9: __m512d = m64x8_constant!(0.0000027557314284120030     );
-const D512_SQP11: __m512d = m64x8_constant!(-0.0000000250508528135474    );
-const D512_SQP13: __m512d = m64x8_constant!(0.0000000001590238118466);
 const D512_AT00: __m512d = m64x8_constant!(0.46364760900080612191885619);
 const D512_AT01: __m512d = m64x8_constant!(0.8000000000000026556883);
 const D512_AT02: __m512d = m64x8_constant!(-0.32000000000002407003032);
@@ -193,17 +374,66 @@ const D512_AT18: __m512d = m64x8_constant!(-0.0070646973307);
 const D512_AT19: __m512d = m64x8_constant!(0.00363977136);
 const D512_AT20: __m512d = m64x8_constant!(0.0022675623613);
 const D512_AT21: __m512d = m64x8_constant!(-0.00207949497);
-const D512_CT1: __m512d = m64x8_constant!(1.0);
-const D512_CT3: __m512d = m64x8_constant!(0.3333333333333346619643685131);
-const D512_CT5: __m512d = m64x8_constant!(0.1333333333236799972803215674);
-const D512_CT7: __m512d = m64x8_constant!(0.0539682703825024279957999835);
-const D512_CT9: __m512d = m64x8_constant!(0.0218602603709103339870063369);
-const D512_CT11: __m512d = m64x8_constant!(0.0104473875384802020842874186);
-const D512_SMALLCONDITION: __m512d = m64x8_constant!(0.07);
 const D512_ONE: __m512d = m64x8_constant!(1.0);
 const D512_NEGONE: __m512d = m64x8_constant!(-1.0);
-const D512_NEGATIVE_TWO: __m512d = m64x8_constant!(-2.0);
 const D512_HALF: __m512d = m64x8_constant!(0.5);
 const D512_ZERO: __m512d = m64x8_constant!(0.0);
 const D512_NAN: __m512d = m64x8_constant!(f64::NAN);
-const D512_AT_BIG: __m512d = m64x8_constant!(1e10);
\ No newline at end of file
+const D512_AT_BIG: __m512d = m64x8_constant!(1e10);
+const D512_TWO: __m512d = m64x8_constant!(2.0);
+const D512_THREE: __m512d = m64x8_constant!(3.0);
+const D512_FOUR: __m512d = m64x8_constant!(4.0);
+const D512_NEGATIVE_ZERO: __m512d = m64x8_constant!(-0.0);
+const D512_POSITIVE_INFINITY: __m512d = m64x8_constant!(f64::INFINITY);
+const D512_NEGATIVE_INFINITY: __m512d = m64x8_constant!(f64::NEG_INFINITY);
+const D512_SINPI3: __m512d = m64x8_constant!(-5.16771278004997);
+const D512_SINPI5: __m512d = m64x8_constant!(2.5501640398773455);
+const D512_SINPI7: __m512d = m64x8_constant!(-0.5992645293207921);
+const D512_SINPI9: __m512d = m64x8_constant!(0.08214588661112823);
+const D512_SINPI11: __m512d = m64x8_constant!(-0.0073704309457143504);
+const D512_SINPI13: __m512d = m64x8_constant!(0.00046630280576761255);
+const D512_SINPI15: __m512d = m64x8_constant!(-2.1915353447830217e-05);
+const D512_SINPI17: __m512d = m64x8_constant!(7.952054001475513e-07);
+const D512_COSPI2: __m512d = m64x8_constant!(-4.934802200544679);
+const D512_COSPI4: __m512d = m64x8_constant!(4.0587121264167685);
+const D512_COSPI6: __m512d = m64x8_constant!(-1.3352627688545895);
+const D512_COSPI8: __m512d = m64x8_constant!(0.2353306303588932);
+const D512_COSPI10: __m512d = m64x8_constant!(-0.02580689139001406);
+const D512_COSPI12: __m512d = m64x8_constant!(0.0019295743094039231);
+const D512_COSPI14: __m512d = m64x8_constant!(-0.0001046381049248457);
+const D512_COSPI16: __m512d = m64x8_constant!(4.303069587032947e-06);
+const D512_COSPI18: __m512d = m64x8_constant!(-1.3878952462213771e-07);
+const D512_QUARTER: __m512d = m64x8_constant!(0.25);
+const D512_FIFTY_TWO: __m512d = m64x8_constant!(52.0);
+const D512_ONE_TWENTYFOURTH: __m512d = m64x8_constant!(1.0 / 24.0);
+const D512_NEG_TWENTYFOUR: __m512d = m64x8_constant!(-24.0);
+const D512_TWO_POW_NEG_TWENTYFOUR: __m512d = m64x8_constant!(1.0 / 16777216.0);
+const D512_TWO_OVER_PI: __m512d = m64x8_constant!(std::f64::consts::FRAC_2_PI);
+const D512_CODY_WAITE_MAX: __m512d = m64x8_constant!(1073741824.0);
+const D512_PAYNE_HANEK_MAX_WORD: __m512d = m64x8_constant!(40.0);
+const D512_PIO2_1: __m512d = m64x8_constant!(std::f64::consts::FRAC_PI_2);
+const D512_PIO2_2: __m512d = m64x8_constant!(6.123233995736766e-17);
+const D512_PIO2_3: __m512d = m64x8_constant!(-1.4973849048591698e-33);
+const D512_S1: __m512d = m64x8_constant!(-0.16666666666666632);
+const D512_S2: __m512d = m64x8_constant!(0.00833333333332249);
+const D512_S3: __m512d = m64x8_constant!(-0.0001984126982985795);
+const D512_S4: __m512d = m64x8_constant!(2.7557313707070068e-06);
+const D512_S5: __m512d = m64x8_constant!(-2.5050760253406863e-08);
+const D512_S6: __m512d = m64x8_constant!(1.58969099521155e-10);
+const D512_C1: __m512d = m64x8_constant!(0.0416666666666666);
+const D512_C2: __m512d = m64x8_constant!(-0.001388888888887411);
+const D512_C3: __m512d = m64x8_constant!(2.480158728947673e-05);
+const D512_C4: __m512d = m64x8_constant!(-2.7557314351390663e-07);
+const D512_C5: __m512d = m64x8_constant!(2.087572321298175e-09);
+const D512_C6: __m512d = m64x8_constant!(-1.1359647557788195e-11);
+
+// enough words of 2/pi that a double of any exponent has PAYNE_HANEK_WORDS of them from word (e - 2)/24 on
+const PAYNE_HANEK_WORDS: usize = 9;
+static TWO_OVER_PI_WORDS: [f64; 49] = [
+    10680707.0, 7228996.0, 1387004.0, 2578385.0, 16069853.0, 12639074.0, 9804092.0, 4427841.0, 16666979.0,
+    11263675.0, 12935607.0, 2387514.0, 4345298.0, 14681673.0, 3074569.0, 13734428.0, 16653803.0, 1880361.0,
+    10960616.0, 8533493.0, 3062596.0, 8710556.0, 7349940.0, 6258241.0, 3772886.0, 3769171.0, 3798172.0, 8675211.0,
+    12450088.0, 3874808.0, 9961438.0, 366607.0, 15675153.0, 9132554.0, 7151469.0, 3571407.0, 2607881.0, 12013382.0,
+    4155038.0, 6285869.0, 7677882.0, 13102053.0, 15825725.0, 473591.0, 9065106.0, 15363067.0, 6271263.0, 9264392.0,
+    5636912.0
+];
\ No newline at end of file
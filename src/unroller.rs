@@ -8,8 +8,7 @@
 //
 // TODO: $numty is implied by $simdty. There has to be a way to get this input given $simdty.
 //
-// BIG TODO: this creates x -> y functions. Expand the macro to do (x0, x1) -> y functions, and 
-// so on for higher dimensions. 
+// This creates x -> y functions. See unroll_fn_n for (x0, x1, ...) -> (y0, y1, ...) functions.
 #[macro_export]
 macro_rules! unroll_fn {
     ($name:ident, $fun:expr, $load:expr, $store:expr, $simdty:ty, $numty:ty) => {
@@ -134,6 +133,137 @@ macro_rules! unroll_fn {
     }
 }
 
+// The N-ary version of unroll_fn.
+//
+// $name    -- a canonoical identifier of the function being created (e.g. pow for power)
+// $fun     -- the avx implementation. It is called as $fun(&x0, &x1, ..., s0, s1, ..., &mut y0, &mut y1, ...)
+// [$x]     -- the names of the input slices
+// [$s: ty] -- (optional) the names and types of scalar arguments. These are passed through to $fun by value.
+// [$y]     -- the names of the output slices
+// the rest are the same as unroll_fn
+//
+// For example, unroll_fn_n!(pow, pow_intr, [x0, x1] -> [y], ...) creates pow(x0: &[f64], x1: &[f64], y: &mut [f64])
+// and pow_par(...), and unroll_fn_n!(powx, powx_intr, [x] [b: f64] -> [y], ...) creates powx(x: &[f64], b: f64, y: &mut [f64]).
+#[macro_export]
+macro_rules! unroll_fn_n {
+    ($name:ident, $fun:expr, [$($x:ident),+] $([$($s:ident : $sty:ty),+])? -> [$($y:ident),+], $load:expr, $store:expr, $simdty:ty, $numty:ty) => {
+
+        paste::paste! {
+
+            #[inline]
+            pub fn $name($($x: &[$numty],)+ $($($s: $sty,)+)? $($y: &mut [$numty]),+)
+            {
+                unsafe{
+                    [<$name u>]($($x,)+ $($($s,)+)? $($y),+);
+                }
+            }
+
+            #[inline]
+            pub fn [<$name _par>]($($x: &[$numty],)+ $($($s: $sty,)+)? $($y: &mut [$numty]),+)
+            {
+                unsafe{
+                    [<$name _paru>]($($x,)+ $($($s,)+)? $($y),+);
+                }
+            }
+
+            attr_helper!($simdty, unsafe fn [<$name u>]($($x: &[$numty],)+ $($($s: $sty,)+)? $($y: &mut [$numty]),+) {
+                let nn = [$($x.len()),+][0];
+                let n = nn as i32;
+                $(assert_eq!(nn, $x.len());)+
+                $(assert_eq!(nn, $y.len());)+
+                const VSZ: i32 = lane_size!($simdty);
+                const VSZU: usize = lane_size!($simdty);
+                const VSZU2: usize = 2 * VSZU;
+                const VSZU3: usize = 3 * VSZU;
+                const VSZ4: i32 = 4 * VSZ;
+
+                if n < VSZ as i32
+                {
+                    $(let mut [<$x a>] = [0.0 as $numty; VSZU];)+
+                    $(let mut [<$y a>] = [0.0 as $numty; VSZU];)+
+                    for i in 0..nn {
+                        $([<$x a>][i] = $x[i];)+
+                    }
+
+                    $(let [<$x x>] = $load([<$x a>].as_ptr());)+
+                    $(let mut [<$y y>] = $load([<$y a>].as_ptr());)+
+
+                    $fun($(&[<$x x>],)+ $($($s,)+)? $(&mut [<$y y>]),+);
+                    $($store([<$y a>].as_mut_ptr(), [<$y y>]);)+
+
+                    for i in 0..nn {
+                        $($y[i] = [<$y a>][i];)+
+                    }
+                    return;
+                }
+
+                let mut i: usize = 0;
+                $(let [<$x ptr>] = $x.as_ptr();)+
+                $(let [<$y ptr>] = $y.as_mut_ptr();)+
+
+                if n >= VSZ4
+                {
+                    while (i as i32) <= (n - VSZ4)
+                    {
+                        $(let [<$x x0>] = $load([<$x ptr>].add(i));)+
+                        $(let mut [<$y y0>] = $load([<$y ptr>].add(i));)+
+                        $(let [<$x x1>] = $load([<$x ptr>].add(i+VSZU));)+
+                        $(let mut [<$y y1>] = $load([<$y ptr>].add(i+VSZU));)+
+                        $(let [<$x x2>] = $load([<$x ptr>].add(i+VSZU2));)+
+                        $(let mut [<$y y2>] = $load([<$y ptr>].add(i+VSZU2));)+
+                        $(let [<$x x3>] = $load([<$x ptr>].add(i+VSZU3));)+
+                        $(let mut [<$y y3>] = $load([<$y ptr>].add(i+VSZU3));)+
+
+                        $fun($(&[<$x x0>],)+ $($($s,)+)? $(&mut [<$y y0>]),+);
+                        $fun($(&[<$x x1>],)+ $($($s,)+)? $(&mut [<$y y1>]),+);
+                        $fun($(&[<$x x2>],)+ $($($s,)+)? $(&mut [<$y y2>]),+);
+                        $fun($(&[<$x x3>],)+ $($($s,)+)? $(&mut [<$y y3>]),+);
+
+                        $($store([<$y ptr>].add(i), [<$y y0>]);)+
+                        i += VSZU;
+                        $($store([<$y ptr>].add(i), [<$y y1>]);)+
+                        i += VSZU;
+                        $($store([<$y ptr>].add(i), [<$y y2>]);)+
+                        i += VSZU;
+                        $($store([<$y ptr>].add(i), [<$y y3>]);)+
+                        i += VSZU;
+                    }
+                }
+
+                while (i as i32) <= (n - VSZ)
+                {
+                    $(let [<$x x>] = $load([<$x ptr>].add(i));)+
+                    $(let mut [<$y y>] = $load([<$y ptr>].add(i));)+
+                    $fun($(&[<$x x>],)+ $($($s,)+)? $(&mut [<$y y>]),+);
+                    $($store([<$y ptr>].add(i), [<$y y>]);)+
+                    i += VSZU;
+                }
+
+                if i != nn
+                {
+                    i = nn - VSZU;
+                    $(let [<$x x>] = $load([<$x ptr>].add(i));)+
+                    $(let mut [<$y y>] = $load([<$y ptr>].add(i));)+
+                    $fun($(&[<$x x>],)+ $($($s,)+)? $(&mut [<$y y>]),+);
+                    $($store([<$y ptr>].add(i), [<$y y>]);)+
+                }
+            });
+
+            #[inline]
+            unsafe fn [<$name  _paru>]($($x: &[$numty],)+ $($($s: $sty,)+)? $($y: &mut [$numty]),+) {
+                use rayon::prelude::*;
+                let chunk: usize = usize::max([$($x.len()),+][0] / 32, 1);
+
+                ($($y.par_chunks_mut(chunk),)+).into_par_iter().enumerate().for_each(|(index, ($($y,)+))| {
+                    let from = index * chunk;
+                    let to = from + [$($y.len()),+][0];
+                    $name($(&$x[from..to],)+ $($($s,)+)? $($y),+);
+                });
+            }
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! lane_size {
     (__m256d) => { 4 } ;
@@ -206,4 +206,2188 @@ fn atan_test()
         assert!(r);
     }
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn pow_test()
+{
+    let n = 1000;
+    let mut x = vec![0.0; n];
+    let mut b = vec![0.0; n];
+    let mut y = vec![0.0; n];
+
+    for i in 0..n {
+        x[i] = 0.01 + 20.0 * (i as f64) / (n as f64);
+        b[i] = -40.0 + 80.0 * (((i * 7919) % n) as f64) / (n as f64);
+    }
+
+    pow(&x, &b, &mut y);
+
+    for i in 0..n
+    {
+        let r = ulps_eq!(y[i], f64::powf(x[i], b[i]), epsilon = 0.0, max_ulps = 2);
+        assert!(r);
+    }
+
+    // large exponents are where exp(b*ln(x)) loses its precision
+    let x = [1.0000001, 0.9999999, 1.5, 2.0, 10.0, 0.5, 1e-300, 1e300];
+    let b = [1e9, -1e9, 1700.0, 1023.5, -307.25, 1074.0, 0.99, -1.02];
+    let mut y = [0.0; 8];
+
+    pow(&x, &b, &mut y);
+
+    for i in 0..x.len()
+    {
+        let r = ulps_eq!(y[i], f64::powf(x[i], b[i]), epsilon = 0.0, max_ulps = 2);
+        assert!(r);
+    }
+}
+
+#[test]
+fn pow_special_test()
+{
+    let inf = f64::INFINITY;
+    let nan = f64::NAN;
+
+    let cases = [
+        (nan, 0.0), (nan, -0.0), (1.0, nan), (-1.0, inf), (-1.0, -inf), (nan, 1.0), (1.5, nan),
+        (0.0, -3.0), (-0.0, -3.0), (0.0, -2.0), (-0.0, -2.5), (0.0, -inf), (-0.0, -inf),
+        (0.0, 3.0), (-0.0, 3.0), (-0.0, 2.0), (-0.0, 0.5), (0.0, inf),
+        (0.5, -inf), (-0.5, -inf), (2.0, -inf), (-2.0, -inf), (0.5, inf), (-0.5, inf), (2.0, inf), (-2.0, inf),
+        (-inf, -3.0), (-inf, -2.0), (-inf, -0.5), (-inf, 3.0), (-inf, 2.0), (-inf, 0.5),
+        (inf, -1.0), (inf, -0.5), (inf, 0.5), (inf, 3.0),
+        (-2.0, 0.5), (-2.0, -1.5), (-2.0, 3.0), (-2.0, -3.0), (-2.0, 2.0), (-0.5, 1e300), (-3.0, 9007199254740993.0),
+        (2.0, 1024.0), (2.0, -1075.0), (2.0, -1074.0), (2.0, -1022.5), (-10.0, 309.0), (-10.0, -400.0),
+    ];
+
+    let x: Vec<f64> = cases.iter().map(|c| c.0).collect();
+    let b: Vec<f64> = cases.iter().map(|c| c.1).collect();
+    let mut y = vec![0.0; cases.len()];
+
+    pow(&x, &b, &mut y);
+
+    for i in 0..cases.len()
+    {
+        let e = f64::powf(x[i], b[i]);
+
+        if f64::is_nan(e)
+        {
+            assert!(f64::is_nan(y[i]));
+        }
+        else
+        {
+            assert!(ulps_eq!(y[i], e, epsilon = 0.0, max_ulps = 2));
+            assert_eq!(f64::is_sign_negative(y[i]), f64::is_sign_negative(e));
+        }
+    }
+}
+
+#[test]
+fn powx_test()
+{
+    let mut x = [0.0; 100];
+    let mut y = [0.0; 100];
+
+    for i in 0..x.len() {
+        x[i] = -5.0 + 10.0 * (i as f64) / (x.len() as f64);
+    }
+
+    powx(&x, 3.0, &mut y);
+
+    for i in 0..x.len()
+    {
+        let r = ulps_eq!(y[i], f64::powf(x[i], 3.0), epsilon = 0.0, max_ulps = 2);
+        assert!(r);
+    }
+
+    powx(&x, 0.5, &mut y);
+
+    for i in 0..x.len()
+    {
+        if x[i] < 0.0
+        {
+            assert!(f64::is_nan(y[i]));
+        }
+        else
+        {
+            let r = ulps_eq!(y[i], f64::sqrt(x[i]), epsilon = 0.0, max_ulps = 2);
+            assert!(r);
+        }
+    }
+}
+
+#[test]
+fn pown_test()
+{
+    let mut x = [0.0; 100];
+    let mut y = [0.0; 100];
+
+    for i in 0..x.len() {
+        x[i] = -5.0 + 10.0 * (i as f64) / (x.len() as f64);
+    }
+
+    for n in [-7, -2, -1, 0, 1, 2, 3, 10, 31]
+    {
+        pown(&x, n, &mut y);
+
+        for i in 0..x.len()
+        {
+            let r = relative_eq!(y[i], f64::powi(x[i], n), epsilon = 0.0, max_relative = 1e-15);
+            assert!(r);
+        }
+    }
+
+    let x = [0.0, -0.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN];
+    let mut y = [0.0; 5];
+
+    pown(&x, -3, &mut y);
+    assert_eq!(y[0], f64::INFINITY);
+    assert_eq!(y[1], f64::NEG_INFINITY);
+    assert_eq!(y[2], 0.0);
+    assert!(f64::is_sign_negative(y[3]) && y[3] == 0.0);
+    assert!(f64::is_nan(y[4]));
+
+    pown(&x, 0, &mut y);
+    assert!(y.iter().all(|v| *v == 1.0));
+
+    // Results that are representable even though x^|n| is not, or is subnormal.
+    let x = [1e160, 1e-77, 2.0, -2.0, 0.5, 1e-160];
+    let n = [-2, -4, -1074, -1073, 1074, -2];
+    for i in 0..x.len()
+    {
+        let mut y = [0.0];
+        pown(&x[i..i + 1], n[i], &mut y);
+        let r = ulps_eq!(y[0], f64::powf(x[i], n[i] as f64), epsilon = 0.0, max_ulps = 1);
+        assert!(r);
+    }
+}
+
+#[test]
+fn cbrt_test()
+{
+    let mut x = [0.0; 1000];
+    let mut y = [0.0; 1000];
+    x[0] = 0.0;
+    x[1] = -0.0;
+    x[2] = f64::INFINITY;
+    x[3] = f64::NEG_INFINITY;
+    x[4] = f64::NAN;
+    x[5] = 5e-324;
+    x[6] = -1.7e308;
+
+    for i in 7..x.len() {
+        x[i] = f64::powf(10.0, -300.0 + 600.0 * (i as f64) / (x.len() as f64)) * if i % 2 == 0 { 1.0 } else { -1.0 };
+    }
+
+    cbrt(&x, &mut y);
+
+    assert_eq!(y[0], 0.0);
+    assert!(y[1] == 0.0 && f64::is_sign_negative(y[1]));
+    assert_eq!(y[2], f64::INFINITY);
+    assert_eq!(y[3], f64::NEG_INFINITY);
+    assert!(f64::is_nan(y[4]));
+
+    for i in 5..x.len()
+    {
+        let r = ulps_eq!(y[i], f64::cbrt(x[i]), epsilon = 0.0, max_ulps = 1);
+        assert!(r);
+    }
+}
+
+#[test]
+fn invsqrt_test()
+{
+    let mut x = [0.0; 1000];
+    let mut y = [0.0; 1000];
+    let mut yf = [0.0; 1000];
+    x[0] = 0.0;
+    x[1] = -0.0;
+    x[2] = f64::INFINITY;
+    x[3] = -1.0;
+    x[4] = f64::NAN;
+    x[5] = 5e-324;
+    x[6] = 1.7e308;
+
+    for i in 7..x.len() {
+        x[i] = f64::powf(10.0, -300.0 + 600.0 * (i as f64) / (x.len() as f64));
+    }
+
+    invsqrt(&x, &mut y);
+    invsqrt_fast(&x, &mut yf);
+
+    assert_eq!(y[0], f64::INFINITY);
+    assert_eq!(y[1], f64::NEG_INFINITY);
+    assert_eq!(y[2], 0.0);
+    assert!(f64::is_nan(y[3]));
+    assert!(f64::is_nan(y[4]));
+
+    for i in 5..x.len()
+    {
+        let r = ulps_eq!(y[i], 1.0 / f64::sqrt(x[i]), epsilon = 0.0, max_ulps = 2);
+        assert!(r);
+        let r = relative_eq!(yf[i], 1.0 / f64::sqrt(x[i]), epsilon = 0.0, max_relative = 1e-8);
+        assert!(r);
+    }
+}
+
+#[test]
+fn inv_test()
+{
+    let mut x = [0.0; 1000];
+    let mut y = [0.0; 1000];
+    let mut yf = [0.0; 1000];
+    x[0] = 0.0;
+    x[1] = -0.0;
+    x[2] = f64::INFINITY;
+    x[3] = f64::NEG_INFINITY;
+    x[4] = f64::NAN;
+    x[5] = 1e-310;
+    x[6] = -1.7e308;
+
+    for i in 7..x.len() {
+        x[i] = f64::powf(10.0, -300.0 + 600.0 * (i as f64) / (x.len() as f64)) * if i % 2 == 0 { 1.0 } else { -1.0 };
+    }
+
+    inv(&x, &mut y);
+    inv_fast(&x, &mut yf);
+
+    assert_eq!(y[0], f64::INFINITY);
+    assert_eq!(y[1], f64::NEG_INFINITY);
+    assert!(y[2] == 0.0 && f64::is_sign_positive(y[2]));
+    assert!(y[3] == 0.0 && f64::is_sign_negative(y[3]));
+    assert!(f64::is_nan(y[4]));
+
+    for i in 5..x.len()
+    {
+        let r = ulps_eq!(y[i], 1.0 / x[i], epsilon = 0.0, max_ulps = 1);
+        assert!(r);
+        let r = relative_eq!(yf[i], 1.0 / x[i], epsilon = 0.0, max_relative = 1e-8);
+        assert!(r);
+    }
+}
+
+#[test]
+fn hypot_test()
+{
+    let inf = f64::INFINITY;
+    let nan = f64::NAN;
+
+    let mut x0 = vec![3.0, 0.0, -0.0, inf, nan, -inf, nan, 1e300, 1e-300, 5e-324, -1e308, 1.0];
+    let mut x1 = vec![4.0, 0.0, 0.0, nan, inf, 1.0, 1.0, 1e300, 1e-300, 5e-324, 1e308, 1e-20];
+    for i in 0..1000 {
+        x0.push(-50.0 + 0.1 * (i as f64));
+        x1.push(f64::powf(1.1, (i % 37) as f64) - 10.0);
+    }
+
+    let mut y = vec![0.0; x0.len()];
+
+    hypot(&x0, &x1, &mut y);
+
+    assert_eq!(y[0], 5.0);
+    assert_eq!(y[1], 0.0);
+    assert_eq!(y[2], 0.0);
+    assert_eq!(y[3], inf);
+    assert_eq!(y[4], inf);
+    assert_eq!(y[5], inf);
+    assert!(f64::is_nan(y[6]));
+
+    for i in 7..y.len()
+    {
+        let r = ulps_eq!(y[i], f64::hypot(x0[i], x1[i]), epsilon = 0.0, max_ulps = 1);
+        assert!(r);
+    }
+}
+
+#[test]
+fn erfc_test()
+{
+    use statrs::function::erf;
+
+    let mut x = vec![0.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 30.0, -30.0];
+    for i in 0..1200 {
+        x.push(-6.0 + 0.027 * (i as f64));
+    }
+
+    let mut y = vec![0.0; x.len()];
+
+    lit_math::erfc(&x, &mut y);
+
+    assert_eq!(y[0], 1.0);
+    assert_eq!(y[1], 0.0);
+    assert_eq!(y[2], 2.0);
+    assert!(f64::is_nan(y[3]));
+    assert_eq!(y[4], 0.0);
+    assert_eq!(y[5], 2.0);
+
+    // statrs is only good to ~1e-10 in places
+    for i in 6..y.len()
+    {
+        let r = relative_eq!(y[i], erf::erfc(x[i]), epsilon = 0.0, max_relative = 1e-9);
+        assert!(r);
+    }
+
+    // reference values from mpmath
+    let x = [-3.5, -0.75, 0.1, 0.507, 1.0, 2.5, 5.0, 10.0, 15.5, 22.0, 26.5];
+    let e = [1.9999992569016276, 1.7111556336535152, 0.887537083981715, 0.4733702241049685, 0.15729920705028513,
+        0.0004069520174449589, 1.537459794428035e-12, 2.088487583762545e-45, 1.6632016400488723e-106,
+        1.6219058609334726e-212, 2.2109076642637343e-307];
+    let mut y = [0.0; 11];
+
+    lit_math::erfc(&x, &mut y);
+
+    for i in 0..y.len()
+    {
+        let r = ulps_eq!(y[i], e[i], epsilon = 0.0, max_ulps = 2);
+        assert!(r);
+    }
+}
+
+#[test]
+fn erfinv_test()
+{
+    use statrs::function::erf;
+
+    let mut x = vec![0.0, 1.0, -1.0, 1.5, f64::NAN, 1e-300];
+    for i in 1..2000 {
+        x.push(-1.0 + 0.001 * (i as f64));
+    }
+
+    let mut y = vec![0.0; x.len()];
+
+    erfinv(&x, &mut y);
+
+    assert_eq!(y[0], 0.0);
+    assert_eq!(y[1], f64::INFINITY);
+    assert_eq!(y[2], f64::NEG_INFINITY);
+    assert!(f64::is_nan(y[3]));
+    assert!(f64::is_nan(y[4]));
+
+    for i in 5..y.len()
+    {
+        let r = relative_eq!(y[i], erf::erf_inv(x[i]), epsilon = 0.0, max_relative = 4e-15);
+        assert!(r);
+    }
+}
+
+#[test]
+fn erfcinv_test()
+{
+    use statrs::function::erf;
+
+    let mut x = vec![0.0, 2.0, 1.0, -0.5, 2.5, f64::NAN];
+    for i in 1..2000 {
+        x.push(0.001 * (i as f64));
+    }
+    for i in 1..300 {
+        x.push(f64::powi(10.0, -i));
+    }
+
+    let mut y = vec![0.0; x.len()];
+
+    erfcinv(&x, &mut y);
+
+    assert_eq!(y[0], f64::INFINITY);
+    assert_eq!(y[1], f64::NEG_INFINITY);
+    assert_eq!(y[2], 0.0);
+    assert!(f64::is_nan(y[3]));
+    assert!(f64::is_nan(y[4]));
+    assert!(f64::is_nan(y[5]));
+
+    for i in 6..y.len()
+    {
+        let r = relative_eq!(y[i], erf::erfc_inv(x[i]), epsilon = 0.0, max_relative = 4e-15);
+        assert!(r);
+    }
+}
+
+#[test]
+fn standard_normal_cdf_test()
+{
+    // reference values from mpmath
+    let x = [-37.0, -20.0, -8.5, -1.5, 0.0, 0.3, 2.0, 6.0, f64::INFINITY, f64::NEG_INFINITY];
+    let e = [5.725571222524577e-300, 2.7536241186062337e-89, 9.479534822203318e-18, 0.06680720126885807, 0.5,
+        0.6179114221889527, 0.9772498680518208, 0.9999999990134123, 1.0, 0.0];
+    let mut y = [0.0; 10];
+
+    standard_normal_cdf(&x, &mut y);
+
+    for i in 0..y.len()
+    {
+        let r = ulps_eq!(y[i], e[i], epsilon = 0.0, max_ulps = 4);
+        assert!(r);
+    }
+}
+
+#[test]
+fn standard_normal_inv_cdf_test()
+{
+    use statrs::function::erf;
+
+    // reference values from mpmath
+    let x = [1e-300, 1e-20, 1e-5, 0.02, 0.3, 0.5, 0.7, 0.99, 1.0 - f64::powi(2.0, -40)];
+    let e = [-37.0470962993612, -9.262340089798407, -4.264890793922825, -2.053748910631823, -0.5244005127080408, 0.0,
+        0.5244005127080407, 2.3263478740408408, 7.047700256664409];
+    let mut y = [0.0; 9];
+
+    standard_normal_inv_cdf(&x, &mut y);
+
+    for i in 0..y.len()
+    {
+        let r = ulps_eq!(y[i], e[i], epsilon = 0.0, max_ulps = 4);
+        assert!(r);
+    }
+
+    let mut x = vec![0.0, 1.0, -0.1, 1.1, f64::NAN];
+    for i in 1..1000 {
+        x.push(0.001 * (i as f64));
+    }
+    let mut y = vec![0.0; x.len()];
+
+    standard_normal_inv_cdf(&x, &mut y);
+
+    assert_eq!(y[0], f64::NEG_INFINITY);
+    assert_eq!(y[1], f64::INFINITY);
+    assert!(f64::is_nan(y[2]));
+    assert!(f64::is_nan(y[3]));
+    assert!(f64::is_nan(y[4]));
+
+    for i in 5..y.len()
+    {
+        let e = -std::f64::consts::SQRT_2 * erf::erfc_inv(2.0 * x[i]);
+        let r = relative_eq!(y[i], e, epsilon = 1e-15, max_relative = 4e-15);
+        assert!(r);
+    }
+}
+
+#[test]
+fn lgamma_test()
+{
+    use statrs::function::gamma;
+
+    // reference values from mpmath
+    let x = [0.5, 1.0 + f64::powi(2.0, -30), 2.5, 7.25, 30.5, 150.75, -0.25, -3.5];
+    let e = [0.5723649429247001, -5.375739784311044e-10, 0.2846828704729192, 7.0521854507385395, 72.9534711841694,
+        603.7668223739875, 1.589575312551186, -1.309006684993042];
+    let mut y = [0.0; 8];
+
+    lgamma(&x, &mut y);
+
+    for i in 0..y.len()
+    {
+        let r = ulps_eq!(y[i], e[i], epsilon = 0.0, max_ulps = 4);
+        assert!(r);
+    }
+
+    let mut x = vec![0.0, -1.0, -20.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 1.0, 2.0, 1e306];
+    for i in 0..1000 {
+        x.push(0.05 + 0.137 * (i as f64));
+    }
+    let mut y = vec![0.0; x.len()];
+
+    lgamma(&x, &mut y);
+
+    assert_eq!(y[0], f64::INFINITY);
+    assert_eq!(y[1], f64::INFINITY);
+    assert_eq!(y[2], f64::INFINITY);
+    assert_eq!(y[3], f64::INFINITY);
+    assert_eq!(y[4], f64::INFINITY);
+    assert!(f64::is_nan(y[5]));
+    assert_eq!(y[6], 0.0);
+    assert_eq!(y[7], 0.0);
+    assert_eq!(y[8], f64::INFINITY);
+
+    for i in 9..y.len()
+    {
+        let r = relative_eq!(y[i], gamma::ln_gamma(x[i]), epsilon = 1e-14, max_relative = 1e-14);
+        assert!(r);
+    }
+}
+
+#[test]
+fn tgamma_test()
+{
+    use statrs::function::gamma;
+
+    // reference values from mpmath
+    let x = [0.5, 1.0 + f64::powi(2.0, -30), 2.5, 7.25, 30.5, 150.75, -0.25, -3.5];
+    let e = [1.772453850905516, 0.999999999462426, 1.329340388179137, 1155.3810139199898, 4.822696933490909e+31,
+        1.63154596407512e+262, -4.901666809860711, 0.2700882058522691];
+    let mut y = [0.0; 8];
+
+    tgamma(&x, &mut y);
+
+    for i in 0..y.len()
+    {
+        let r = ulps_eq!(y[i], e[i], epsilon = 0.0, max_ulps = 4);
+        assert!(r);
+    }
+
+    let mut x = vec![0.0, -0.0, -1.0, -20.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 172.0, 5.0];
+    for i in 0..1000 {
+        x.push(0.05 + 0.165 * (i as f64));
+    }
+    let mut y = vec![0.0; x.len()];
+
+    tgamma(&x, &mut y);
+
+    assert_eq!(y[0], f64::INFINITY);
+    assert_eq!(y[1], f64::NEG_INFINITY);
+    assert!(f64::is_nan(y[2]));
+    assert!(f64::is_nan(y[3]));
+    assert_eq!(y[4], f64::INFINITY);
+    assert!(f64::is_nan(y[5]));
+    assert!(f64::is_nan(y[6]));
+    assert_eq!(y[7], f64::INFINITY);
+    assert_eq!(y[8], 24.0);
+
+    // statrs is only good to ~1e-13 in places
+    for i in 9..y.len()
+    {
+        let r = relative_eq!(y[i], gamma::gamma(x[i]), epsilon = 0.0, max_relative = 1e-12);
+        assert!(r);
+    }
+}
+
+#[test]
+fn digamma_test()
+{
+    use statrs::function::gamma;
+
+    // reference values from mpmath
+    let x = [0.5, 1.0 + f64::powi(2.0, -30), 2.5, 7.25, 30.5, 150.75, -0.25, -3.5];
+    let e = [-1.9635100260214235, -0.5772156633695686, 0.7031566406452432, 1.910453526883736, 3.401243668961661,
+        5.01230241909542, 2.9141391202135276, 1.388870926359529];
+    let mut y = [0.0; 8];
+
+    digamma(&x, &mut y);
+
+    for i in 0..y.len()
+    {
+        let r = ulps_eq!(y[i], e[i], epsilon = 0.0, max_ulps = 8);
+        assert!(r);
+    }
+
+    let mut x = vec![0.0, -1.0, -20.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN];
+    for i in 0..1000 {
+        x.push(0.05 + 0.137 * (i as f64));
+    }
+    let mut y = vec![0.0; x.len()];
+
+    digamma(&x, &mut y);
+
+    assert_eq!(y[0], f64::NEG_INFINITY);
+    assert!(f64::is_nan(y[1]));
+    assert!(f64::is_nan(y[2]));
+    assert_eq!(y[3], f64::INFINITY);
+    assert!(f64::is_nan(y[4]));
+    assert!(f64::is_nan(y[5]));
+
+    for i in 6..y.len()
+    {
+        let r = relative_eq!(y[i], gamma::digamma(x[i]), epsilon = 1e-14, max_relative = 1e-13);
+        assert!(r);
+    }
+}
+
+#[test]
+fn gamma_pq_test()
+{
+    use statrs::function::gamma;
+
+    let mut a = vec![1.0, 2.0, -1.0, 1.0, 3.0, 3.0, f64::NAN];
+    let mut x = vec![0.0, f64::INFINITY, 1.0, -1.0, 1e-300, 500.0, 1.0];
+    for i in 0..40 {
+        for j in 0..40 {
+            a.push(0.1 + 0.7 * (i as f64));
+            x.push(0.05 + 0.9 * (j as f64));
+        }
+    }
+    let mut p = vec![0.0; x.len()];
+    let mut q = vec![0.0; x.len()];
+
+    gamma_p(&a, &x, &mut p);
+    gamma_q(&a, &x, &mut q);
+
+    assert_eq!(p[0], 0.0);
+    assert_eq!(q[0], 1.0);
+    assert_eq!(p[1], 1.0);
+    assert_eq!(q[1], 0.0);
+    assert!(f64::is_nan(p[2]) && f64::is_nan(q[2]));
+    assert!(f64::is_nan(p[3]) && f64::is_nan(q[3]));
+    assert!(p[4] >= 0.0 && p[4] < 1e-299);
+    assert_eq!(p[5], 1.0);
+    assert!(f64::is_nan(p[6]) && f64::is_nan(q[6]));
+
+    for i in 7..p.len()
+    {
+        if x[i] < a[i] + 1.0 {
+            assert!(relative_eq!(p[i], gamma::gamma_lr(a[i], x[i]), epsilon = 1e-300, max_relative = 1e-12));
+        }
+        else {
+            assert!(relative_eq!(q[i], gamma::gamma_ur(a[i], x[i]), epsilon = 1e-300, max_relative = 1e-12));
+        }
+        assert!(relative_eq!(p[i] + q[i], 1.0, epsilon = 1e-15));
+    }
+
+    // Large a with x near a, against mpmath.
+    let a = [1e7, 1e8, 1e8, 1e9, 1e9, 1e7, 1e8];
+    let x = [1e7, 1e8 - 1e4, 1e8, 1e9, 1e9 + 3e4, 1e7 - 2e4, 1e8 + 5e4];
+    let real_p = [0.50004205220872370, 0.15865525352814383, 0.50001329807601412, 0.50000420522087006,
+        0.82860941259815861, 1.2364070637223815e-10, 0.99999971215703131];
+    let real_q = [0.49995794779127630, 0.84134474647185617, 0.49998670192398588, 0.49999579477912994,
+        0.17139058740184139, 0.99999999987635929, 2.8784296868527811e-7];
+    let mut p = [0.0; 7];
+    let mut q = [0.0; 7];
+    gamma_p(&a, &x, &mut p);
+    gamma_q(&a, &x, &mut q);
+    assert!((0..7).all(|i| relative_eq!(p[i], real_p[i], max_relative = 1e-13)));
+    assert!((0..7).all(|i| relative_eq!(q[i], real_q[i], max_relative = 1e-13)));
+}
+
+#[test]
+fn beta_inc_test()
+{
+    use statrs::function::beta;
+
+    let mut a = vec![1.0, 1.0, -1.0, 1.0, 2.0, f64::NAN];
+    let mut b = vec![1.0, 1.0, 1.0, 1.0, 3.0, 1.0];
+    let mut x = vec![0.0, 1.0, 0.5, 1.5, 0.25, 0.5];
+    for i in 0..20 {
+        for j in 0..20 {
+            for k in 1..20 {
+                a.push(0.2 + 1.3 * (i as f64));
+                b.push(0.3 + 1.7 * (j as f64));
+                x.push(0.05 * (k as f64));
+            }
+        }
+    }
+    let mut y = vec![0.0; x.len()];
+
+    beta_inc(&a, &b, &x, &mut y);
+
+    assert_eq!(y[0], 0.0);
+    assert_eq!(y[1], 1.0);
+    assert!(f64::is_nan(y[2]));
+    assert!(f64::is_nan(y[3]));
+    // I_x(2, 3) = 6x^2 - 8x^3 + 3x^4
+    assert!(relative_eq!(y[4], 0.26171875, max_relative = 4e-15));
+    assert!(f64::is_nan(y[5]));
+
+    for i in 6..y.len()
+    {
+        assert!(relative_eq!(y[i], beta::beta_reg(a[i], b[i], x[i]), epsilon = 1e-300, max_relative = 1e-11));
+    }
+}
+
+
+#[test]
+fn normal_dist_test()
+{
+    use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+
+    let mut x = Vec::new();
+    let mut mu = Vec::new();
+    let mut sigma = Vec::new();
+    for i in 0..50 {
+        for j in 0..5 {
+            x.push(-10.0 + 0.4 * (i as f64));
+            mu.push(-1.0 + 0.5 * (j as f64));
+            sigma.push(0.5 + 0.75 * (j as f64));
+        }
+    }
+    let mut pdf = vec![0.0; x.len()];
+    let mut logpdf = vec![0.0; x.len()];
+    let mut cdf = vec![0.0; x.len()];
+    let mut q = vec![0.0; x.len()];
+
+    normal_pdf(&x, &mu, &sigma, &mut pdf);
+    normal_logpdf(&x, &mu, &sigma, &mut logpdf);
+    normal_cdf(&x, &mu, &sigma, &mut cdf);
+    normal_quantile(&cdf, &mu, &sigma, &mut q);
+
+    for i in 0..x.len()
+    {
+        let n = Normal::new(mu[i], sigma[i]).unwrap();
+        assert!(relative_eq!(pdf[i], n.pdf(x[i]), epsilon = 1e-300, max_relative = 1e-13));
+        assert!(relative_eq!(logpdf[i], n.ln_pdf(x[i]), max_relative = 1e-14));
+        // statrs is only good to ~1e-10 in the tails
+        assert!(relative_eq!(cdf[i], n.cdf(x[i]), epsilon = 1e-300, max_relative = 1e-9));
+        if cdf[i] > 1e-300 && cdf[i] < 0.99
+        {
+            assert!(relative_eq!(q[i], x[i], epsilon = 1e-13, max_relative = 1e-12));
+        }
+    }
+
+    let mut y = vec![0.0; 3];
+    normal_pdfx(&[0.0, 1.0, 2.0], 1.0, 2.0, &mut y);
+    assert!(relative_eq!(y[1], 0.19947114020071635, max_relative = 1e-15));
+    normal_pdf(&[0.0], &[0.0], &[-1.0], &mut y[..1]);
+    assert!(f64::is_nan(y[0]));
+}
+
+
+#[test]
+fn lognormal_exponential_dist_test()
+{
+    use statrs::distribution::{Continuous, ContinuousCDF, Exp, LogNormal};
+
+    let x: Vec<f64> = (0..200).map(|i| 0.05 * (i as f64)).collect();
+    let mut pdf = vec![0.0; x.len()];
+    let mut logpdf = vec![0.0; x.len()];
+    let mut cdf = vec![0.0; x.len()];
+    let mut q = vec![0.0; x.len()];
+
+    lognormal_pdfx(&x, 0.3, 0.8, &mut pdf);
+    lognormal_logpdfx(&x, 0.3, 0.8, &mut logpdf);
+    lognormal_cdfx(&x, 0.3, 0.8, &mut cdf);
+    lognormal_quantilex(&cdf, 0.3, 0.8, &mut q);
+
+    let ln = LogNormal::new(0.3, 0.8).unwrap();
+    assert_eq!(pdf[0], 0.0);
+    assert_eq!(logpdf[0], f64::NEG_INFINITY);
+    assert_eq!(cdf[0], 0.0);
+    for i in 1..x.len()
+    {
+        assert!(relative_eq!(pdf[i], ln.pdf(x[i]), epsilon = 1e-300, max_relative = 1e-13));
+        assert!(relative_eq!(logpdf[i], ln.ln_pdf(x[i]), epsilon = 1e-14, max_relative = 1e-14));
+        assert!(relative_eq!(cdf[i], ln.cdf(x[i]), epsilon = 1e-300, max_relative = 1e-9));
+        assert!(relative_eq!(q[i], x[i], max_relative = 1e-12));
+    }
+
+    exponential_pdfx(&x, 1.5, &mut pdf);
+    exponential_logpdfx(&x, 1.5, &mut logpdf);
+    exponential_cdfx(&x, 1.5, &mut cdf);
+    exponential_quantilex(&cdf, 1.5, &mut q);
+
+    let e = Exp::new(1.5).unwrap();
+    for i in 0..x.len()
+    {
+        assert!(relative_eq!(pdf[i], e.pdf(x[i]), max_relative = 1e-14));
+        assert!(relative_eq!(logpdf[i], e.ln_pdf(x[i]), epsilon = 1e-15, max_relative = 1e-14));
+        assert!(relative_eq!(cdf[i], e.cdf(x[i]), max_relative = 1e-14));
+        if i > 0 && cdf[i] < 0.999
+        {
+            assert!(relative_eq!(q[i], x[i], max_relative = 1e-12));
+        }
+    }
+
+    // no cancellation for small arguments
+    exponential_cdfx(&[1e-20, -1.0], 1.0, &mut cdf[..2]);
+    assert!(relative_eq!(cdf[0], 1e-20, max_relative = 1e-15));
+    assert_eq!(cdf[1], 0.0);
+    exponential_quantilex(&[1e-20, 1.0], 1.0, &mut q[..2]);
+    assert!(relative_eq!(q[0], 1e-20, max_relative = 1e-15));
+    assert_eq!(q[1], f64::INFINITY);
+}
+
+
+#[test]
+fn student_t_dist_test()
+{
+    use statrs::distribution::{Continuous, ContinuousCDF, StudentsT};
+
+    let mut x = Vec::new();
+    let mut nu = Vec::new();
+    for i in 0..81 {
+        for &n in [0.5, 1.0, 2.5, 7.0, 30.0].iter() {
+            x.push(-20.0 + 0.5 * (i as f64));
+            nu.push(n);
+        }
+    }
+    let mut pdf = vec![0.0; x.len()];
+    let mut logpdf = vec![0.0; x.len()];
+    let mut cdf = vec![0.0; x.len()];
+    let mut q = vec![0.0; x.len()];
+
+    student_t_pdf(&x, &nu, &mut pdf);
+    student_t_logpdf(&x, &nu, &mut logpdf);
+    student_t_cdf(&x, &nu, &mut cdf);
+    student_t_quantile(&cdf, &nu, &mut q);
+
+    for i in 0..x.len()
+    {
+        let t = StudentsT::new(0.0, 1.0, nu[i]).unwrap();
+        // statrs is only good to ~1e-13 in the tails
+        assert!(relative_eq!(pdf[i], t.pdf(x[i]), max_relative = 1e-12));
+        assert!(relative_eq!(logpdf[i], t.ln_pdf(x[i]), epsilon = 1e-12, max_relative = 1e-13));
+        assert!(relative_eq!(cdf[i], t.cdf(x[i]), epsilon = 1e-300, max_relative = 1e-10));
+        if cdf[i] < 0.999
+        {
+            assert!(relative_eq!(q[i], x[i], epsilon = 1e-12, max_relative = 1e-10));
+        }
+    }
+
+    // Cauchy for nu = 1
+    student_t_cdfx(&[1.0, -1.0, 0.0], 1.0, &mut cdf[..3]);
+    assert!(relative_eq!(cdf[0], 0.75, max_relative = 4e-15));
+    assert!(relative_eq!(cdf[1], 0.25, max_relative = 4e-15));
+    assert_eq!(cdf[2], 0.5);
+}
+
+
+#[test]
+fn gamma_dist_test()
+{
+    use statrs::distribution::{ChiSquared, Continuous, ContinuousCDF, Gamma};
+
+    let mut x = Vec::new();
+    let mut shape = Vec::new();
+    let mut scale = Vec::new();
+    for i in 1..60 {
+        for &(k, s) in [(0.5, 1.0), (1.0, 2.0), (2.5, 0.5), (9.0, 3.0), (40.0, 0.25)].iter() {
+            x.push(0.25 * (i as f64));
+            shape.push(k);
+            scale.push(s);
+        }
+    }
+    let mut pdf = vec![0.0; x.len()];
+    let mut logpdf = vec![0.0; x.len()];
+    let mut cdf = vec![0.0; x.len()];
+    let mut q = vec![0.0; x.len()];
+
+    gamma_pdf(&x, &shape, &scale, &mut pdf);
+    gamma_logpdf(&x, &shape, &scale, &mut logpdf);
+    gamma_cdf(&x, &shape, &scale, &mut cdf);
+    gamma_quantile(&cdf, &shape, &scale, &mut q);
+
+    for i in 0..x.len()
+    {
+        // statrs takes the rate
+        let g = Gamma::new(shape[i], 1.0 / scale[i]).unwrap();
+        assert!(relative_eq!(pdf[i], g.pdf(x[i]), epsilon = 1e-300, max_relative = 1e-12));
+        assert!(relative_eq!(logpdf[i], g.ln_pdf(x[i]), epsilon = 1e-13, max_relative = 1e-13));
+        assert!(relative_eq!(cdf[i], g.cdf(x[i]), epsilon = 1e-300, max_relative = 1e-11));
+        if cdf[i] > 1e-300 && cdf[i] < 0.999
+        {
+            assert!(relative_eq!(q[i], x[i], max_relative = 1e-10));
+        }
+    }
+
+    chi_squared_pdfx(&x, 3.0, &mut pdf);
+    chi_squared_cdfx(&x, 3.0, &mut cdf);
+    chi_squared_quantilex(&cdf, 3.0, &mut q);
+    let c = ChiSquared::new(3.0).unwrap();
+    for i in 0..x.len()
+    {
+        assert!(relative_eq!(pdf[i], c.pdf(x[i]), epsilon = 1e-300, max_relative = 1e-12));
+        assert!(relative_eq!(cdf[i], c.cdf(x[i]), epsilon = 1e-300, max_relative = 1e-11));
+        if cdf[i] < 0.999
+        {
+            assert!(relative_eq!(q[i], x[i], max_relative = 1e-10));
+        }
+    }
+
+    gamma_pdfx(&[0.0, -1.0, 1.0], 1.0, 2.0, &mut pdf[..3]);
+    assert!(relative_eq!(pdf[0], 0.5, max_relative = 1e-15));
+    assert_eq!(pdf[1], 0.0);
+    gamma_quantilex(&[0.0, 1.0, 0.5], 1.0, -2.0, &mut q[..3]);
+    assert!(f64::is_nan(q[2]));
+    gamma_cdfx(&[f64::NAN], 2.0, 1.0, &mut cdf[..1]);
+    chi_squared_cdfx(&[f64::NAN], 3.0, &mut cdf[1..2]);
+    assert!(f64::is_nan(cdf[0]) && f64::is_nan(cdf[1]));
+
+    // The starting point underflows to 0, where Newton's method can't move, so this one is left to the bisection.
+    // The true quantile, 0.5^(1e300), underflows too.
+    gamma_quantilex(&[0.5], 1e-300, 1.0, &mut q[..1]);
+    assert!(q[0] >= 0.0 && q[0] < 1e-300);
+}
+
+
+#[test]
+fn beta_dist_test()
+{
+    use statrs::distribution::{Beta, Continuous, ContinuousCDF};
+
+    let mut x = Vec::new();
+    let mut a = Vec::new();
+    let mut b = Vec::new();
+    for i in 1..40 {
+        for &(aa, bb) in [(0.5, 0.5), (1.0, 3.0), (2.5, 0.7), (5.0, 5.0), (30.0, 12.0)].iter() {
+            x.push(0.025 * (i as f64));
+            a.push(aa);
+            b.push(bb);
+        }
+    }
+    let mut pdf = vec![0.0; x.len()];
+    let mut logpdf = vec![0.0; x.len()];
+    let mut cdf = vec![0.0; x.len()];
+    let mut q = vec![0.0; x.len()];
+
+    beta_pdf(&x, &a, &b, &mut pdf);
+    beta_logpdf(&x, &a, &b, &mut logpdf);
+    beta_cdf(&x, &a, &b, &mut cdf);
+    beta_quantile(&cdf, &a, &b, &mut q);
+
+    for i in 0..x.len()
+    {
+        let d = Beta::new(a[i], b[i]).unwrap();
+        assert!(relative_eq!(pdf[i], d.pdf(x[i]), epsilon = 1e-300, max_relative = 1e-12));
+        assert!(relative_eq!(logpdf[i], d.ln_pdf(x[i]), epsilon = 1e-13, max_relative = 1e-12));
+        assert!(relative_eq!(cdf[i], d.cdf(x[i]), epsilon = 1e-300, max_relative = 1e-11));
+        if cdf[i] > 1e-300 && cdf[i] < 0.999
+        {
+            assert!(relative_eq!(q[i], x[i], max_relative = 1e-10));
+        }
+    }
+
+    beta_cdfx(&[-1.0, 2.0, 0.5, f64::NAN], 2.0, 2.0, &mut cdf[..4]);
+    assert_eq!(cdf[0], 0.0);
+    assert_eq!(cdf[1], 1.0);
+    assert!(relative_eq!(cdf[2], 0.5, max_relative = 4e-15));
+    assert!(f64::is_nan(cdf[3]));
+
+    // Newton's method runs out of steps on these, and the bisection finishes them. Beta(1, b) has
+    // 1 - I_x = (1 - x)^b, so the quantile is known in closed form.
+    let p = 1.0 - 1e-15;
+    beta_quantilex(&[p, 0.5], 1.0, 1000.0, &mut q[..2]);
+    assert!(relative_eq!(q[0], -f64::exp_m1(f64::ln(1.0 - p) / 1000.0), max_relative = 1e-10));
+    assert!(relative_eq!(q[1], -f64::exp_m1(f64::ln(0.5) / 1000.0), max_relative = 1e-12));
+}
+
+
+#[test]
+fn poisson_binomial_dist_test()
+{
+    use statrs::distribution::{Binomial, Discrete, DiscreteCDF, Poisson};
+
+    let k: Vec<f64> = (0..80).map(|i| i as f64).collect();
+    let mut pmf = vec![0.0; k.len()];
+    let mut logpmf = vec![0.0; k.len()];
+    let mut cdf = vec![0.0; k.len()];
+    let mut q = vec![0.0; k.len()];
+
+    for &lambda in [0.3, 4.0, 25.5].iter()
+    {
+        poisson_pmfx(&k, lambda, &mut pmf);
+        poisson_logpmfx(&k, lambda, &mut logpmf);
+        poisson_cdfx(&k, lambda, &mut cdf);
+        poisson_quantilex(&cdf, lambda, &mut q);
+
+        let d = Poisson::new(lambda).unwrap();
+        for i in 0..k.len()
+        {
+            assert!(relative_eq!(pmf[i], d.pmf(i as u64), epsilon = 1e-300, max_relative = 1e-12));
+            assert!(relative_eq!(logpmf[i], d.ln_pmf(i as u64), epsilon = 1e-13, max_relative = 1e-13));
+            assert!(relative_eq!(cdf[i], d.cdf(i as u64), epsilon = 1e-300, max_relative = 1e-11));
+            if cdf[i] < 1.0 - 1e-12 && pmf[i] > 1e-12
+            {
+                assert_eq!(q[i], k[i]);
+            }
+        }
+    }
+
+    for &(n, p) in [(10.0, 0.5), (40.0, 0.05), (79.0, 0.9)].iter()
+    {
+        binomial_pmfx(&k, n, p, &mut pmf);
+        binomial_logpmfx(&k, n, p, &mut logpmf);
+        binomial_cdfx(&k, n, p, &mut cdf);
+        binomial_quantilex(&cdf, n, p, &mut q);
+
+        let d = Binomial::new(p, n as u64).unwrap();
+        for i in 0..k.len()
+        {
+            assert!(relative_eq!(pmf[i], d.pmf(i as u64), epsilon = 1e-300, max_relative = 1e-12));
+            if pmf[i] > 0.0
+            {
+                assert!(relative_eq!(logpmf[i], d.ln_pmf(i as u64), epsilon = 1e-13, max_relative = 1e-13));
+            }
+            assert!(relative_eq!(cdf[i], d.cdf(i as u64), epsilon = 1e-300, max_relative = 1e-11));
+            if cdf[i] < 1.0 - 1e-12 && pmf[i] > 1e-12
+            {
+                assert_eq!(q[i], k[i]);
+            }
+        }
+    }
+
+    poisson_pmfx(&[1.5, -1.0], 2.0, &mut pmf[..2]);
+    assert_eq!(pmf[0], 0.0);
+    assert_eq!(pmf[1], 0.0);
+    binomial_quantilex(&[0.0, 1.0], 10.0, 0.3, &mut q[..2]);
+    assert_eq!(q[0], 0.0);
+    assert_eq!(q[1], 10.0);
+    poisson_cdfx(&[f64::NAN], 2.0, &mut cdf[..1]);
+    binomial_cdfx(&[f64::NAN], 10.0, 0.3, &mut cdf[1..2]);
+    assert!(f64::is_nan(cdf[0]) && f64::is_nan(cdf[1]));
+
+    // Far out in skewed tails the Cornish-Fisher guess is off by hundreds, but the search still stops at the
+    // smallest k with cdf(k) >= x.
+    let x = [1e-300, 1e-300, 0.5, 1.0 - 1e-15, 1e-300];
+    let mut qs = [0.0; 5];
+    let mut below = [0.0; 5];
+    let mut at = [0.0; 5];
+    let lambda = [1e-10, 1e6, 1e6, 1e-3, 50.0];
+    for i in 0..x.len()
+    {
+        poisson_quantilex(&x[i..i + 1], lambda[i], &mut qs[i..i + 1]);
+        poisson_cdfx(&[qs[i] - 1.0, qs[i]], lambda[i], &mut cdf[..2]);
+        below[i] = cdf[0];
+        at[i] = cdf[1];
+    }
+    assert_eq!(qs[0], 0.0);
+    assert!((0..x.len()).all(|i| below[i] < x[i] && at[i] >= x[i]));
+
+    let n = [1e6, 1e6, 1e6, 200.0, 1e12];
+    let p = [1.0 - 1e-9, 1e-9, 0.5, 0.999, 1e-12];
+    for i in 0..x.len()
+    {
+        binomial_quantilex(&x[i..i + 1], n[i], p[i], &mut qs[i..i + 1]);
+        binomial_cdfx(&[qs[i] - 1.0, qs[i]], n[i], p[i], &mut cdf[..2]);
+        below[i] = cdf[0];
+        at[i] = cdf[1];
+    }
+    assert!((0..x.len()).all(|i| below[i] < x[i] && at[i] >= x[i]));
+}
+
+
+#[test]
+fn bivariate_normal_cdf_test()
+{
+    use statrs::distribution::{ContinuousCDF, Normal};
+
+    // (x, y, rho, P(X < x, Y < y)) from numerical integration in mpmath
+    let cases = [
+        (0.0, 0.0, 0.5, 0.33333333333333333),
+        (1.0, -0.5, 0.2, 0.27575580849620518),
+        (-2.0, 1.5, -0.6, 0.012244428471443277),
+        (0.3, 0.7, 0.95, 0.61241716824368813),
+        (-1.2, -0.8, 0.99, 0.1150460050460241),
+        (2.5, -1.0, -0.95, 0.15244559406671957),
+        (-3.0, -3.0, 0.5, 8.1889661832192112e-5),
+        (1.0, 2.0, -0.999, 0.81859461412036374),
+        (-5.0, 2.0, 0.8, 2.8665157187919391e-7),
+        (0.5, 0.5, -0.5, 0.41922310903660271),
+        (-1.0, 1.0, -0.93, 0.036116810863904638),
+        (4.0, 4.0, 0.1, 0.99993666266162746),
+    ];
+    let x: Vec<f64> = cases.iter().map(|c| c.0).collect();
+    let y: Vec<f64> = cases.iter().map(|c| c.1).collect();
+    let rho: Vec<f64> = cases.iter().map(|c| c.2).collect();
+    let mut p = vec![0.0; cases.len()];
+
+    bivariate_normal_cdf(&x, &y, &rho, &mut p);
+
+    for i in 0..cases.len()
+    {
+        assert!(relative_eq!(p[i], cases[i].3, epsilon = 1e-15, max_relative = 1e-12));
+    }
+
+    // rho = 0, 1 and -1 reduce to the univariate CDF
+    let n = Normal::new(0.0, 1.0).unwrap();
+    let mut x = Vec::new();
+    let mut y = Vec::new();
+    let mut rho = Vec::new();
+    for i in 0..30 {
+        for j in 0..30 {
+            for &r in [0.0, 1.0, -1.0].iter() {
+                x.push(-4.0 + 0.27 * (i as f64));
+                y.push(-4.0 + 0.27 * (j as f64));
+                rho.push(r);
+            }
+        }
+    }
+    let mut p = vec![0.0; x.len()];
+    bivariate_normal_cdf_par(&x, &y, &rho, &mut p);
+    for i in 0..x.len()
+    {
+        let expected = match rho[i] {
+            r if r > 0.0 => n.cdf(x[i].min(y[i])),
+            r if r < 0.0 => (n.cdf(x[i]) + n.cdf(y[i]) - 1.0).max(0.0),
+            _ => n.cdf(x[i]) * n.cdf(y[i]),
+        };
+        assert!(relative_eq!(p[i], expected, epsilon = 1e-15, max_relative = 1e-9));
+    }
+
+    let mut p = vec![0.0; 5];
+    bivariate_normal_cdf(&[f64::INFINITY, 0.0, f64::NEG_INFINITY, 0.0, f64::NAN], &[0.0, f64::INFINITY, 0.0, 0.0, 0.0],
+        &[0.5, 0.5, 0.5, 1.5, 0.5], &mut p);
+    assert_eq!(p[0], 0.5);
+    assert_eq!(p[1], 0.5);
+    assert_eq!(p[2], 0.0);
+    assert!(f64::is_nan(p[3]));
+    assert!(f64::is_nan(p[4]));
+}
+
+#[test]
+fn bessel_test()
+{
+    let x = [1e-10, 0.1, 0.5, 1.0, 2.0, 3.5, 7.9, 8.1, 12.0, 30.0, 100.0, 1000.0];
+    // (function, values at x) from mpmath
+    let cases: [(fn(&[f64], &mut [f64]), [f64; 12]); 8] = [
+        (j0, [1.0, 0.99750156206604, 0.9384698072408129, 0.7651976865579666, 0.22389077914123567,
+            -0.3801277399872634, 0.19436184484127825, 0.14751745404437766, 0.047689310796833535,
+            -0.08636798358104021, 0.019985850304223122, 0.024786686152420176]),
+        (j1, [5.00000000000000018e-11, 0.049937526036242, 0.2422684576748739, 0.4400505857449335, 0.5767248077568734,
+            0.1373775273623272, 0.2191793999217512, 0.24760776698159287, -0.2234471044906276, -0.11875106261662294,
+            -0.07714535201411216, 0.004728311907089524]),
+        (y0, [-14.732516272697241, -1.5342386513503667, -0.44451873350670656, 0.08825696421567696,
+            0.5103756726497451, 0.1890219439208265, 0.20652094814437577, 0.23809132870223482, -0.22523731263436145,
+            -0.11729573168666403, -0.07724431336508315, 0.0047159179776228135]),
+        (y1, [-6366197723.675814, -6.4589510947020266, -1.471472392670243, -0.7812128213002887, -0.10703243154093754,
+            0.41018841788751187, -0.18172107728057313, -0.13314879595249593, -0.05709921826089652,
+            0.08442557066174723, -0.020372312002759792, -0.024784331292351778]),
+        (i0e, [0.9999999999, 0.9071009257823011, 0.6450352704491501, 0.46575960759364043, 0.30850832255367105,
+            0.22280243801077917, 0.14436986414104191, 0.14251180948829528, 0.11642622121344044, 0.0731459464822373,
+            0.03994437929909668, 0.012617240455891257]),
+        (i1e, [4.99999999949999987e-11, 0.045298446808809324, 0.1564208031848717, 0.20791041534970844,
+            0.21526928924893765, 0.18739997660305, 0.13489649943989376, 0.13340068832583662, 0.11146429929018098,
+            0.07191633059864755, 0.03974415302513025, 0.012610930256928629]),
+        (k0e, [23.141782447913048, 2.6823261022628944, 1.5241093857739094, 1.144463079806895, 0.8415682150707714,
+            0.6490263376886884, 0.4393000819002152, 0.4339943754308567, 0.3581948784890782, 0.22788666561625373,
+            0.12517562165912657, 0.03962832160075422]),
+        (k1e, [10000000001.0, 10.890182683049696, 2.731009708211786, 1.6361534862632583, 1.0334768470686886,
+            0.7364675480289125, 0.4663177847368799, 0.4600435707528057, 0.37283175336970986, 0.2316541293777118,
+            0.12579995047957854, 0.03964813081296021]),
+    ];
+
+    let mut y = [0.0; 12];
+    for (f, expected) in cases.iter()
+    {
+        f(&x, &mut y);
+        for i in 0..x.len()
+        {
+            // J and Y only hold an absolute error near their zeros
+            assert!(relative_eq!(y[i], expected[i], epsilon = 5e-15, max_relative = 1e-14));
+        }
+    }
+
+    // large x, where the phase x - pi/4 can no longer be rounded before reduction, from mpmath
+    let x = [1e6, 1e15, 1e20];
+    let cases: [(fn(&[f64], &mut [f64]), [f64; 3]); 4] = [
+        (j0, [0.00033104301373987376, 6.156638646885021e-09, 6.698009040703424e-12]),
+        (j1, [-0.000725968356813763, 2.4468665123771328e-08, -7.95068198242545e-11]),
+        (y0, [-0.0007259685223351791, 2.4468665123771324e-08, -7.95068198242545e-11]),
+        (y1, [-0.00033104337672417626, -6.15663864688501e-09, -6.698009040703424e-12]),
+    ];
+    let mut y = [0.0; 3];
+    for (f, expected) in cases.iter()
+    {
+        f(&x, &mut y);
+        for i in 0..x.len()
+        {
+            assert!(relative_eq!(y[i], expected[i], max_relative = 1e-13));
+        }
+    }
+
+    // unscaled I and K from mpmath
+    let x = [0.5, 2.0, 9.0, 50.0];
+    let cases: [(fn(&[f64], &mut [f64]), [f64; 4]); 4] = [
+        (i0, [1.0634833707413236, 2.2795853023360673, 1093.5883545113747, 2.9325537838493362e+20]),
+        (i1, [0.2578943053908963, 1.590636854637329, 1030.9147225169565, 2.903078590103557e+20]),
+        (k0, [0.9244190712276659, 0.11389387274953344, 5.08813129564592462e-05, 3.41016774978949556e-23]),
+        (k1, [1.656441120003301, 0.13986588181652243, 5.36370163794519481e-05, 3.44410222671755546e-23]),
+    ];
+    let mut y = [0.0; 4];
+    for (f, expected) in cases.iter()
+    {
+        f(&x, &mut y);
+        for i in 0..x.len()
+        {
+            assert!(relative_eq!(y[i], expected[i], max_relative = 1e-14));
+        }
+    }
+
+    // parity of J and I against the positive half
+    let x: Vec<f64> = (0..1000).map(|i| 0.037 * (i as f64)).collect();
+    let nx: Vec<f64> = x.iter().map(|v| -v).collect();
+    for &(f, sign) in [(j0_par as fn(&[f64], &mut [f64]), 1.0), (j1_par, -1.0), (i0_par, 1.0), (i1_par, -1.0),
+        (i0e_par, 1.0), (i1e_par, -1.0)].iter()
+    {
+        let mut y = vec![0.0; x.len()];
+        let mut ny = vec![0.0; x.len()];
+        f(&x, &mut y);
+        f(&nx, &mut ny);
+        for i in 0..x.len()
+        {
+            assert_eq!(ny[i], sign * y[i]);
+        }
+    }
+
+    let x = [0.0, -1.0, f64::INFINITY, f64::NAN];
+    let mut y = [0.0; 4];
+    j0(&x, &mut y);
+    assert!(relative_eq!(y[0], 1.0, max_relative = 1e-15));
+    assert_eq!(y[2], 0.0);
+    assert!(f64::is_nan(y[3]));
+    i0(&x, &mut y);
+    assert_eq!(y[0], 1.0);
+    assert_eq!(y[2], f64::INFINITY);
+    y0(&x, &mut y);
+    assert_eq!(y[0], f64::NEG_INFINITY);
+    assert!(f64::is_nan(y[1]));
+    assert_eq!(y[2], 0.0);
+    assert!(f64::is_nan(y[3]));
+    k1(&x, &mut y);
+    assert_eq!(y[0], f64::INFINITY);
+    assert!(f64::is_nan(y[1]));
+    assert_eq!(y[2], 0.0);
+    assert!(f64::is_nan(y[3]));
+}
+
+#[test]
+fn sinpi_cospi_tanpi_test()
+{
+    // (x, sin(pi x), cos(pi x), tan(pi x)) from mpmath
+    let cases = [
+        (0.1, 0.30901699437494745, 0.9510565162951535, 0.32491969623290634),
+        (-0.3, -0.8090169943749475, 0.5877852522924731, -1.3763819204711734),
+        (0.7, 0.8090169943749475, -0.587785252292473, -1.376381920471174),
+        (1.2345, -0.6718498738846351, -0.740687347644875, 0.9070627114407736),
+        (-7.77, 0.6613118653236529, 0.7501110696304587, 0.8816185923631915),
+        (1e-300, 3.1415926535897936e-300, 1.0, 3.1415926535897936e-300),
+        (123456.789, 0.6153863701685053, -0.7882256119987674, -0.7807236415574219),
+        (1125899906842624.8, 0.7071067811865476, -0.7071067811865476, -1.0),
+        (3.0000001, -3.1415926484482773e-07, -0.9999999999999507, 3.1415926484484324e-07),
+    ];
+    let x: Vec<f64> = cases.iter().map(|c| c.0).collect();
+    let mut s = vec![0.0; x.len()];
+    let mut c = vec![0.0; x.len()];
+    let mut t = vec![0.0; x.len()];
+    sinpi(&x, &mut s);
+    cospi(&x, &mut c);
+    tanpi(&x, &mut t);
+    for i in 0..x.len()
+    {
+        assert!(relative_eq!(s[i], cases[i].1, max_relative = 3e-16));
+        assert!(relative_eq!(c[i], cases[i].2, max_relative = 3e-16));
+        assert!(relative_eq!(t[i], cases[i].3, max_relative = 5e-16));
+    }
+
+    // the reduction is exact, so shifting by whole periods changes nothing, and agrees with sin(pi x) for small x
+    let x: Vec<f64> = (0..4096).map(|i| -1.0 + (i as f64) / 2048.0).collect();
+    let shifted: Vec<f64> = x.iter().map(|v| v + 2.0 * 1024.0 * 1024.0).collect();
+    for &f in [sinpi_par as fn(&[f64], &mut [f64]), cospi_par, tanpi_par].iter()
+    {
+        let mut y = vec![0.0; x.len()];
+        let mut ys = vec![0.0; x.len()];
+        f(&x, &mut y);
+        f(&shifted, &mut ys);
+        assert_eq!(y, ys);
+    }
+    let mut y = vec![0.0; x.len()];
+    sinpi(&x, &mut y);
+    for i in 0..x.len()
+    {
+        assert!(relative_eq!(y[i], f64::sin(std::f64::consts::PI * x[i]), epsilon = 1e-15));
+    }
+
+    let x = [0.0, -0.0, 1.0, -2.0, 0.5, 1.5, 1e300, f64::INFINITY, f64::NAN];
+    let mut s = [0.0; 9];
+    let mut c = [0.0; 9];
+    let mut t = [0.0; 9];
+    sinpi(&x, &mut s);
+    cospi(&x, &mut c);
+    tanpi(&x, &mut t);
+    assert_eq!(s[0].to_bits(), 0.0f64.to_bits());
+    assert_eq!(s[1].to_bits(), (-0.0f64).to_bits());
+    assert_eq!(s[2], 0.0);
+    assert_eq!(s[3], 0.0);
+    assert_eq!(s[4], 1.0);
+    assert_eq!(s[5], -1.0);
+    assert_eq!(s[6], 0.0);
+    assert_eq!(c[0], 1.0);
+    assert_eq!(c[2], -1.0);
+    assert_eq!(c[4].to_bits(), 0.0f64.to_bits());
+    assert_eq!(c[5].to_bits(), 0.0f64.to_bits());
+    assert_eq!(c[6], 1.0);
+    assert_eq!(t[0].to_bits(), 0.0f64.to_bits());
+    assert_eq!(t[1].to_bits(), (-0.0f64).to_bits());
+    assert_eq!(t[2], 0.0);
+    assert_eq!(t[4], f64::INFINITY);
+    assert_eq!(t[5], f64::NEG_INFINITY);
+    for i in 7..9
+    {
+        assert!(f64::is_nan(s[i]) && f64::is_nan(c[i]) && f64::is_nan(t[i]));
+    }
+}
+
+#[test]
+fn sin_tan_large_argument_test()
+{
+    // (x, sin(x), tan(x)) from mpmath, across the Cody-Waite and Payne-Hanek reductions. The last x is the double
+    // closest to a multiple of pi/2.
+    let cases = [
+        (355.0, -3.014435335948845e-05, 3.0144353373184265e-05),
+        (103993.0, -1.9129335778423752e-05, -1.9129335781923763e-05),
+        (1000000.1, -0.2547258270603761, -0.2634150149595979),
+        (1073741823.0, -0.9955341030193974, 10.545622343967292),
+        (1073741825.0, 0.3284483319740196, 0.347740288941375),
+        (1000000000000000.0, 0.8582727931702359, -1.672414782127583),
+        (9007199254740992.0, -0.848925964814655, 1.6062574002155612),
+        (1e+22, -0.8522008497671888, -1.6287782256068988),
+        (-1e+100, 0.3806377310050287, 0.4116229628832498),
+        (1e+200, -0.6439687185395058, -0.8417321552123704),
+        (1e+300, -0.8178819121159085, 1.4214488238747245),
+        (1.7976931348623157e+308, 0.004961954789184062, -0.004962015874444895),
+        (5.319372648326541e+255, 1.0, -2.133485385753704e+18),
+    ];
+    let x: Vec<f64> = cases.iter().map(|c| c.0).collect();
+    let mut s = vec![0.0; x.len()];
+    let mut t = vec![0.0; x.len()];
+    sin(&x, &mut s);
+    tan(&x, &mut t);
+    for i in 0..x.len()
+    {
+        assert!(relative_eq!(s[i], cases[i].1, max_relative = 5e-16));
+        assert!(relative_eq!(t[i], cases[i].2, max_relative = 5e-16));
+    }
+
+    // sin(-x) = -sin(x) holds exactly through both reductions
+    let x: Vec<f64> = (0..2000).map(|i| 1.37f64.powf(0.5 * (i as f64))).collect();
+    let nx: Vec<f64> = x.iter().map(|v| -v).collect();
+    let mut y = vec![0.0; x.len()];
+    let mut ny = vec![0.0; x.len()];
+    sin_par(&x, &mut y);
+    sin_par(&nx, &mut ny);
+    for i in 0..x.len()
+    {
+        assert_eq!(ny[i], -y[i]);
+    }
+
+    // Signed zeros come back unchanged
+    let x = [0.0, -0.0];
+    let mut s = [1.0; 2];
+    let mut t = [1.0; 2];
+    sin(&x, &mut s);
+    tan(&x, &mut t);
+    assert!(s[0] == 0.0 && s[0].is_sign_positive() && s[1] == 0.0 && s[1].is_sign_negative());
+    assert!(t[0] == 0.0 && t[0].is_sign_positive() && t[1] == 0.0 && t[1].is_sign_negative());
+}
+
+#[test]
+fn rounding_test()
+{
+    let mut x: Vec<f64> = (0..2000).map(|i| -10.0 + 0.01 * (i as f64)).collect();
+    x.extend_from_slice(&[0.5, -0.5, 2.5, -2.5, 0.49999999999999994, -0.0, 4503599627370497.0, 1e300, -1e-300,
+        f64::INFINITY, f64::NEG_INFINITY]);
+    let n = x.len();
+    let mut y = vec![0.0; n];
+    let mut fp = vec![0.0; n];
+
+    let same = |a: f64, b: f64| a.to_bits() == b.to_bits();
+
+    floor(&x, &mut y);
+    assert!((0..n).all(|i| same(y[i], x[i].floor())));
+    ceil(&x, &mut y);
+    assert!((0..n).all(|i| same(y[i], x[i].ceil())));
+    trunc(&x, &mut y);
+    assert!((0..n).all(|i| same(y[i], x[i].trunc())));
+    round_par(&x, &mut y);
+    assert!((0..n).all(|i| same(y[i], x[i].round())));
+    rint(&x, &mut y);
+    for i in 0..n
+    {
+        let half = (x[i] - x[i].trunc()).abs() == 0.5;
+        assert!(same(y[i], if half { 2.0 * (0.5 * x[i]).round() } else { x[i].round() }));
+    }
+    modf(&x, &mut y, &mut fp);
+    for i in 0..n
+    {
+        assert!(same(y[i], x[i].trunc()));
+        let expected = if x[i].is_infinite() { 0.0f64.copysign(x[i]) } else { (x[i] - x[i].trunc()).copysign(x[i]) };
+        assert!(same(fp[i], expected));
+    }
+
+    let mut m = vec![0.0; n];
+    let mut e = vec![0.0; n];
+    frexp(&x, &mut m, &mut e);
+    ldexp(&m, &e, &mut y);
+    for i in 0..n
+    {
+        assert!(same(y[i], x[i]));
+        assert!(m[i] == 0.0 || m[i].is_infinite() || (0.5..1.0).contains(&m[i].abs()));
+    }
+    let x = [1.0, 0.75, -3.0, 1e-310, f64::MAX, 5e-324, -0.1, f64::NAN];
+    let mut m = [0.0; 8];
+    let mut e = [0.0; 8];
+    frexp(&x, &mut m, &mut e);
+    assert_eq!(&m[..7], &[0.5, 0.75, -0.75, 0.5752618031559393, 0.9999999999999999, 0.5, -0.8]);
+    assert_eq!(&e[..7], &[1.0, 0.0, 2.0, -1029.0, 1024.0, -1073.0, -3.0]);
+    assert!(f64::is_nan(m[7]));
+}
+
+#[test]
+fn fmod_remainder_test()
+{
+    // (x0, x1, fmod, remainder) from C's fmod and remainder
+    let cases = [
+        (1e+300, 3.0, 0.0f64, 0.0f64),
+        (-1e+300, 3.0, -0.0, -0.0),
+        (5.5, 2.0, 1.5, -0.5),
+        (-5.5, 2.0, -1.5, 0.5),
+        (4.5, 1.0, 0.5, 0.5),
+        (3.5, 1.0, 0.5, -0.5),
+        (7.0, -3.0, 1.0, 1.0),
+        (1e-310, 3e-312, 9.9999999999e-313, 9.9999999999e-313),
+        (1.7976931348623157e+308, 1.5e+308, 2.976931348623157e+307, 2.976931348623157e+307),
+        (123456.789, 0.1, 0.08899999999745414, -0.011000000002545862),
+        (1e+22, 1e-300, 9.997433296225588e-301, -2.566703774412376e-304),
+        (-0.0, 1.0, -0.0, -0.0),
+        (2.0, 4e-320, 1.581e-320, 1.581e-320),
+    ];
+    let x0: Vec<f64> = cases.iter().map(|c| c.0).collect();
+    let x1: Vec<f64> = cases.iter().map(|c| c.1).collect();
+    let mut f = vec![0.0; cases.len()];
+    let mut r = vec![0.0; cases.len()];
+    fmod(&x0, &x1, &mut f);
+    remainder(&x0, &x1, &mut r);
+    for i in 0..cases.len()
+    {
+        assert_eq!(f[i].to_bits(), cases[i].2.to_bits());
+        assert_eq!(r[i].to_bits(), cases[i].3.to_bits());
+    }
+
+    // Rust's % is an exact fmod
+    let x0: Vec<f64> = (0..3000).map(|i| (-1.0f64).powi(i) * 1.7f64.powi(i % 600 - 300)).collect();
+    let x1: Vec<f64> = (0..3000).map(|i| 0.3 + 1.3f64.powi(i % 97 - 40)).collect();
+    let mut f = vec![0.0; x0.len()];
+    fmod_par(&x0, &x1, &mut f);
+    for i in 0..x0.len()
+    {
+        assert_eq!(f[i].to_bits(), (x0[i] % x1[i]).to_bits());
+    }
+
+    let x0 = [1.0, f64::INFINITY, 1.0, f64::NAN, 1.0, 3.0];
+    let x1 = [0.0, 1.0, f64::NAN, 1.0, f64::INFINITY, f64::NEG_INFINITY];
+    let mut f = [0.0; 6];
+    let mut r = [0.0; 6];
+    fmod(&x0, &x1, &mut f);
+    remainder(&x0, &x1, &mut r);
+    for i in 0..4
+    {
+        assert!(f64::is_nan(f[i]) && f64::is_nan(r[i]));
+    }
+    assert_eq!(&f[4..], &[1.0, 3.0]);
+    assert_eq!(&r[4..], &[1.0, 3.0]);
+}
+
+#[test]
+fn arith_test()
+{
+    let x0: Vec<f64> = (0..1003).map(|i| -5.0 + 0.01 * (i as f64)).collect();
+    let x1: Vec<f64> = (0..1003).map(|i| 3.0 - 0.007 * (i as f64)).collect();
+    let n = x0.len();
+    let mut y = vec![0.0; n];
+
+    add(&x0, &x1, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i] + x1[i]));
+    sub_par(&x0, &x1, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i] - x1[i]));
+    mul(&x0, &x1, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i] * x1[i]));
+    div(&x0, &x1, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i] / x1[i]));
+    sqr(&x0, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i] * x0[i]));
+    abs(&x0, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i].abs()));
+    fmax(&x0, &x1, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i].max(x1[i])));
+    fmin(&x0, &x1, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i].min(x1[i])));
+    copysign(&x0, &x1, &mut y);
+    assert!((0..n).all(|i| y[i] == x0[i].copysign(x1[i])));
+    linear_frac(&x0, &x1, 2.0, -1.0, 0.5, 3.0, &mut y);
+    assert!((0..n).all(|i| relative_eq!(y[i], (2.0 * x0[i] - 1.0) / (0.5 * x1[i] + 3.0), max_relative = 1e-15)));
+
+    let a = [1.0, f64::NAN, f64::NAN, -0.0];
+    let b = [f64::NAN, 2.0, f64::NAN, 1.0];
+    let mut y = [0.0; 4];
+    fmax(&a, &b, &mut y);
+    assert_eq!(&y[..2], &[1.0, 2.0]);
+    assert!(f64::is_nan(y[2]));
+    fmin(&a, &b, &mut y);
+    assert_eq!(&y[..2], &[1.0, 2.0]);
+    assert!(f64::is_nan(y[2]));
+    copysign(&b, &a, &mut y);
+    assert_eq!(y[3].to_bits(), (-1.0f64).to_bits());
+
+    let a = [1.0, 1.0, 0.0, -0.0, 0.0, f64::MAX, f64::INFINITY, 5e-324, -1.0, 2.0, f64::NAN, 1.0];
+    let b = [2.0, 0.0, 1.0, -1.0, -0.0, f64::INFINITY, 0.0, 0.0, -2.0, 2.0, 1.0, f64::NAN];
+    let mut y = [0.0; 12];
+    nextafter(&a, &b, &mut y);
+    assert_eq!(y[0], 1.0 + f64::EPSILON);
+    assert_eq!(y[1], 1.0 - f64::EPSILON / 2.0);
+    assert_eq!(y[2], 5e-324);
+    assert_eq!(y[3], -5e-324);
+    assert_eq!(y[4].to_bits(), (-0.0f64).to_bits());
+    assert_eq!(y[5], f64::INFINITY);
+    assert_eq!(y[6], f64::MAX);
+    assert_eq!(y[7], 0.0);
+    assert_eq!(y[8], -1.0 - f64::EPSILON);
+    assert_eq!(y[9], 2.0);
+    assert!(f64::is_nan(y[10]) && f64::is_nan(y[11]));
+}
+
+
+#[test]
+fn activation_test()
+{
+    let x = [-800.0, -40.0, -5.0, -1.0, -1e-08, 0.0, 0.3, 1.0, 5.0, 40.0, 800.0];
+    let expected: [(fn(&[f64], &mut [f64]), [f64; 11]); 8] = [
+        (sigmoid, [0.0, 4.248354255291589e-18, 0.0066928509242848554, 0.2689414213699951, 0.4999999975, 0.5, 0.574442516811659, 0.7310585786300049, 0.9933071490757152, 1.0, 1.0]),
+        (softplus, [0.0, 4.248354255291589e-18, 0.006715348489118068, 0.3132616875182228, 0.6931471755599453, 0.6931471805599453, 0.8543552444685271, 1.3132616875182228, 5.006715348489118, 40.0, 800.0]),
+        (log_sigmoid, [-800.0, -40.0, -5.006715348489118, -1.3132616875182228, -0.6931471855599454, -0.6931471805599453, -0.5543552444685271, -0.3132616875182228, -0.006715348489118068, -4.248354255291589e-18, 0.0]),
+        (gelu, [-0.0, -0.0, -1.4332578593959695e-06, -0.15865525393145705, -4.999999960105772e-09, 0.0, 0.1853734266566858, 0.8413447460685429, 4.999998566742141, 40.0, 800.0]),
+        (gelu_tanh, [0.0, 0.0, -2.291796196629506e-07, -0.1588080093917233, -4.999999960105772e-09, 0.0, 0.18537092354275925, 0.8411919906082767, 4.999999770820381, 40.0, 800.0]),
+        (silu, [-0.0, -1.6993417021166355e-16, -0.03346425462142428, -0.2689414213699951, -4.9999999750000005e-09, 0.0, 0.17233275504349768, 0.7310585786300049, 4.966535745378576, 40.0, 800.0]),
+        (selu, [-1.7580993408473768, -1.7580993408473768, -1.7462533606696202, -1.1113307378125628, -1.7580993320568802e-08, 0.0, 0.31521029620664415, 1.0507009873554805, 5.253504936777403, 42.02803949421922, 840.5607898843843]),
+        (mish, [-0.0, -1.6993417021166355e-16, -0.033576237730161704, -0.3034014613741089, -5.999999968e-09, 0.0, 0.20800137234811503, 0.8650983882673103, 4.999552077529406, 40.0, 800.0]),
+    ];
+    let mut y = [0.0; 11];
+    for (f, e) in expected.iter()
+    {
+        f(&x, &mut y);
+        for i in 0..x.len()
+        {
+            assert!(relative_eq!(y[i], e[i], epsilon = 1e-300, max_relative = 1e-14), "x = {}: {} != {}", x[i], y[i], e[i]);
+        }
+    }
+
+    let x = [f64::NEG_INFINITY, f64::INFINITY, f64::NAN];
+    sigmoid(&x, &mut y[..3]);
+    assert_eq!(&y[..2], &[0.0, 1.0]);
+    assert!(f64::is_nan(y[2]));
+    softplus(&x, &mut y[..3]);
+    assert_eq!(&y[..2], &[0.0, f64::INFINITY]);
+    assert!(f64::is_nan(y[2]));
+    log_sigmoid(&x, &mut y[..3]);
+    assert_eq!(&y[..2], &[f64::NEG_INFINITY, 0.0]);
+    assert!(f64::is_nan(y[2]));
+    for f in [gelu, gelu_tanh, silu, mish]
+    {
+        f(&x, &mut y[..3]);
+        assert_eq!(&y[..2], &[0.0, f64::INFINITY]);
+        assert!(f64::is_nan(y[2]));
+    }
+    elu(&x, 0.5, &mut y[..3]);
+    assert_eq!(&y[..2], &[-0.5, f64::INFINITY]);
+    assert!(f64::is_nan(y[2]));
+
+    let x: Vec<f64> = (0..1003).map(|i| -20.0 + 0.04 * (i as f64)).collect();
+    let n = x.len();
+    let mut y = vec![0.0; n];
+    let mut z = vec![0.0; n];
+    silu(&x, &mut y);
+    swish_par(&x, 1.0, &mut z);
+    assert!((0..n).all(|i| relative_eq!(y[i], z[i], max_relative = 1e-15)));
+    let xs: Vec<f64> = x.iter().map(|v| 1.7 * v).collect();
+    silu(&xs, &mut y);
+    swish(&x, 1.7, &mut z);
+    assert!((0..n).all(|i| relative_eq!(y[i], 1.7 * z[i], max_relative = 1e-14)));
+    elu(&x, 1.6732632423543772, &mut y);
+    selu_par(&x, &mut z);
+    assert!((0..n).all(|i| relative_eq!(1.0507009873554805 * y[i], z[i], max_relative = 1e-15)));
+    let xn: Vec<f64> = x.iter().map(|v| -v).collect();
+    softplus(&x, &mut y);
+    softplus(&xn, &mut z);
+    assert!((0..n).all(|i| relative_eq!(y[i] - z[i], x[i], epsilon = 1e-13)));
+    sigmoid(&x, &mut y);
+    sigmoid(&xn, &mut z);
+    assert!((0..n).all(|i| relative_eq!(y[i] + z[i], 1.0, epsilon = 1e-15)));
+}
+
+
+#[test]
+fn softmax_test()
+{
+    let x: Vec<f64> = (0..13).map(|i| 0.5 * (i as f64) - 3.7 + ((i % 3) as f64) * 0.11).collect();
+    assert!(relative_eq!(logsumexp(&x), 3.3239763383962115, max_relative = 1e-15));
+    let xs: Vec<f64> = x.iter().map(|v| v + 1000.0).collect();
+    assert!(relative_eq!(logsumexp(&xs), 1003.3239763383962115, max_relative = 1e-15));
+    let xs: Vec<f64> = x.iter().map(|v| v - 1000.0).collect();
+    assert!(relative_eq!(logsumexp(&xs), -996.6760236616037885, max_relative = 1e-15));
+    assert_eq!(logsumexp(&[]), f64::NEG_INFINITY);
+    assert_eq!(logsumexp(&[f64::NEG_INFINITY, 2.0]), 2.0);
+    assert_eq!(logsumexp(&[f64::INFINITY, 2.0]), f64::INFINITY);
+    assert!(f64::is_nan(logsumexp(&[1.0, f64::NAN, 2.0])));
+
+    for n in [1, 7, 8, 9, 16, 23, 100, 1003, 100_003]
+    {
+        let x: Vec<f64> = (0..n).map(|i| 40.0 * ((i as f64) * 0.37).sin() + 700.0).collect();
+        let m = x.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let s: f64 = x.iter().map(|v| (v - m).exp()).sum();
+        let lse = m + s.ln();
+        assert!(relative_eq!(logsumexp(&x), lse, max_relative = 1e-15));
+        assert!(relative_eq!(logsumexp_par(&x), lse, max_relative = 1e-15));
+
+        let mut y = vec![0.0; n];
+        let mut z = vec![0.0; n];
+        softmax(&x, &mut y);
+        softmax_par(&x, &mut z);
+        assert!((0..n).all(|i| relative_eq!(y[i], (x[i] - m).exp() / s, max_relative = 1e-13)));
+        assert!((0..n).all(|i| relative_eq!(y[i], z[i], max_relative = 1e-13)));
+        assert!(relative_eq!(y.iter().sum::<f64>(), 1.0, max_relative = 1e-13));
+        log_softmax(&x, &mut y);
+        log_softmax_par(&x, &mut z);
+        assert!((0..n).all(|i| relative_eq!(y[i], x[i] - lse, epsilon = 1e-12)));
+        assert!((0..n).all(|i| relative_eq!(y[i], z[i], epsilon = 1e-12)));
+    }
+
+    let mut y = [0.0; 3];
+    softmax(&[f64::NEG_INFINITY, 0.0, 0.0], &mut y);
+    assert_eq!(y, [0.0, 0.5, 0.5]);
+    log_softmax(&[f64::NEG_INFINITY, 0.0, 0.0], &mut y);
+    assert_eq!(y, [f64::NEG_INFINITY, -std::f64::consts::LN_2, -std::f64::consts::LN_2]);
+
+    let (rows, cols) = (37, 21);
+    let x: Vec<f64> = (0..rows * cols).map(|i| 5.0 * ((i as f64) * 1.3).cos()).collect();
+    let mut lse = vec![0.0; rows];
+    let mut lse_par = vec![0.0; rows];
+    logsumexp_rows(&x, cols, &mut lse);
+    logsumexp_rows_par(&x, cols, &mut lse_par);
+    assert_eq!(lse, lse_par);
+    assert!((0..rows).all(|r| lse[r] == logsumexp(&x[r * cols..(r + 1) * cols])));
+
+    let mut y = vec![0.0; rows * cols];
+    let mut z = vec![0.0; rows * cols];
+    let mut w = vec![0.0; cols];
+    softmax_rows(&x, cols, &mut y);
+    softmax_rows_par(&x, cols, &mut z);
+    assert_eq!(y, z);
+    for r in 0..rows
+    {
+        softmax(&x[r * cols..(r + 1) * cols], &mut w);
+        assert_eq!(&y[r * cols..(r + 1) * cols], &w[..]);
+    }
+    log_softmax_rows(&x, cols, &mut y);
+    log_softmax_rows_par(&x, cols, &mut z);
+    assert_eq!(y, z);
+    assert!((0..rows * cols).all(|i| relative_eq!(y[i], x[i] - lse[i / cols], epsilon = 1e-14)));
+}
+
+
+#[test]
+fn blas1_test()
+{
+    for n in [0, 1, 7, 8, 15, 31, 32, 33, 100, 1003]
+    {
+        let x: Vec<f64> = (0..n).map(|i| ((i as f64) * 0.37).sin() * (1.0 + (i % 5) as f64)).collect();
+        let y0: Vec<f64> = (0..n).map(|i| ((i as f64) * 0.11).cos() - 0.5).collect();
+
+        let mut y = y0.clone();
+        axpy(1.5, &x, &mut y);
+        assert!((0..n).all(|i| y[i] == 1.5f64.mul_add(x[i], y0[i])));
+        let mut y = y0.clone();
+        axpby(1.5, &x, -0.25, &mut y);
+        assert!((0..n).all(|i| relative_eq!(y[i], 1.5 * x[i] - 0.25 * y0[i], epsilon = 1e-15)));
+        let mut y = y0.clone();
+        scal(-3.0, &mut y);
+        assert!((0..n).all(|i| y[i] == -3.0 * y0[i]));
+        copy(&x, &mut y);
+        assert_eq!(x, y);
+        let (mut a, mut b) = (x.clone(), y0.clone());
+        swap(&mut a, &mut b);
+        assert_eq!((a, b), (y0.clone(), x.clone()));
+
+        assert!(relative_eq!(asum(&x), x.iter().map(|v| v.abs()).sum::<f64>(), max_relative = 1e-14));
+        assert!(relative_eq!(nrm2(&x), x.iter().map(|v| v * v).sum::<f64>().sqrt(), max_relative = 1e-14));
+
+        let amax = x.iter().map(|v| v.abs()).fold(-1.0, f64::max);
+        let amin = x.iter().map(|v| v.abs()).fold(f64::INFINITY, f64::min);
+        assert_eq!(iamax(&x), x.iter().position(|v| v.abs() == amax));
+        assert_eq!(iamin(&x), x.iter().position(|v| v.abs() == amin));
+
+        let (mut a, mut b) = (x.clone(), y0.clone());
+        rot(&mut a, &mut b, 0.6, 0.8);
+        assert!((0..n).all(|i| relative_eq!(a[i], 0.6 * x[i] + 0.8 * y0[i], epsilon = 1e-15)));
+        assert!((0..n).all(|i| relative_eq!(b[i], 0.6 * y0[i] - 0.8 * x[i], epsilon = 1e-15)));
+    }
+
+    assert_eq!(nrm2(&[3e300, 4e300]), 5e300);
+    assert_eq!(nrm2(&[3e-310, 4e-310]), 5e-310);
+    assert!(relative_eq!(nrm2(&[1e300; 100]), 1e301, max_relative = 1e-15));
+    assert_eq!(nrm2(&[0.0; 9]), 0.0);
+    assert_eq!(nrm2(&[1.0, f64::NEG_INFINITY]), f64::INFINITY);
+    assert!(f64::is_nan(nrm2(&[f64::NAN, 0.0])));
+    assert!(f64::is_nan(nrm2(&[1.0, f64::NAN, 2.0])));
+
+    let mut x = [1.0; 40];
+    x[3] = -7.0;
+    x[21] = 7.0;
+    x[30] = f64::NAN;
+    x[35] = 0.5;
+    x[38] = -0.5;
+    assert_eq!(iamax(&x), Some(3));
+    assert_eq!(iamin(&x), Some(35));
+    x[0] = f64::NAN;
+    assert_eq!(iamax(&x[..5]), Some(3));
+    assert_eq!(iamax(&[]), None);
+
+    let (mut a, mut b, mut c, mut s) = (3.0, 4.0, 0.0, 0.0);
+    rotg(&mut a, &mut b, &mut c, &mut s);
+    assert!(relative_eq!(a, 5.0) && relative_eq!(c, 0.6) && relative_eq!(s, 0.8) && relative_eq!(b, 1.0 / 0.6));
+    let (mut a, mut b) = (-4.0, 3.0);
+    rotg(&mut a, &mut b, &mut c, &mut s);
+    assert!(relative_eq!(a, -5.0) && relative_eq!(c, 0.8) && relative_eq!(s, -0.6) && relative_eq!(b, -0.6));
+    let (mut a, mut b) = (1e300, 1e300);
+    rotg(&mut a, &mut b, &mut c, &mut s);
+    assert!(relative_eq!(a, 1e300 * std::f64::consts::SQRT_2) && relative_eq!(c, std::f64::consts::FRAC_1_SQRT_2));
+    let (mut a, mut b) = (0.0, 2.0);
+    rotg(&mut a, &mut b, &mut c, &mut s);
+    assert_eq!((a, b, c, s), (2.0, 1.0, 0.0, 1.0));
+}
+
+
+#[test]
+fn dot_test()
+{
+    for n in [0, 5, 16, 63, 64, 1000, 100_003]
+    {
+        let x: Vec<f64> = (0..n).map(|i| ((i as f64) * 0.37).sin()).collect();
+        let y: Vec<f64> = (0..n).map(|i| ((i as f64) * 0.11).cos() - 0.25).collect();
+        let real: f64 = x.iter().zip(&y).map(|(a, b)| a * b).sum();
+        let r = unsafe{ dot(&x, &y) };
+        assert!(relative_eq!(r, real, epsilon = 1e-10));
+        assert!(relative_eq!(dot_par(&x, &y), real, epsilon = 1e-10));
+        assert!(relative_eq!(dot_par_chunked(&x, &y, 7), real, epsilon = 1e-10));
+        assert_eq!(dot_par_chunked(&x, &y, 1), r);
+        assert_eq!(dot_par_chunked(&x, &y, 13), dot_par_chunked(&x, &y, 13));
+
+        let xf: Vec<f32> = x.iter().map(|v| *v as f32).collect();
+        let yf: Vec<f32> = y.iter().map(|v| *v as f32).collect();
+        let realf: f64 = xf.iter().zip(&yf).map(|(a, b)| (*a as f64) * (*b as f64)).sum();
+        assert!(relative_eq!(dot_f32_f64acc(&xf, &yf), realf, epsilon = 1e-10));
+        assert!(relative_eq!(dot_f32(&xf, &yf) as f64, realf, epsilon = 1e-5 * (n as f64).max(1.0).sqrt()));
+    }
+
+    // A running f32 sum would stall at 2^24 here.
+    let x = vec![1.0f32; 1 << 25];
+    assert_eq!(dot_f32_f64acc(&x, &x), (1u64 << 25) as f64);
+}
+
+
+#[test]
+fn compensated_dot_test()
+{
+    let x = [1e16, 1.0, -1e16, 3.0, 1e-3];
+    assert!(relative_eq!(sum_kahan(&x), 4.001, max_relative = 1e-15));
+    let x: Vec<f64> = (0..1003).flat_map(|i| [1e16, (i % 7) as f64 * 0.1, -1e16]).collect();
+    let real: f64 = (0..1003).map(|i| (i % 7) as f64 * 0.1).sum();
+    assert!(relative_eq!(sum_kahan(&x), real, max_relative = 1e-14));
+    assert_eq!(sum_kahan(&[]), 0.0);
+
+    let x = [1e20, 1.0, -1e20, 1e-5];
+    let y = [1.0, 1.0, 1.0, 3.0];
+    assert_eq!(dot_compensated(&x, &y), 1.00003);
+    let x: Vec<f64> = (0..1003).flat_map(|i| [1e20 + (i as f64) * 1e5, 1.0 / (1.0 + i as f64), -1e20]).collect();
+    let y: Vec<f64> = (0..1003).flat_map(|i| [1.0 + 1e-16 * (i as f64), 1.0, 1.0]).collect();
+    let r = dot_reproducible(&x, &y);
+    assert!(relative_eq!(dot_compensated(&x, &y), r, max_relative = 1e-15));
+
+    let n = 1000;
+    let mut x: Vec<f64> = (0..n).map(|i| (((i * 7919) % 1000) as f64 - 500.0) * 2.0f64.powi(((i * 37) % 80) as i32 - 40)).collect();
+    let mut y: Vec<f64> = (0..n).map(|i| (((i * 104729) % 997) as f64 - 498.0) / 3.0).collect();
+    x.extend([1e308, -1e308, 1e-300, 5e-324, 0.1]);
+    y.extend([10.0, 10.0, 1e-300, 0.5, 0.1]);
+    let r = dot_reproducible(&x, &y);
+    assert_eq!(r, -7.2039613761076216e+16);
+    assert_eq!(dot_reproducible_par(&x, &y).to_bits(), r.to_bits());
+    for chunks in [1, 2, 3, 7, 64, 1005] {
+        assert_eq!(dot_reproducible_par_chunked(&x, &y, chunks).to_bits(), r.to_bits());
+    }
+    x.reverse();
+    y.reverse();
+    assert_eq!(dot_reproducible(&x, &y).to_bits(), r.to_bits());
+
+    // Halfway cases and the sticky bit below the last place of a subnormal.
+    assert_eq!(dot_reproducible(&[5e-324], &[0.5]), 0.0);
+    assert_eq!(dot_reproducible(&[5e-324], &[1.5]), 1e-323);
+    assert_eq!(dot_reproducible(&[1e-300, 5e-324], &[1e-300, 0.5]), 5e-324);
+    assert_eq!(dot_reproducible(&[1.0, 1.0], &[1.0, f64::EPSILON / 2.0]), 1.0);
+    assert_eq!(dot_reproducible(&[1.0, 1.0, 1e-300], &[1.0, f64::EPSILON / 2.0, 1e-300]), 1.0 + f64::EPSILON);
+    assert_eq!(dot_reproducible(&[-3.0, 1.0], &[1.0, 2.0]), -1.0);
+    assert_eq!(dot_reproducible(&[f64::MAX, f64::MAX], &[1.0, 1.0]), f64::INFINITY);
+    assert_eq!(dot_reproducible(&[f64::MAX, f64::MAX, -f64::MAX], &[2.0, 1.0, 2.0]), f64::MAX);
+    assert_eq!(dot_reproducible(&[], &[]), 0.0);
+    assert_eq!(dot_reproducible(&[1.0, f64::INFINITY], &[1.0, -2.0]), f64::NEG_INFINITY);
+    assert!(f64::is_nan(dot_reproducible(&[f64::INFINITY, f64::NEG_INFINITY], &[1.0, 1.0])));
+    assert!(f64::is_nan(dot_reproducible(&[f64::INFINITY, 1.0], &[0.0, 1.0])));
+}
+
+
+#[test]
+fn gemv_test()
+{
+    let (m, n, lda) = (37, 23, 29);
+    let a: Vec<f64> = (0..m * lda).map(|i| ((i as f64) * 0.7).sin()).collect();
+    let xn: Vec<f64> = (0..n).map(|i| ((i as f64) * 0.3).cos()).collect();
+    let xm: Vec<f64> = (0..m).map(|i| 1.0 - 0.05 * (i as f64)).collect();
+    let y0m: Vec<f64> = (0..m).map(|i| (i as f64) * 0.5).collect();
+    let y0n: Vec<f64> = (0..n).map(|i| 2.0 - (i as f64)).collect();
+
+    let real: Vec<f64> = (0..m).map(|i| 1.5 * (0..n).map(|j| a[i * lda + j] * xn[j]).sum::<f64>() - 0.5 * y0m[i]).collect();
+    let mut y = y0m.clone();
+    gemv(Layout::RowMajor, Transpose::NoTrans, 1.5, &a, lda, &xn, -0.5, &mut y);
+    assert!((0..m).all(|i| relative_eq!(y[i], real[i], epsilon = 1e-13)));
+    let mut y = y0m.clone();
+    gemv_par(Layout::RowMajor, Transpose::NoTrans, 1.5, &a, lda, &xn, -0.5, &mut y);
+    assert!((0..m).all(|i| relative_eq!(y[i], real[i], epsilon = 1e-13)));
+
+    let real: Vec<f64> = (0..n).map(|j| 1.5 * (0..m).map(|i| a[i * lda + j] * xm[i]).sum::<f64>() - 0.5 * y0n[j]).collect();
+    let mut y = y0n.clone();
+    gemv(Layout::RowMajor, Transpose::Trans, 1.5, &a, lda, &xm, -0.5, &mut y);
+    assert!((0..n).all(|j| relative_eq!(y[j], real[j], epsilon = 1e-13)));
+    let mut y = y0n.clone();
+    gemv_par(Layout::RowMajor, Transpose::Trans, 1.5, &a, lda, &xm, -0.5, &mut y);
+    assert!((0..n).all(|j| relative_eq!(y[j], real[j], epsilon = 1e-13)));
+
+    // beta = 0 overwrites y without reading it.
+    let mut y = vec![f64::NAN; n];
+    gemv(Layout::RowMajor, Transpose::Trans, 1.0, &a, lda, &xm, 0.0, &mut y);
+    assert!(y.iter().all(|v| v.is_finite()));
+
+    // The 2x3 matrix [1 2 3; 4 5 6] stored column-major, with a padded leading dimension.
+    let a = [1.0, 4.0, f64::NAN, 2.0, 5.0, f64::NAN, 3.0, 6.0, f64::NAN];
+    let mut y = [0.0; 2];
+    gemv(Layout::ColMajor, Transpose::NoTrans, 1.0, &a, 3, &[1.0, 1.0, 1.0], 0.0, &mut y);
+    assert_eq!(y, [6.0, 15.0]);
+    let mut y = [1.0; 3];
+    gemv(Layout::ColMajor, Transpose::Trans, 2.0, &a, 3, &[1.0, -1.0], 1.0, &mut y);
+    assert_eq!(y, [-5.0, -5.0, -5.0]);
+    let mut y = [1.0; 3];
+    gemv_par(Layout::ColMajor, Transpose::Trans, 2.0, &a, 3, &[1.0, -1.0], 1.0, &mut y);
+    assert_eq!(y, [-5.0, -5.0, -5.0]);
+
+    let (m, n) = (5, 20_000);
+    let a: Vec<f64> = (0..m * n).map(|i| ((i as f64) * 0.7).sin()).collect();
+    let mut y = vec![1.0; n];
+    let mut z = vec![1.0; n];
+    gemv(Layout::RowMajor, Transpose::Trans, 1.5, &a, n, &xm[..m], 2.0, &mut y);
+    gemv_par(Layout::RowMajor, Transpose::Trans, 1.5, &a, n, &xm[..m], 2.0, &mut z);
+    assert_eq!(y, z);
+}
+
+
+#[test]
+fn gemm_test()
+{
+    // Where element (i, j) of a matrix lives, and how many leading-dimension strides a rows x cols matrix spans.
+    let at = |layout: Layout, i: usize, j: usize, ld: usize| if layout == Layout::RowMajor { i * ld + j } else { j * ld + i };
+    let lines = |layout: Layout, rows: usize, cols: usize| if layout == Layout::RowMajor { rows } else { cols };
+    let naive = |layout: Layout, ta: Transpose, tb: Transpose, m: usize, n: usize, k: usize, alpha: f64, a: &[f64], lda: usize, b: &[f64], ldb: usize, beta: f64, c: &mut [f64], ldc: usize| {
+        for i in 0..m {
+            for j in 0..n {
+                let mut s = 0.0;
+                for p in 0..k {
+                    let aa = if ta == Transpose::NoTrans { a[at(layout, i, p, lda)] } else { a[at(layout, p, i, lda)] };
+                    let bb = if tb == Transpose::NoTrans { b[at(layout, p, j, ldb)] } else { b[at(layout, j, p, ldb)] };
+                    s += aa * bb;
+                }
+                let ij = at(layout, i, j, ldc);
+                c[ij] = alpha * s + if beta == 0.0 { 0.0 } else { beta * c[ij] };
+            }
+        }
+    };
+
+    let shapes = [(1, 1, 1), (8, 16, 4), (7, 15, 3), (9, 17, 33), (130, 37, 300), (33, 2050, 20), (0, 5, 5), (5, 5, 0)];
+
+    for layout in [Layout::RowMajor, Layout::ColMajor] {
+        for (m, n, k) in shapes {
+            for ta in [Transpose::NoTrans, Transpose::Trans] {
+                for tb in [Transpose::NoTrans, Transpose::Trans] {
+                    let (arows, acols) = if ta == Transpose::NoTrans { (m, k) } else { (k, m) };
+                    let (brows, bcols) = if tb == Transpose::NoTrans { (k, n) } else { (n, k) };
+                    let lda = lines(layout, acols, arows) + 3;
+                    let ldb = lines(layout, bcols, brows) + 2;
+                    let ldc = lines(layout, n, m) + 1;
+                    let a: Vec<f64> = (0..lines(layout, arows, acols) * lda).map(|i| ((i as f64) * 0.37).sin()).collect();
+                    let b: Vec<f64> = (0..lines(layout, brows, bcols) * ldb).map(|i| ((i as f64) * 0.11).cos()).collect();
+                    let c0: Vec<f64> = (0..lines(layout, m, n) * ldc).map(|i| (i % 13) as f64 - 6.0).collect();
+
+                    let mut real = c0.clone();
+                    naive(layout, ta, tb, m, n, k, 1.25, &a, lda, &b, ldb, -0.5, &mut real, ldc);
+                    let mut c = c0.clone();
+                    gemm(layout, ta, tb, m, n, k, 1.25, &a, lda, &b, ldb, -0.5, &mut c, ldc);
+                    assert!((0..c.len()).all(|i| relative_eq!(c[i], real[i], epsilon = 1e-11)));
+                    let mut cp = c0.clone();
+                    gemm_par(layout, ta, tb, m, n, k, 1.25, &a, lda, &b, ldb, -0.5, &mut cp, ldc);
+                    assert_eq!(c, cp);
+                }
+            }
+        }
+    }
+
+    // beta = 0 ignores whatever was in C, and the padding past each row of C is left alone.
+    let a = [1.0, 2.0, 3.0, 4.0];
+    let b = [5.0, 6.0, 7.0, 8.0];
+    let mut c = [f64::NAN, f64::NAN, -1.0, f64::NAN, f64::NAN, -1.0];
+    gemm(Layout::RowMajor, Transpose::NoTrans, Transpose::NoTrans, 2, 2, 2, 1.0, &a, 2, &b, 2, 0.0, &mut c, 3);
+    assert_eq!(c, [19.0, 22.0, -1.0, 43.0, 50.0, -1.0]);
+}
+
+
+#[test]
+fn cholesky_test()
+{
+    let n = 37;
+    let m: Vec<f64> = (0..n * n).map(|i| ((i as f64) * 0.37).sin()).collect();
+    let mut a = vec![0.0; n * n];
+    gemm(Layout::RowMajor, Transpose::NoTrans, Transpose::Trans, n, n, n, 1.0, &m, n, &m, n, 0.0, &mut a, n);
+    for i in 0..n {
+        a[i * n + i] += n as f64;
+    }
+
+    let mut l = a.clone();
+    cholesky(&mut l, n).unwrap();
+    assert!((0..n).all(|i| (i + 1..n).all(|j| l[i * n + j] == 0.0) && l[i * n + i] > 0.0));
+    let mut llt = vec![0.0; n * n];
+    gemm(Layout::RowMajor, Transpose::NoTrans, Transpose::Trans, n, n, n, 1.0, &l, n, &l, n, 0.0, &mut llt, n);
+    assert!((0..n * n).all(|i| relative_eq!(llt[i], a[i], epsilon = 1e-12)));
+
+    let x: Vec<f64> = (0..n).map(|i| 1.0 - 0.1 * (i as f64)).collect();
+    let mut b = vec![0.0; n];
+    gemv(Layout::RowMajor, Transpose::NoTrans, 1.0, &a, n, &x, 0.0, &mut b);
+    cholesky_solve(&l, n, &mut b);
+    assert!((0..n).all(|i| relative_eq!(b[i], x[i], epsilon = 1e-12)));
+
+    let mut not_pd = [4.0, 2.0, 2.0, 1.0];
+    assert_eq!(cholesky(&mut not_pd, 2), Err(LinalgError::NotPositiveDefinite(1)));
+    let mut not_pd = [1.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 1.0];
+    assert_eq!(cholesky(&mut not_pd, 3), Err(LinalgError::NotPositiveDefinite(1)));
+    assert!(cholesky(&mut [f64::NAN], 1).is_err());
+    assert!(cholesky(&mut [], 0).is_ok());
+
+    // Every triangle, transpose and diagonal combination against a full solve through gemv.
+    let lda = n + 3;
+    let t: Vec<f64> = (0..n * lda).map(|i| ((i as f64) * 0.11).cos() + if i % (lda + 1) == 0 { 4.0 } else { 0.0 }).collect();
+    let nrhs = 5;
+    let xs: Vec<f64> = (0..n * nrhs).map(|i| ((i as f64) * 0.7).sin()).collect();
+
+    for uplo in [Triangle::Lower, Triangle::Upper] {
+        for trans in [Transpose::NoTrans, Transpose::Trans] {
+            for diag in [Diagonal::NonUnit, Diagonal::Unit] {
+                let mut full = vec![0.0; n * n];
+                for i in 0..n {
+                    for j in 0..n {
+                        let inside = if uplo == Triangle::Lower { j <= i } else { j >= i };
+                        full[i * n + j] = if i == j && diag == Diagonal::Unit { 1.0 } else if inside { t[i * lda + j] } else { 0.0 };
+                    }
+                }
+
+                let mut b = vec![0.0; n];
+                gemv(Layout::RowMajor, trans, 1.0, &full, n, &xs[..n], 0.0, &mut b);
+                trsv(Layout::RowMajor, uplo, trans, diag, &t, lda, &mut b);
+                assert!((0..n).all(|i| relative_eq!(b[i], xs[i], epsilon = 1e-12)));
+
+                let ldb = nrhs + 2;
+                let mut bm = vec![f64::NAN; n * ldb];
+                for j in 0..nrhs {
+                    let mut col = vec![0.0; n];
+                    let xcol: Vec<f64> = (0..n).map(|i| xs[i * nrhs + j]).collect();
+                    gemv(Layout::RowMajor, trans, 2.0, &full, n, &xcol, 0.0, &mut col);
+                    (0..n).for_each(|i| bm[i * ldb + j] = col[i]);
+                }
+                trsm(Layout::RowMajor, uplo, trans, diag, n, nrhs, 0.5, &t, lda, &mut bm, ldb);
+                assert!((0..n).all(|i| (0..nrhs).all(|j| relative_eq!(bm[i * ldb + j], xs[i * nrhs + j], epsilon = 1e-12))));
+                assert!((0..n).all(|i| bm[i * ldb + nrhs].is_nan()));
+
+                // The same triangle stored column-major is t read as its transpose.
+                let tc: Vec<f64> = (0..n * lda).map(|p| if p % lda < n { t[(p % lda) * lda + p / lda] } else { f64::NAN }).collect();
+                let mut b = vec![0.0; n];
+                gemv(Layout::RowMajor, trans, 1.0, &full, n, &xs[..n], 0.0, &mut b);
+                trsv(Layout::ColMajor, uplo, trans, diag, &tc, lda, &mut b);
+                assert!((0..n).all(|i| relative_eq!(b[i], xs[i], epsilon = 1e-12)));
+
+                let ldb = n + 2;
+                let mut bm = vec![f64::NAN; nrhs * ldb];
+                for j in 0..nrhs {
+                    let xcol: Vec<f64> = (0..n).map(|i| xs[i * nrhs + j]).collect();
+                    gemv(Layout::RowMajor, trans, 2.0, &full, n, &xcol, 0.0, &mut bm[j * ldb..j * ldb + n]);
+                }
+                trsm(Layout::ColMajor, uplo, trans, diag, n, nrhs, 0.5, &tc, lda, &mut bm, ldb);
+                assert!((0..n).all(|i| (0..nrhs).all(|j| relative_eq!(bm[j * ldb + i], xs[i * nrhs + j], epsilon = 1e-12))));
+                assert!((0..nrhs).all(|j| bm[j * ldb + n].is_nan()));
+            }
+        }
+    }
+}
+
+
+#[test]
+fn lu_test()
+{
+    for n in [1, 2, 9, 37, 120] {
+        let a: Vec<f64> = (0..n * n).map(|i| ((i as f64) * 0.37).sin() + ((i * i) as f64 * 0.013).cos()).collect();
+
+        let mut lu = a.clone();
+        let piv = lu_factor(&mut lu, n).unwrap();
+        assert!((0..n).all(|k| piv[k] >= k && piv[k] < n));
+        assert!((0..n).all(|i| (0..i).all(|j| lu[i * n + j].abs() <= 1.0)));
+
+        let x: Vec<f64> = (0..n).map(|i| 1.0 - 0.1 * (i as f64)).collect();
+        let mut b = vec![0.0; n];
+        gemv(Layout::RowMajor, Transpose::NoTrans, 1.0, &a, n, &x, 0.0, &mut b);
+        lu_solve(&lu, n, &piv, &mut b);
+        assert!((0..n).all(|i| relative_eq!(b[i], x[i], epsilon = 1e-9)));
+
+        let inv = inverse(&a, n).unwrap();
+        let mut eye = vec![0.0; n * n];
+        gemm(Layout::RowMajor, Transpose::NoTrans, Transpose::NoTrans, n, n, n, 1.0, &a, n, &inv, n, 0.0, &mut eye, n);
+        assert!((0..n * n).all(|i| relative_eq!(eye[i], if i % (n + 1) == 0 { 1.0 } else { 0.0 }, epsilon = 1e-9)));
+    }
+
+    assert_eq!(det(&[3.0], 1), 3.0);
+    assert!(relative_eq!(det(&[1.0, 2.0, 3.0, 4.0], 2), -2.0, epsilon = 1e-15));
+    assert!(relative_eq!(det(&[0.0, 1.0, 1.0, 0.0], 2), -1.0));
+    assert!(relative_eq!(det(&[2.0, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0], 3), 4.0, epsilon = 1e-14));
+    assert!(relative_eq!(det(&[0.0, 0.0, 1.0, 0.0, 2.0, 0.0, 3.0, 0.0, 0.0], 3), -6.0));
+    assert_eq!(det(&[1.0, 2.0, 2.0, 4.0], 2), 0.0);
+    assert_eq!(det(&[], 0), 1.0);
+
+    let mut singular = [1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0];
+    assert_eq!(lu_factor(&mut singular, 3), Err(LinalgError::Singular(2)));
+    assert_eq!(inverse(&[0.0, 0.0, 0.0, 1.0], 2), Err(LinalgError::Singular(0)));
+    assert_eq!(LinalgError::Singular(2).to_string(), "matrix is singular (zero pivot in column 2)");
+}
+
+
+#[test]
+fn sym_eigen_test()
+{
+    for n in [1, 2, 3, 10, 57, 200] {
+        let m: Vec<f64> = (0..n * n).map(|i| ((i as f64) * 0.37).sin()).collect();
+        let mut a = vec![0.0; n * n];
+        gemm(Layout::RowMajor, Transpose::NoTrans, Transpose::Trans, n, n, n, 1.0, &m, n, &m, n, 0.0, &mut a, n);
+        // Only the lower triangle should be read.
+        (0..n).for_each(|i| (i + 1..n).for_each(|j| a[i * n + j] = f64::NAN));
+        let full: Vec<f64> = (0..n * n).map(|i| if i % n > i / n { a[(i % n) * n + i / n] } else { a[i] }).collect();
+
+        let (w, v) = sym_eigen(&a, n);
+        assert!(w.windows(2).all(|p| p[0] <= p[1]));
+
+        let mut av = vec![0.0; n * n];
+        gemm(Layout::RowMajor, Transpose::NoTrans, Transpose::NoTrans, n, n, n, 1.0, &full, n, &v, n, 0.0, &mut av, n);
+        let scale = w.iter().fold(1.0f64, |s, x| s.max(x.abs()));
+        assert!((0..n * n).all(|i| relative_eq!(av[i], v[i] * w[i % n], epsilon = 1e-12 * scale)));
+
+        let mut vtv = vec![0.0; n * n];
+        gemm(Layout::RowMajor, Transpose::Trans, Transpose::NoTrans, n, n, n, 1.0, &v, n, &v, n, 0.0, &mut vtv, n);
+        assert!((0..n * n).all(|i| relative_eq!(vtv[i], if i % (n + 1) == 0 { 1.0 } else { 0.0 }, epsilon = 1e-12)));
+
+        let trace: f64 = (0..n).map(|i| full[i * n + i]).sum();
+        assert!(relative_eq!(w.iter().sum::<f64>(), trace, max_relative = 1e-12));
+    }
+
+    let (w, v) = sym_eigen(&[2.0, 0.0, 1.0, 2.0], 2);
+    assert!(relative_eq!(w[0], 1.0, epsilon = 1e-15) && relative_eq!(w[1], 3.0, epsilon = 1e-15));
+    assert!(relative_eq!(v[0].abs(), std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-15));
+    assert!(relative_eq!(v[0], -v[2], epsilon = 1e-15));
+
+    let (w, v) = sym_eigen(&[3.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 3.0], 3);
+    assert_eq!(w, vec![3.0; 3]);
+    assert_eq!(v, vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+
+    let (w, v) = sym_eigen(&[], 0);
+    assert!(w.is_empty() && v.is_empty());
+}
+
+
+#[test]
+fn batch_test()
+{
+    for n in [2, 3, 4] {
+        for count in [1, 8, 13, 100] {
+            let nn = n * n;
+            // Field-major storage: entry (i, j) of matrix b at a[(i * n + j) * count + b].
+            let a: Vec<f64> = (0..nn * count).map(|i| ((i as f64) * 0.37).sin() + if (i / count) % (n + 1) == 0 { 2.0 } else { 0.0 }).collect();
+            let x: Vec<f64> = (0..n * count).map(|i| ((i as f64) * 0.11).cos()).collect();
+            let matrix = |src: &[f64], b: usize| (0..nn).map(|f| src[f * count + b]).collect::<Vec<f64>>();
+
+            let mut d = vec![0.0; count];
+            batch_det(&a, n, &mut d);
+            let mut inv = vec![0.0; nn * count];
+            batch_inverse(&a, n, &mut inv);
+            let mut y = vec![0.0; n * count];
+            batch_matvec(&a, n, &x, &mut y);
+
+            for b in 0..count {
+                let m = matrix(&a, b);
+                assert!(relative_eq!(d[b], det(&m, n), epsilon = 1e-13, max_relative = 1e-13));
+                let expected = inverse(&m, n).unwrap();
+                let got = matrix(&inv, b);
+                assert!((0..nn).all(|f| relative_eq!(got[f], expected[f], epsilon = 1e-12, max_relative = 1e-12)));
+                for i in 0..n {
+                    let yi: f64 = (0..n).map(|j| m[i * n + j] * x[j * count + b]).sum();
+                    assert!(relative_eq!(y[i * count + b], yi, epsilon = 1e-14));
+                }
+            }
+
+            // A = M M^T + I is positive definite, with NaN in the upper triangle that must not be read.
+            let mut spd = vec![0.0; nn * count];
+            for b in 0..count {
+                let m = matrix(&a, b);
+                for i in 0..n {
+                    for j in 0..n {
+                        spd[(i * n + j) * count + b] = if j > i { f64::NAN } else {
+                            (0..n).map(|k| m[i * n + k] * m[j * n + k]).sum::<f64>() + if i == j { 1.0 } else { 0.0 }
+                        };
+                    }
+                }
+            }
+            let mut l = vec![0.0; nn * count];
+            batch_cholesky(&spd, n, &mut l);
+            for b in 0..count {
+                let mut expected = matrix(&spd, b);
+                cholesky(&mut expected, n).unwrap();
+                let got = matrix(&l, b);
+                assert!((0..nn).all(|f| relative_eq!(got[f], expected[f], epsilon = 1e-14, max_relative = 1e-13)));
+            }
+        }
+    }
+
+    // Bad lanes stay in their lane: [4 1; 1 2] is fine, [1 2; 2 4] is singular and [1 2; 2 1] is indefinite.
+    let count = 3;
+    let a = [4.0, 1.0, 1.0, 1.0, 2.0, 2.0, 1.0, 2.0, 2.0, 2.0, 4.0, 1.0];
+    let mut d = [0.0; 3];
+    batch_det(&a, 2, &mut d);
+    assert_eq!(d, [7.0, 0.0, -3.0]);
+    let mut inv = [0.0; 12];
+    batch_inverse(&a, 2, &mut inv);
+    assert!(relative_eq!(inv[0], 2.0 / 7.0) && relative_eq!(inv[3 * count], 4.0 / 7.0));
+    assert!(!inv[1].is_finite() && relative_eq!(inv[count + 2], 2.0 / 3.0));
+    let mut l = [0.0; 12];
+    batch_cholesky(&a, 2, &mut l);
+    assert!(relative_eq!(l[0], 2.0) && relative_eq!(l[2 * count], 0.5) && relative_eq!(l[3 * count], 1.75f64.sqrt()));
+    assert_eq!(l[count], 0.0);
+    assert!(l[3 * count + 2].is_nan() && relative_eq!(l[2 * count + 2], 2.0));
+}
+
+#[test]
+fn layout_test()
+{
+    // Shapes around the 8x8 tile and the cache block, so every kind of edge tile shows up.
+    for &(rows, cols) in &[(1, 1), (1, 13), (13, 1), (8, 8), (7, 9), (64, 64), (65, 63), (100, 37), (3, 200)] {
+        let a: Vec<f64> = (0..rows * cols).map(|i| i as f64 + 0.5).collect();
+        let mut b = vec![0.0; rows * cols];
+        transpose(&a, rows, cols, &mut b);
+        for i in 0..rows {
+            for j in 0..cols {
+                assert_eq!(b[j * rows + i], a[i * cols + j]);
+            }
+        }
+        let mut c = vec![0.0; rows * cols];
+        transpose(&b, cols, rows, &mut c);
+        assert_eq!(c, a);
+    }
+
+    // Records of bid, ask and size: one slice per field after the conversion, and back again.
+    let count = 21;
+    let fields = 3;
+    let aos: Vec<f64> = (0..count * fields).map(|i| (i / fields) as f64 * 10.0 + (i % fields) as f64).collect();
+    let mut soa = vec![0.0; count * fields];
+    aos_to_soa(&aos, fields, &mut soa);
+    for f in 0..fields {
+        assert!(soa[f * count..(f + 1) * count].iter().enumerate().all(|(r, &v)| v == r as f64 * 10.0 + f as f64));
+    }
+    let mut back = vec![0.0; count * fields];
+    soa_to_aos(&soa, fields, &mut back);
+    assert_eq!(back, aos);
+
+    // 2x2 matrices stored record by record feed straight into the batch functions.
+    let mats = [4.0, 1.0, 1.0, 2.0, 1.0, 2.0, 2.0, 4.0, 1.0, 2.0, 2.0, 1.0];
+    let mut a = [0.0; 12];
+    aos_to_soa(&mats, 4, &mut a);
+    let mut d = [0.0; 3];
+    batch_det(&a, 2, &mut d);
+    assert_eq!(d, [7.0, 0.0, -3.0]);
+}